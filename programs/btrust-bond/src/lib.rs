@@ -1,9 +1,17 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer, MintTo, Burn};
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::solana_program::sysvar::slot_hashes;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer, MintTo, Burn, FreezeAccount, ThawAccount, CloseAccount};
 use anchor_spl::associated_token::AssociatedToken;
 
 declare_id!("BTRUSTxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx");
 
+/// Pyth oracle program (mainnet)
+pub mod pyth {
+    use anchor_lang::prelude::*;
+    declare_id!("FsJ3A3u2vn5cTVofAjvy6y5kwABJAqYWpe4975bi2epH");
+}
+
 /// Platform fee in basis points (0.5% = 50 bps)
 const PLATFORM_FEE_BPS: u64 = 50;
 /// Basis points denominator
@@ -14,6 +22,51 @@ const MIN_COLLATERAL_RATIO_BPS: u64 = 15000;
 const LIQUIDATION_THRESHOLD_BPS: u64 = 12000;
 /// Liquidation penalty (10% = 1000 bps)
 const LIQUIDATION_PENALTY_BPS: u64 = 1000;
+/// Maximum share of outstanding principal repaid per liquidation (50% = 5000 bps)
+const LIQUIDATION_CLOSE_FACTOR_BPS: u64 = 5000;
+/// Fixed-point scale for the cumulative yield-per-bond index
+const YIELD_INDEX_SCALE: u128 = 1_000_000_000_000;
+/// Seconds in a (365 day) year
+const SECONDS_PER_YEAR: i64 = 31_536_000;
+/// Maximum platform fee the authority can set (5% = 500 bps)
+const MAX_PLATFORM_FEE_BPS: u64 = 500;
+/// Number of steps in a callable bond's call price schedule
+const MAX_CALL_SCHEDULE_STEPS: usize = 8;
+/// Number of put windows on a putable bond
+const MAX_PUT_WINDOWS: usize = 8;
+
+/// Per-bond pause flags
+pub const PAUSE_ISSUANCE: u8 = 1 << 0;
+pub const PAUSE_TRADING: u8 = 1 << 1;
+pub const PAUSE_YIELD: u8 = 1 << 2;
+pub const PAUSE_REDEMPTION: u8 = 1 << 3;
+pub const PAUSE_LIQUIDATION: u8 = 1 << 4;
+pub const PAUSE_ALL: u8 =
+    PAUSE_ISSUANCE | PAUSE_TRADING | PAUSE_YIELD | PAUSE_REDEMPTION | PAUSE_LIQUIDATION;
+
+/// Maximum age of an oracle price before it is rejected (seconds)
+const MAX_ORACLE_STALENESS_SECS: i64 = 60;
+/// Maximum oracle confidence interval relative to price (2% = 200 bps)
+const MAX_ORACLE_CONF_BPS: u64 = 200;
+/// Longest a reference rate may be published before a coupon fixing date (seconds)
+const MAX_RATE_STALENESS_SECS: i64 = 86_400;
+/// Reference rate publications kept so periods can be fixed after later updates
+const RATE_HISTORY_LEN: usize = 32;
+/// Maturity recorded for perpetual bonds
+const PERPETUAL_MATURITY: i64 = i64::MAX;
+/// Time after an auction ends for it to be settled before bidders may withdraw (7 days)
+const AUCTION_SETTLEMENT_WINDOW_SECS: i64 = 604_800;
+/// Slots after a sinking fund draw is committed until the slot whose hash seeds it
+const SINKING_DRAW_DELAY_SLOTS: u64 = 1;
+/// How long a revealed sinking fund draw stays open for allocation (30 days)
+const SINKING_DRAW_EXPIRY_SECS: i64 = 2_592_000;
+
+/// Length of a bondholder vote on an amendment (7 days)
+const VOTING_PERIOD_SECS: i64 = 604_800;
+/// Share of the snapshotted supply that must vote for a result to count (50% = 5000 bps)
+const VOTE_QUORUM_BPS: u64 = 5000;
+/// Share of votes cast that must approve an amendment (66.67% = 6667 bps)
+const VOTE_SUPERMAJORITY_BPS: u64 = 6667;
 
 #[program]
 pub mod btrust_bond {
@@ -27,6 +80,9 @@ pub mod btrust_bond {
         platform.total_bonds_issued = 0;
         platform.total_volume = 0;
         platform.fee_bps = PLATFORM_FEE_BPS;
+        platform.pending_authority = Pubkey::default();
+        platform.guardian = Pubkey::default();
+        platform.is_paused = false;
         platform.bump = ctx.bumps.platform;
         
         emit!(PlatformInitialized {
@@ -37,15 +93,345 @@ pub mod btrust_bond {
         Ok(())
     }
 
+    /// Update the platform fee charged on primary and secondary sales
+    pub fn set_fee_bps(ctx: Context<UpdatePlatform>, fee_bps: u64) -> Result<()> {
+        require!(fee_bps <= MAX_PLATFORM_FEE_BPS, BtrustError::FeeTooHigh);
+        
+        let platform = &mut ctx.accounts.platform;
+        let old_fee_bps = platform.fee_bps;
+        platform.fee_bps = fee_bps;
+        
+        emit!(PlatformFeeUpdated {
+            old_fee_bps,
+            new_fee_bps: fee_bps,
+        });
+        
+        Ok(())
+    }
+
+    /// Point platform fees at a new treasury account
+    pub fn set_treasury(ctx: Context<SetTreasury>) -> Result<()> {
+        require!(
+            ctx.accounts.new_treasury.key() != Pubkey::default(),
+            BtrustError::InvalidAddress
+        );
+        
+        let platform = &mut ctx.accounts.platform;
+        let old_treasury = platform.treasury;
+        platform.treasury = ctx.accounts.new_treasury.key();
+        
+        emit!(TreasuryUpdated {
+            old_treasury,
+            new_treasury: platform.treasury,
+        });
+        
+        Ok(())
+    }
+
+    /// Propose a new platform authority (step one of the handoff)
+    pub fn propose_authority(
+        ctx: Context<UpdatePlatform>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        require!(new_authority != Pubkey::default(), BtrustError::InvalidAddress);
+        
+        let platform = &mut ctx.accounts.platform;
+        platform.pending_authority = new_authority;
+        
+        emit!(AuthorityProposed {
+            authority: platform.authority,
+            pending_authority: new_authority,
+        });
+        
+        Ok(())
+    }
+
+    /// Accept a proposed platform authority (step two of the handoff)
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let platform = &mut ctx.accounts.platform;
+        require!(
+            platform.pending_authority != Pubkey::default(),
+            BtrustError::NoPendingAuthority
+        );
+        
+        let old_authority = platform.authority;
+        platform.authority = ctx.accounts.pending_authority.key();
+        platform.pending_authority = Pubkey::default();
+        
+        emit!(AuthorityTransferred {
+            old_authority,
+            new_authority: platform.authority,
+        });
+        
+        Ok(())
+    }
+
+    /// Set the guardian key allowed to pause the platform and bonds
+    pub fn set_guardian(ctx: Context<UpdatePlatform>, guardian: Pubkey) -> Result<()> {
+        let platform = &mut ctx.accounts.platform;
+        let old_guardian = platform.guardian;
+        platform.guardian = guardian;
+        
+        emit!(GuardianUpdated {
+            old_guardian,
+            new_guardian: guardian,
+        });
+        
+        Ok(())
+    }
+
+    /// Pause or unpause every bond on the platform
+    pub fn set_platform_paused(ctx: Context<SetPlatformPause>, paused: bool) -> Result<()> {
+        let platform = &mut ctx.accounts.platform;
+        platform.is_paused = paused;
+        
+        emit!(PlatformPauseUpdated {
+            paused,
+            updated_by: ctx.accounts.admin.key(),
+        });
+        
+        Ok(())
+    }
+
+    /// Set the pause flags for a single bond
+    pub fn set_bond_pause_flags(ctx: Context<SetBondPause>, pause_flags: u8) -> Result<()> {
+        require!(pause_flags & !PAUSE_ALL == 0, BtrustError::InvalidPauseFlags);
+        
+        let bond = &mut ctx.accounts.bond;
+        bond.pause_flags = pause_flags;
+        
+        emit!(BondPauseUpdated {
+            bond: bond.key(),
+            pause_flags,
+            updated_by: ctx.accounts.admin.key(),
+        });
+        
+        Ok(())
+    }
+
+    /// Create a mock price feed for local testing (`mock-oracle` builds only)
+    pub fn init_mock_price_feed(
+        ctx: Context<InitMockPriceFeed>,
+        price: i64,
+        conf: u64,
+        expo: i32,
+    ) -> Result<()> {
+        require!(cfg!(feature = "mock-oracle"), BtrustError::MockOracleDisabled);
+        
+        let feed = &mut ctx.accounts.price_feed;
+        feed.authority = ctx.accounts.authority.key();
+        feed.price = price;
+        feed.conf = conf;
+        feed.expo = expo;
+        feed.publish_time = Clock::get()?.unix_timestamp;
+        
+        Ok(())
+    }
+
+    /// Publish a new price to a mock price feed (`mock-oracle` builds only)
+    pub fn set_mock_price(
+        ctx: Context<SetMockPrice>,
+        price: i64,
+        conf: u64,
+    ) -> Result<()> {
+        require!(cfg!(feature = "mock-oracle"), BtrustError::MockOracleDisabled);
+        
+        let feed = &mut ctx.accounts.price_feed;
+        feed.price = price;
+        feed.conf = conf;
+        feed.publish_time = Clock::get()?.unix_timestamp;
+        
+        Ok(())
+    }
+
+    /// Create a reference rate account for floating-rate bonds
+    pub fn init_reference_rate(
+        ctx: Context<InitReferenceRate>,
+        publisher: Pubkey,
+        rate_bps: u64,
+    ) -> Result<()> {
+        let reference_rate = &mut ctx.accounts.reference_rate;
+        reference_rate.publisher = publisher;
+        reference_rate.publish(rate_bps, Clock::get()?.unix_timestamp);
+        
+        Ok(())
+    }
+
+    /// Publish a new value for a reference rate
+    pub fn publish_reference_rate(
+        ctx: Context<PublishReferenceRate>,
+        rate_bps: u64,
+    ) -> Result<()> {
+        let reference_rate = &mut ctx.accounts.reference_rate;
+        reference_rate.publish(rate_bps, Clock::get()?.unix_timestamp);
+        
+        emit!(ReferenceRatePublished {
+            reference_rate: reference_rate.key(),
+            rate_bps,
+            updated_at: reference_rate.updated_at,
+        });
+        
+        Ok(())
+    }
+
+    /// Register the price feed used to value a collateral mint
+    pub fn set_collateral_oracle(ctx: Context<SetCollateralOracle>) -> Result<()> {
+        // Only register feeds that are readable and live right now
+        load_oracle_price(&ctx.accounts.price_oracle, Clock::get()?.unix_timestamp)?;
+        
+        let collateral_oracle = &mut ctx.accounts.collateral_oracle;
+        collateral_oracle.collateral_mint = ctx.accounts.collateral_mint.key();
+        collateral_oracle.price_oracle = ctx.accounts.price_oracle.key();
+        collateral_oracle.bump = ctx.bumps.collateral_oracle;
+        
+        emit!(CollateralOracleSet {
+            collateral_mint: collateral_oracle.collateral_mint,
+            price_oracle: collateral_oracle.price_oracle,
+            set_by: ctx.accounts.authority.key(),
+        });
+        
+        Ok(())
+    }
+
+    /// Point a bond at the feed currently registered for its collateral mint
+    pub fn sync_bond_oracle(ctx: Context<SyncBondOracle>) -> Result<()> {
+        let bond = &mut ctx.accounts.bond;
+        let price_oracle = ctx.accounts.collateral_oracle.price_oracle;
+        require!(bond.price_oracle != price_oracle, BtrustError::OracleUnchanged);
+        
+        bond.price_oracle = price_oracle;
+        
+        emit!(BondOracleSynced {
+            bond: bond.key(),
+            price_oracle,
+        });
+        
+        Ok(())
+    }
+
     /// Create a new bond offering
     pub fn create_bond(
         ctx: Context<CreateBond>,
         args: CreateBondArgs,
     ) -> Result<()> {
+        require!(!ctx.accounts.platform.is_paused, BtrustError::PlatformPaused);
         require!(args.principal_amount > 0, BtrustError::InvalidPrincipal);
         require!(args.coupon_rate_bps <= 10000, BtrustError::InvalidCouponRate); // Max 100% APY
-        require!(args.maturity_timestamp > Clock::get()?.unix_timestamp, BtrustError::InvalidMaturity);
+        if args.is_zero_coupon {
+            require!(args.coupon_rate_bps == 0, BtrustError::InvalidCouponRate);
+            require!(args.amortization_bps == 0, BtrustError::InvalidAmortization);
+            require!(
+                args.discount_yield_bps > 0 && args.discount_yield_bps <= 10000,
+                BtrustError::InvalidDiscountYield
+            );
+        }
+        // Perpetual bonds never mature; they can only be retired by a call
+        let maturity_timestamp = if args.is_perpetual {
+            require!(args.maturity_timestamp == 0, BtrustError::InvalidMaturity);
+            require!(!args.is_zero_coupon, BtrustError::InvalidMaturity);
+            require!(args.amortization_bps == 0, BtrustError::InvalidAmortization);
+            PERPETUAL_MATURITY
+        } else {
+            args.maturity_timestamp
+        };
+        require!(maturity_timestamp > Clock::get()?.unix_timestamp, BtrustError::InvalidMaturity);
         require!(args.total_supply > 0, BtrustError::InvalidSupply);
+        require!(
+            matches!(args.payment_frequency, 1 | 2 | 4 | 12),
+            BtrustError::InvalidPaymentFrequency
+        );
+        require!(args.grace_period >= 0, BtrustError::InvalidGracePeriod);
+        require!(
+            args.amortization_bps <= BPS_DENOMINATOR,
+            BtrustError::InvalidAmortization
+        );
+        if args.subscription_end > 0 {
+            require!(
+                args.subscription_start < args.subscription_end
+                    && args.subscription_end <= maturity_timestamp,
+                BtrustError::InvalidSubscriptionWindow
+            );
+        }
+        if args.auction_end > 0 {
+            require!(args.subscription_end == 0, BtrustError::InvalidAuction);
+            require!(
+                args.auction_start < args.auction_end
+                    && args.auction_end <= maturity_timestamp,
+                BtrustError::InvalidAuction
+            );
+            require!(
+                args.auction_floor_price > 0
+                    && args.auction_start_price >= args.auction_floor_price,
+                BtrustError::InvalidAuction
+            );
+        }
+        if args.first_call_date > 0 {
+            require!(
+                args.first_call_date < maturity_timestamp,
+                BtrustError::InvalidCallSchedule
+            );
+            require!(args.call_notice_period >= 0, BtrustError::InvalidCallSchedule);
+            require!(
+                args.call_schedule_bps[0] >= BPS_DENOMINATOR,
+                BtrustError::InvalidCallSchedule
+            );
+            // Steps are at or above par, and a zero ends the schedule
+            let steps = args.call_schedule_bps.iter().take_while(|bps| **bps > 0);
+            for bps in steps {
+                require!(*bps >= BPS_DENOMINATOR, BtrustError::InvalidCallSchedule);
+            }
+        }
+        
+        if args.is_variable_rate {
+            require!(!args.is_zero_coupon, BtrustError::InvalidRateTerms);
+            require!(
+                args.reference_rate != Pubkey::default(),
+                BtrustError::InvalidReferenceRate
+            );
+            require!(
+                args.rate_floor_bps <= args.rate_cap_bps && args.rate_cap_bps <= 10000,
+                BtrustError::InvalidRateTerms
+            );
+        }
+        if args.conversion_mint != Pubkey::default() {
+            require!(args.conversion_ratio > 0, BtrustError::InvalidConversion);
+            require!(
+                args.conversion_start < args.conversion_end
+                    && args.conversion_end <= maturity_timestamp,
+                BtrustError::InvalidConversion
+            );
+        }
+        if args.put_price_bps > 0 {
+            require!(args.put_window_length > 0, BtrustError::InvalidPutSchedule);
+            require!(args.put_dates[0] > 0, BtrustError::InvalidPutSchedule);
+            // Windows open in order without overlapping, and a zero ends the schedule
+            let mut window_end = 0;
+            for open in args.put_dates.iter().take_while(|date| **date > 0) {
+                require!(*open >= window_end, BtrustError::InvalidPutSchedule);
+                window_end = open
+                    .checked_add(args.put_window_length)
+                    .ok_or(BtrustError::MathOverflow)?;
+            }
+            require!(
+                window_end <= maturity_timestamp,
+                BtrustError::InvalidPutSchedule
+            );
+        }
+        
+        // Secured bonds are valued with the platform-registered feed for the
+        // collateral mint, which must be a readable, live price feed
+        if !args.is_unsecured {
+            let collateral_oracle = ctx
+                .accounts
+                .collateral_oracle
+                .as_ref()
+                .ok_or(BtrustError::CollateralOracleNotRegistered)?;
+            require!(
+                ctx.accounts.price_oracle.key() == collateral_oracle.price_oracle,
+                BtrustError::InvalidOracle
+            );
+            load_oracle_price(&ctx.accounts.price_oracle, Clock::get()?.unix_timestamp)?;
+        }
         
         let bond = &mut ctx.accounts.bond;
         let platform = &mut ctx.accounts.platform;
@@ -54,6 +440,10 @@ pub mod btrust_bond {
         bond.bond_mint = ctx.accounts.bond_mint.key();
         bond.collateral_mint = ctx.accounts.collateral_mint.key();
         bond.collateral_vault = ctx.accounts.collateral_vault.key();
+        bond.payment_mint = ctx.accounts.payment_mint.key();
+        bond.price_oracle = ctx.accounts.price_oracle.key();
+        bond.collateral_decimals = ctx.accounts.collateral_mint.decimals;
+        bond.payment_decimals = ctx.accounts.payment_mint.decimals;
         bond.name = args.name;
         bond.symbol = args.symbol;
         bond.description = args.description;
@@ -63,19 +453,110 @@ pub mod btrust_bond {
         bond.discord = args.discord;
         bond.principal_amount = args.principal_amount;
         bond.coupon_rate_bps = args.coupon_rate_bps;
+        bond.amortization_bps = args.amortization_bps;
+        bond.amortized_per_bond = 0;
+        bond.is_zero_coupon = args.is_zero_coupon;
+        bond.discount_yield_bps = args.discount_yield_bps;
         bond.is_variable_rate = args.is_variable_rate;
+        bond.reference_rate = args.reference_rate;
+        bond.rate_spread_bps = args.rate_spread_bps;
+        bond.rate_floor_bps = args.rate_floor_bps;
+        bond.rate_cap_bps = args.rate_cap_bps;
+        // Floating-rate bonds start from the issue rate until the first fixing
+        bond.last_fixed_rate_bps = if args.is_variable_rate {
+            args.coupon_rate_bps.clamp(args.rate_floor_bps, args.rate_cap_bps)
+        } else {
+            args.coupon_rate_bps
+        };
         bond.payment_frequency = args.payment_frequency;
-        bond.maturity_timestamp = args.maturity_timestamp;
+        bond.maturity_timestamp = maturity_timestamp;
+        bond.is_perpetual = args.is_perpetual;
         bond.created_at = Clock::get()?.unix_timestamp;
         bond.total_supply = args.total_supply;
         bond.outstanding_supply = 0;
+        bond.total_issued = 0;
         bond.is_capped = args.is_capped;
         bond.collateral_ratio_bps = args.collateral_ratio_bps.max(MIN_COLLATERAL_RATIO_BPS);
+        bond.is_unsecured = args.is_unsecured;
         bond.collateral_deposited = 0;
         bond.total_yield_paid = 0;
+        bond.yield_owed = 0;
+        bond.principal_repaid = 0;
+        bond.yield_index = 0;
+        bond.funded_yield_index = 0;
+        // Zero-coupon bonds have no coupon dates to record
+        bond.coupon_periods = if bond.is_zero_coupon {
+            0
+        } else {
+            bond.scheduled_coupon_periods()?
+        };
+        bond.coupon_periods_recorded = 0;
+        bond.coupon_periods_funded = 0;
+        // The discount compounds over the whole term, so it must be priceable at creation
+        if bond.is_zero_coupon {
+            let price = bond.discount_price(bond.created_at)
+                .map_err(|_| error!(BtrustError::InvalidDiscountYield))?;
+            require!(price > 0, BtrustError::InvalidDiscountYield);
+        }
+        bond.grace_period = args.grace_period;
+        bond.subscription_start = args.subscription_start;
+        bond.subscription_end = args.subscription_end;
+        bond.soft_cap = args.soft_cap;
+        bond.subscription_raised = 0;
+        bond.auction_start = args.auction_start;
+        bond.auction_end = args.auction_end;
+        bond.auction_start_price = args.auction_start_price;
+        bond.auction_floor_price = args.auction_floor_price;
+        bond.auction_bid_quantity = 0;
+        bond.auction_unclaimed = 0;
+        bond.auction_clearing_price = 0;
+        bond.auction_settled_index = 0;
+        bond.auction_settled_amortized = 0;
+        bond.is_settled = false;
+        bond.first_call_date = args.first_call_date;
+        bond.call_schedule_bps = args.call_schedule_bps;
+        bond.call_notice_period = args.call_notice_period;
+        bond.call_date = 0;
+        bond.call_price = 0;
+        bond.put_dates = args.put_dates;
+        bond.put_window_length = args.put_window_length;
+        bond.put_price_bps = args.put_price_bps;
+        bond.put_tendered = 0;
+        bond.put_window_end = 0;
+        bond.put_due = 0;
+        bond.sinking_fund_per_period = args.sinking_fund_per_period;
+        bond.sinking_fund_deposited = 0;
+        bond.sinking_drawn_pending = 0;
+        bond.sinking_draw_remaining = 0;
+        bond.sinking_draw_eligible = 0;
+        bond.sinking_draw_seed = [0; 32];
+        bond.sinking_draw_slot = 0;
+        bond.sinking_draw_revealed_at = 0;
+        bond.sinking_fund_draws = 0;
+        bond.sinking_fund_retired = 0;
+        bond.escrowed_supply = 0;
+        bond.retire_queue_head = Pubkey::default();
+        bond.proposal_count = 0;
+        bond.pending_coupon_rate_bps = 0;
+        bond.pending_rate_period = 0;
+        bond.trustee = args.trustee;
+        bond.is_accelerated = false;
+        bond.trading_frozen = false;
+        bond.recovery_claimed = false;
+        bond.conversion_mint = args.conversion_mint;
+        bond.conversion_ratio = args.conversion_ratio;
+        bond.conversion_start = args.conversion_start;
+        bond.conversion_end = args.conversion_end;
+        bond.has_vaults = false;
+        bond.has_sinking_fund = false;
+        bond.has_conversion_vault = false;
         bond.last_yield_payment = Clock::get()?.unix_timestamp;
         bond.is_active = true;
         bond.is_matured = false;
+        bond.is_defaulted = false;
+        bond.defaulted_at = 0;
+        bond.is_liquidated = false;
+        bond.pause_flags = 0;
         bond.bump = ctx.bumps.bond;
         
         platform.total_bonds_issued += 1;
@@ -87,7 +568,10 @@ pub mod btrust_bond {
             principal_amount: bond.principal_amount,
             coupon_rate_bps: bond.coupon_rate_bps,
             maturity_timestamp: bond.maturity_timestamp,
+            is_perpetual: bond.is_perpetual,
             total_supply: bond.total_supply,
+            payment_mint: bond.payment_mint,
+            is_unsecured: bond.is_unsecured,
         });
         
         Ok(())
@@ -128,204 +612,452 @@ pub mod btrust_bond {
         Ok(())
     }
 
-    /// Purchase bonds from an offering
-    pub fn purchase_bond(
-        ctx: Context<PurchaseBond>,
-        quantity: u64,
-    ) -> Result<()> {
-        require!(quantity > 0, BtrustError::InvalidAmount);
-        
+    /// Create the bond's redemption and yield vaults in its payment mint
+    pub fn init_bond_vaults(ctx: Context<InitBondVaults>) -> Result<()> {
         let bond = &mut ctx.accounts.bond;
-        let platform = &ctx.accounts.platform;
-        
-        require!(bond.is_active, BtrustError::BondNotActive);
-        require!(!bond.is_matured, BtrustError::BondMatured);
-        
-        if bond.is_capped {
-            require!(
-                bond.outstanding_supply + quantity <= bond.total_supply,
-                BtrustError::ExceedsSupply
-            );
-        }
+        bond.has_vaults = true;
         
-        // Calculate payment amount
-        let payment_amount = bond.principal_amount
-            .checked_mul(quantity)
-            .ok_or(BtrustError::MathOverflow)?;
+        emit!(BondVaultsInitialized {
+            bond: bond.key(),
+            payment_mint: bond.payment_mint,
+            redemption_vault: ctx.accounts.redemption_vault.key(),
+            yield_vault: ctx.accounts.yield_vault.key(),
+        });
         
-        // Calculate platform fee
-        let fee_amount = payment_amount
-            .checked_mul(platform.fee_bps)
-            .ok_or(BtrustError::MathOverflow)?
-            .checked_div(BPS_DENOMINATOR)
-            .ok_or(BtrustError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Escrow principal in the redemption vault ahead of maturity
+    pub fn fund_redemption(ctx: Context<FundRedemption>, amount: u64) -> Result<()> {
+        require!(amount > 0, BtrustError::InvalidAmount);
         
-        let issuer_amount = payment_amount
-            .checked_sub(fee_amount)
-            .ok_or(BtrustError::MathOverflow)?;
+        let bond = &mut ctx.accounts.bond;
+        require!(!bond.in_recovery(), BtrustError::BondInRecovery);
+        require!(amount <= bond.outstanding_debt()?, BtrustError::ExceedsOutstandingDebt);
         
-        // Transfer payment to issuer (minus fee)
         token::transfer(
             CpiContext::new(
                 ctx.accounts.token_program.to_account_info(),
                 Transfer {
-                    from: ctx.accounts.buyer_payment.to_account_info(),
-                    to: ctx.accounts.issuer_payment.to_account_info(),
-                    authority: ctx.accounts.buyer.to_account_info(),
+                    from: ctx.accounts.funder_payment.to_account_info(),
+                    to: ctx.accounts.redemption_vault.to_account_info(),
+                    authority: ctx.accounts.funder.to_account_info(),
                 },
             ),
-            issuer_amount,
+            amount,
         )?;
         
-        // Transfer fee to treasury
-        if fee_amount > 0 {
+        bond.principal_repaid = bond.principal_repaid
+            .checked_add(amount)
+            .ok_or(BtrustError::MathOverflow)?;
+        
+        emit!(RedemptionFunded {
+            bond: bond.key(),
+            funder: ctx.accounts.funder.key(),
+            amount,
+            total_escrowed: bond.principal_repaid,
+        });
+        
+        Ok(())
+    }
+
+    /// Cancel an offering that sold nothing, returning its collateral, vault balances and rent
+    pub fn cancel_offering(ctx: Context<CancelOffering>) -> Result<()> {
+        let bond = &ctx.accounts.bond;
+        
+        require!(bond.total_issued == 0, BtrustError::BondsIssued);
+        require!(bond.outstanding_supply == 0, BtrustError::BondsOutstanding);
+        require!(bond.auction_bid_quantity == 0, BtrustError::BondsOutstanding);
+        
+        // Leave no vault behind owned by a closed bond
+        require!(
+            ctx.accounts.redemption_vault.is_some() == bond.has_vaults
+                && ctx.accounts.yield_vault.is_some() == bond.has_vaults
+                && ctx.accounts.subscription_vault.is_some() == bond.has_vaults
+                && ctx.accounts.sinking_fund.is_some() == bond.has_sinking_fund
+                && ctx.accounts.conversion_vault.is_some() == bond.has_conversion_vault,
+            BtrustError::VaultMissing
+        );
+        
+        let bond_mint = bond.bond_mint;
+        let seeds = &[
+            b"bond",
+            bond_mint.as_ref(),
+            &[bond.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        let collateral_returned = ctx.accounts.collateral_vault.amount;
+        if collateral_returned > 0 {
             token::transfer(
-                CpiContext::new(
+                CpiContext::new_with_signer(
                     ctx.accounts.token_program.to_account_info(),
                     Transfer {
-                        from: ctx.accounts.buyer_payment.to_account_info(),
-                        to: ctx.accounts.treasury.to_account_info(),
-                        authority: ctx.accounts.buyer.to_account_info(),
+                        from: ctx.accounts.collateral_vault.to_account_info(),
+                        to: ctx.accounts.issuer_collateral.to_account_info(),
+                        authority: bond.to_account_info(),
                     },
+                    signer_seeds,
                 ),
-                fee_amount,
+                collateral_returned,
             )?;
         }
         
-        // Mint bond tokens to buyer
-        let bond_key = bond.key();
-        let seeds = &[
-            b"bond",
-            bond_key.as_ref(),
-            &[bond.bump],
+        let mut payment_returned = 0;
+        let payment_vaults = [
+            &ctx.accounts.redemption_vault,
+            &ctx.accounts.yield_vault,
+            &ctx.accounts.subscription_vault,
+            &ctx.accounts.sinking_fund,
         ];
-        let signer_seeds = &[&seeds[..]];
+        for vault in payment_vaults.into_iter().flatten() {
+            if vault.amount > 0 {
+                token::transfer(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        Transfer {
+                            from: vault.to_account_info(),
+                            to: ctx.accounts.issuer_payment.to_account_info(),
+                            authority: bond.to_account_info(),
+                        },
+                        signer_seeds,
+                    ),
+                    vault.amount,
+                )?;
+                payment_returned += vault.amount;
+            }
+        }
         
-        token::mint_to(
-            CpiContext::new_with_signer(
+        if let Some(vault) = &ctx.accounts.conversion_vault {
+            if vault.amount > 0 {
+                let destination = ctx.accounts.issuer_conversion_account
+                    .as_ref()
+                    .ok_or(BtrustError::InvalidConversionMint)?;
+                token::transfer(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        Transfer {
+                            from: vault.to_account_info(),
+                            to: destination.to_account_info(),
+                            authority: bond.to_account_info(),
+                        },
+                        signer_seeds,
+                    ),
+                    vault.amount,
+                )?;
+            }
+        }
+        
+        for vault in payment_vaults
+            .into_iter()
+            .flatten()
+            .chain(ctx.accounts.conversion_vault.as_ref())
+            .chain(std::iter::once(&ctx.accounts.collateral_vault))
+        {
+            token::close_account(CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
-                MintTo {
-                    mint: ctx.accounts.bond_mint.to_account_info(),
-                    to: ctx.accounts.buyer_bond_account.to_account_info(),
+                CloseAccount {
+                    account: vault.to_account_info(),
+                    destination: ctx.accounts.issuer.to_account_info(),
                     authority: bond.to_account_info(),
                 },
                 signer_seeds,
-            ),
-            quantity,
-        )?;
-        
-        bond.outstanding_supply += quantity;
-        
-        // Create or update holder position
-        let position = &mut ctx.accounts.holder_position;
-        if position.holder == Pubkey::default() {
-            position.holder = ctx.accounts.buyer.key();
-            position.bond = bond.key();
-            position.quantity = quantity;
-            position.purchase_price = bond.principal_amount;
-            position.purchase_timestamp = Clock::get()?.unix_timestamp;
-            position.total_yield_claimed = 0;
-            position.bump = ctx.bumps.holder_position;
-        } else {
-            // Average purchase price
-            let total_value = position.quantity
-                .checked_mul(position.purchase_price)
-                .ok_or(BtrustError::MathOverflow)?
-                .checked_add(payment_amount)
-                .ok_or(BtrustError::MathOverflow)?;
-            position.quantity += quantity;
-            position.purchase_price = total_value
-                .checked_div(position.quantity)
-                .ok_or(BtrustError::MathOverflow)?;
+            ))?;
         }
         
-        emit!(BondPurchased {
+        emit!(OfferingCancelled {
             bond: bond.key(),
-            buyer: ctx.accounts.buyer.key(),
-            quantity,
-            payment_amount,
-            fee_amount,
+            issuer: bond.issuer,
+            collateral_returned,
+            payment_returned,
         });
         
         Ok(())
     }
 
-    /// Deposit yield payment for bond holders
-    pub fn deposit_yield(
-        ctx: Context<DepositYield>,
+    /// Withdraw collateral above the bond's configured collateral ratio
+    pub fn withdraw_collateral(
+        ctx: Context<WithdrawCollateral>,
         amount: u64,
     ) -> Result<()> {
         require!(amount > 0, BtrustError::InvalidAmount);
         
         let bond = &mut ctx.accounts.bond;
-        require!(bond.is_active, BtrustError::BondNotActive);
-        require!(bond.issuer == ctx.accounts.issuer.key(), BtrustError::Unauthorized);
+        require_not_paused(&ctx.accounts.platform, bond, PAUSE_LIQUIDATION)?;
+        require!(!bond.in_recovery(), BtrustError::BondInRecovery);
+        require!(amount <= bond.collateral_deposited, BtrustError::InsufficientBalance);
         
-        // Transfer yield to yield vault
-        token::transfer(
-            CpiContext::new(
+        let remaining = bond.collateral_deposited - amount;
+        let outstanding_value = bond.outstanding_debt()?
+            .checked_add(bond.pending_auction_debt()?)
+            .ok_or(BtrustError::MathOverflow)?;
+        
+        // Unsecured bonds carry no collateral obligation
+        if !bond.is_unsecured && outstanding_value > 0 {
+            let required_value = outstanding_value
+                .checked_mul(bond.collateral_ratio_bps)
+                .ok_or(BtrustError::MathOverflow)?
+                .checked_div(BPS_DENOMINATOR)
+                .ok_or(BtrustError::MathOverflow)?;
+            
+            let price = load_oracle_price(
+                &ctx.accounts.price_oracle,
+                Clock::get()?.unix_timestamp,
+            )?;
+            let remaining_value = collateral_value(bond, remaining, &price)?;
+            
+            require!(
+                remaining_value >= required_value,
+                BtrustError::InsufficientCollateral
+            );
+        }
+        
+        // Transfer collateral back to issuer
+        let bond_mint = bond.bond_mint;
+        let seeds = &[
+            b"bond",
+            bond_mint.as_ref(),
+            &[bond.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        token::transfer(
+            CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
                 Transfer {
-                    from: ctx.accounts.issuer_payment.to_account_info(),
-                    to: ctx.accounts.yield_vault.to_account_info(),
-                    authority: ctx.accounts.issuer.to_account_info(),
+                    from: ctx.accounts.collateral_vault.to_account_info(),
+                    to: ctx.accounts.issuer_collateral.to_account_info(),
+                    authority: bond.to_account_info(),
                 },
+                signer_seeds,
             ),
             amount,
         )?;
         
-        bond.last_yield_payment = Clock::get()?.unix_timestamp;
+        bond.collateral_deposited = remaining;
         
-        emit!(YieldDeposited {
+        emit!(CollateralWithdrawn {
             bond: bond.key(),
             amount,
-            timestamp: bond.last_yield_payment,
+            total_collateral: bond.collateral_deposited,
         });
         
         Ok(())
     }
 
-    /// Claim accrued yield
-    pub fn claim_yield(ctx: Context<ClaimYield>) -> Result<()> {
-        let bond = &ctx.accounts.bond;
-        let position = &mut ctx.accounts.holder_position;
+    /// Purchase bonds from an offering
+    pub fn purchase_bond(
+        ctx: Context<PurchaseBond>,
+        quantity: u64,
+    ) -> Result<()> {
+        require!(quantity > 0, BtrustError::InvalidAmount);
         
-        require!(position.holder == ctx.accounts.holder.key(), BtrustError::Unauthorized);
+        let bond = &mut ctx.accounts.bond;
+        let platform = &ctx.accounts.platform;
+        
+        require_not_paused(platform, bond, PAUSE_ISSUANCE)?;
+        require!(bond.is_active, BtrustError::BondNotActive);
+        require!(!bond.is_matured, BtrustError::BondMatured);
+        require!(!bond.is_called(), BtrustError::BondCalled);
+        require!(!bond.has_auction(), BtrustError::AuctionBond);
+        
+        if bond.is_capped {
+            require!(
+                bond.outstanding_supply + quantity <= bond.total_supply,
+                BtrustError::ExceedsSupply
+            );
+        }
         
-        // Calculate yield owed
-        let time_held = Clock::get()?.unix_timestamp
-            .checked_sub(position.purchase_timestamp)
+        // Calculate payment amount; zero-coupon bonds sell below par
+        let face_amount = bond.face_value()
+            .checked_mul(quantity)
             .ok_or(BtrustError::MathOverflow)?;
+        let payment_amount = if bond.is_zero_coupon {
+            bond.discount_price(Clock::get()?.unix_timestamp)?
+                .checked_mul(quantity)
+                .ok_or(BtrustError::MathOverflow)?
+        } else {
+            face_amount
+        };
+        
+        // Secured bonds can only be sold while collateral covers the new debt
+        require_collateral_covers(bond, &ctx.accounts.price_oracle, face_amount)?;
+        
+        let mut fee_amount = 0;
+        if bond.has_subscription() {
+            let now = Clock::get()?.unix_timestamp;
+            require!(now >= bond.subscription_start, BtrustError::SubscriptionNotOpen);
+            require!(now < bond.subscription_end, BtrustError::SubscriptionClosed);
+            
+            // Hold the full payment in escrow; the fee is taken at settlement
+            token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.buyer_payment.to_account_info(),
+                        to: ctx.accounts.subscription_vault.to_account_info(),
+                        authority: ctx.accounts.buyer.to_account_info(),
+                    },
+                ),
+                payment_amount,
+            )?;
+            
+            bond.subscription_raised = bond.subscription_raised
+                .checked_add(payment_amount)
+                .ok_or(BtrustError::MathOverflow)?;
+        } else {
+            // Calculate platform fee
+            fee_amount = payment_amount
+                .checked_mul(platform.fee_bps)
+                .ok_or(BtrustError::MathOverflow)?
+                .checked_div(BPS_DENOMINATOR)
+                .ok_or(BtrustError::MathOverflow)?;
+            
+            let issuer_amount = payment_amount
+                .checked_sub(fee_amount)
+                .ok_or(BtrustError::MathOverflow)?;
+            
+            // Transfer payment to issuer (minus fee)
+            token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.buyer_payment.to_account_info(),
+                        to: ctx.accounts.issuer_payment.to_account_info(),
+                        authority: ctx.accounts.buyer.to_account_info(),
+                    },
+                ),
+                issuer_amount,
+            )?;
+            
+            // Transfer fee to treasury
+            if fee_amount > 0 {
+                token::transfer(
+                    CpiContext::new(
+                        ctx.accounts.token_program.to_account_info(),
+                        Transfer {
+                            from: ctx.accounts.buyer_payment.to_account_info(),
+                            to: ctx.accounts.treasury.to_account_info(),
+                            authority: ctx.accounts.buyer.to_account_info(),
+                        },
+                    ),
+                    fee_amount,
+                )?;
+            }
+        }
+        
+        // Mint bond tokens to buyer
+        let bond_mint = bond.bond_mint;
+        let seeds = &[
+            b"bond",
+            bond_mint.as_ref(),
+            &[bond.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        thaw_holder_account(
+            &ctx.accounts.token_program,
+            bond,
+            &ctx.accounts.bond_mint,
+            &ctx.accounts.buyer_bond_account,
+        )?;
+        
+        token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.bond_mint.to_account_info(),
+                    to: ctx.accounts.buyer_bond_account.to_account_info(),
+                    authority: bond.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            quantity,
+        )?;
+        
+        freeze_holder_account(
+            &ctx.accounts.token_program,
+            bond,
+            &ctx.accounts.bond_mint,
+            &mut ctx.accounts.buyer_bond_account,
+        )?;
+        
+        bond.outstanding_supply += quantity;
+        if !bond.has_subscription() {
+            bond.total_issued += quantity;
+        }
+        
+        // Create or update holder position
+        let position = &mut ctx.accounts.holder_position;
+        if position.holder == Pubkey::default() {
+            position.holder = ctx.accounts.buyer.key();
+            position.bond = bond.key();
+            position.purchase_price = bond.face_value();
+            position.purchase_timestamp = Clock::get()?.unix_timestamp;
+            position.total_yield_claimed = 0;
+            position.bump = ctx.bumps.holder_position;
+        }
         
-        let annual_yield = position.quantity
+        // Settle yield on the pre-purchase balance before it changes
+        position.settle_yield(bond)?;
+        
+        // Average purchase price
+        let total_value = position.quantity
             .checked_mul(position.purchase_price)
             .ok_or(BtrustError::MathOverflow)?
-            .checked_mul(bond.coupon_rate_bps)
-            .ok_or(BtrustError::MathOverflow)?
-            .checked_div(BPS_DENOMINATOR)
+            .checked_add(payment_amount)
             .ok_or(BtrustError::MathOverflow)?;
-        
-        let yield_owed = annual_yield
-            .checked_mul(time_held as u64)
-            .ok_or(BtrustError::MathOverflow)?
-            .checked_div(31536000) // seconds in a year
-            .ok_or(BtrustError::MathOverflow)?
-            .checked_sub(position.total_yield_claimed)
+        position.quantity += quantity;
+        position.purchase_price = total_value
+            .checked_div(position.quantity)
             .ok_or(BtrustError::MathOverflow)?;
         
-        require!(yield_owed > 0, BtrustError::NoYieldToClaim);
+        if bond.has_subscription() {
+            position.subscribed_quantity += quantity;
+            position.subscription_paid = position.subscription_paid
+                .checked_add(payment_amount)
+                .ok_or(BtrustError::MathOverflow)?;
+        }
         
-        // Check yield vault balance
-        let vault_balance = ctx.accounts.yield_vault.amount;
-        let claimable = yield_owed.min(vault_balance);
+        emit!(BondPurchased {
+            bond: bond.key(),
+            buyer: ctx.accounts.buyer.key(),
+            quantity,
+            payment_amount,
+            fee_amount,
+        });
         
-        require!(claimable > 0, BtrustError::InsufficientYieldBalance);
+        Ok(())
+    }
+
+    /// Release escrowed subscription proceeds to the issuer once the soft cap is met
+    pub fn settle_subscription(ctx: Context<SettleSubscription>) -> Result<()> {
+        let bond = &mut ctx.accounts.bond;
+        let platform = &ctx.accounts.platform;
         
-        // Transfer yield to holder
-        let bond_key = bond.key();
+        require_not_paused(platform, bond, PAUSE_ISSUANCE)?;
+        require!(bond.has_subscription(), BtrustError::NoSubscription);
+        require!(!bond.is_settled, BtrustError::SubscriptionSettled);
+        require!(
+            Clock::get()?.unix_timestamp >= bond.subscription_end,
+            BtrustError::SubscriptionOpen
+        );
+        require!(bond.subscription_raised >= bond.soft_cap, BtrustError::SoftCapNotMet);
+        
+        let raised = bond.subscription_raised;
+        let fee_amount = raised
+            .checked_mul(platform.fee_bps)
+            .ok_or(BtrustError::MathOverflow)?
+            .checked_div(BPS_DENOMINATOR)
+            .ok_or(BtrustError::MathOverflow)?;
+        let issuer_amount = raised
+            .checked_sub(fee_amount)
+            .ok_or(BtrustError::MathOverflow)?;
+        
+        let bond_mint = bond.bond_mint;
         let seeds = &[
             b"bond",
-            bond_key.as_ref(),
+            bond_mint.as_ref(),
             &[bond.bump],
         ];
         let signer_seeds = &[&seeds[..]];
@@ -334,44 +1066,79 @@ pub mod btrust_bond {
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
                 Transfer {
-                    from: ctx.accounts.yield_vault.to_account_info(),
-                    to: ctx.accounts.holder_payment.to_account_info(),
-                    authority: ctx.accounts.bond.to_account_info(),
+                    from: ctx.accounts.subscription_vault.to_account_info(),
+                    to: ctx.accounts.issuer_payment.to_account_info(),
+                    authority: bond.to_account_info(),
                 },
                 signer_seeds,
             ),
-            claimable,
+            issuer_amount,
         )?;
         
-        position.total_yield_claimed += claimable;
+        if fee_amount > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.subscription_vault.to_account_info(),
+                        to: ctx.accounts.treasury.to_account_info(),
+                        authority: bond.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                fee_amount,
+            )?;
+        }
         
-        emit!(YieldClaimed {
+        bond.total_issued += bond.outstanding_supply;
+        bond.is_settled = true;
+        
+        emit!(SubscriptionSettled {
             bond: bond.key(),
-            holder: ctx.accounts.holder.key(),
-            amount: claimable,
+            raised,
+            fee_amount,
+            outstanding_supply: bond.outstanding_supply,
         });
         
         Ok(())
     }
 
-    /// Redeem bonds at maturity
-    pub fn redeem_bond(ctx: Context<RedeemBond>, quantity: u64) -> Result<()> {
-        require!(quantity > 0, BtrustError::InvalidAmount);
-        
+    /// Refund a subscriber when the offering closes below its soft cap
+    pub fn refund_subscription(ctx: Context<RefundSubscription>) -> Result<()> {
         let bond = &mut ctx.accounts.bond;
         let position = &mut ctx.accounts.holder_position;
         
+        require!(bond.has_subscription(), BtrustError::NoSubscription);
         require!(
-            Clock::get()?.unix_timestamp >= bond.maturity_timestamp,
-            BtrustError::BondNotMatured
+            Clock::get()?.unix_timestamp >= bond.subscription_end,
+            BtrustError::SubscriptionOpen
         );
-        require!(position.quantity >= quantity, BtrustError::InsufficientBalance);
+        require!(bond.subscription_raised < bond.soft_cap, BtrustError::SoftCapMet);
+        require!(position.subscribed_quantity > 0, BtrustError::NothingToRefund);
         
-        let redemption_amount = bond.principal_amount
-            .checked_mul(quantity)
-            .ok_or(BtrustError::MathOverflow)?;
+        // No coupon is recorded on an unsettled offering, so refunds need not
+        // wait for record dates that pass after it closes
+        position.snapshot_votes(bond);
+        
+        let quantity = position.subscribed_quantity;
+        let refund_amount = position.subscription_paid;
+        
+        let bond_mint = bond.bond_mint;
+        let seeds = &[
+            b"bond",
+            bond_mint.as_ref(),
+            &[bond.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        // Thaw so the subscribed bonds can be burned
+        thaw_holder_account(
+            &ctx.accounts.token_program,
+            bond,
+            &ctx.accounts.bond_mint,
+            &ctx.accounts.holder_bond_account,
+        )?;
         
-        // Burn bond tokens
         token::burn(
             CpiContext::new(
                 ctx.accounts.token_program.to_account_info(),
@@ -384,265 +1151,322 @@ pub mod btrust_bond {
             quantity,
         )?;
         
-        // Transfer principal from redemption vault
-        let bond_key = bond.key();
-        let seeds = &[
-            b"bond",
-            bond_key.as_ref(),
-            &[bond.bump],
-        ];
-        let signer_seeds = &[&seeds[..]];
+        freeze_holder_account(
+            &ctx.accounts.token_program,
+            bond,
+            &ctx.accounts.bond_mint,
+            &mut ctx.accounts.holder_bond_account,
+        )?;
         
         token::transfer(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
                 Transfer {
-                    from: ctx.accounts.redemption_vault.to_account_info(),
+                    from: ctx.accounts.subscription_vault.to_account_info(),
                     to: ctx.accounts.holder_payment.to_account_info(),
                     authority: bond.to_account_info(),
                 },
                 signer_seeds,
             ),
-            redemption_amount,
+            refund_amount,
         )?;
         
         bond.outstanding_supply -= quantity;
-        position.quantity -= quantity;
-        
-        if bond.outstanding_supply == 0 {
-            bond.is_matured = true;
-            bond.is_active = false;
-        }
+        bond.subscription_raised -= refund_amount;
+        position.quantity = position.quantity.saturating_sub(quantity);
+        position.subscribed_quantity = 0;
+        position.subscription_paid = 0;
+        position.release_drawn(bond);
         
-        emit!(BondRedeemed {
+        emit!(SubscriptionRefunded {
             bond: bond.key(),
             holder: ctx.accounts.holder.key(),
             quantity,
-            redemption_amount,
+            refund_amount,
         });
         
         Ok(())
     }
 
-    /// Liquidate undercollateralized bond
-    pub fn liquidate(ctx: Context<Liquidate>) -> Result<()> {
+    /// Bid in a descending-price auction, locking funds at the current price
+    pub fn place_bid(ctx: Context<PlaceBid>, quantity: u64) -> Result<()> {
+        require!(quantity > 0, BtrustError::InvalidAmount);
+        
         let bond = &mut ctx.accounts.bond;
         
+        require_not_paused(&ctx.accounts.platform, bond, PAUSE_ISSUANCE)?;
         require!(bond.is_active, BtrustError::BondNotActive);
-        require!(bond.collateral_deposited > 0, BtrustError::NoCollateral);
-        
-        // Calculate required collateral
-        let outstanding_value = bond.outstanding_supply
-            .checked_mul(bond.principal_amount)
-            .ok_or(BtrustError::MathOverflow)?;
+        require!(bond.has_auction(), BtrustError::NotAuctionBond);
         
-        let required_collateral = outstanding_value
-            .checked_mul(LIQUIDATION_THRESHOLD_BPS)
-            .ok_or(BtrustError::MathOverflow)?
-            .checked_div(BPS_DENOMINATOR)
-            .ok_or(BtrustError::MathOverflow)?;
+        let now = Clock::get()?.unix_timestamp;
+        require!(now >= bond.auction_start, BtrustError::AuctionNotOpen);
+        require!(!bond.is_auction_closed(now), BtrustError::AuctionClosed);
         
-        // Note: In production, you'd use an oracle for collateral price
-        // For now, assuming 1:1 value ratio
-        require!(
-            bond.collateral_deposited < required_collateral,
-            BtrustError::NotLiquidatable
-        );
+        // The last bid is trimmed to what is left of the supply
+        let quantity = quantity.min(bond.total_supply - bond.auction_bid_quantity);
+        require!(quantity > 0, BtrustError::InvalidAmount);
         
-        // Calculate liquidation bonus
-        let bonus = bond.collateral_deposited
-            .checked_mul(LIQUIDATION_PENALTY_BPS)
-            .ok_or(BtrustError::MathOverflow)?
-            .checked_div(BPS_DENOMINATOR)
+        let new_debt = bond.face_value()
+            .checked_mul(quantity)
             .ok_or(BtrustError::MathOverflow)?;
+        require_collateral_covers(bond, &ctx.accounts.price_oracle, new_debt)?;
         
-        let liquidator_reward = bond.collateral_deposited
-            .checked_sub(bonus)
+        let price = bond.auction_price(now);
+        let locked_amount = price
+            .checked_mul(quantity)
             .ok_or(BtrustError::MathOverflow)?;
         
-        // Transfer collateral to liquidator
-        let bond_key = bond.key();
-        let seeds = &[
-            b"bond",
-            bond_key.as_ref(),
-            &[bond.bump],
-        ];
-        let signer_seeds = &[&seeds[..]];
-        
         token::transfer(
-            CpiContext::new_with_signer(
+            CpiContext::new(
                 ctx.accounts.token_program.to_account_info(),
                 Transfer {
-                    from: ctx.accounts.collateral_vault.to_account_info(),
-                    to: ctx.accounts.liquidator_collateral.to_account_info(),
-                    authority: bond.to_account_info(),
+                    from: ctx.accounts.bidder_payment.to_account_info(),
+                    to: ctx.accounts.subscription_vault.to_account_info(),
+                    authority: ctx.accounts.bidder.to_account_info(),
                 },
-                signer_seeds,
             ),
-            liquidator_reward,
+            locked_amount,
         )?;
         
-        bond.collateral_deposited = 0;
-        bond.is_active = false;
+        let bid = &mut ctx.accounts.bid;
+        if bid.bidder == Pubkey::default() {
+            bid.bond = bond.key();
+            bid.bidder = ctx.accounts.bidder.key();
+            bid.bump = ctx.bumps.bid;
+        }
+        bid.quantity += quantity;
+        bid.locked_amount = bid.locked_amount
+            .checked_add(locked_amount)
+            .ok_or(BtrustError::MathOverflow)?;
         
-        emit!(BondLiquidated {
+        bond.auction_bid_quantity += quantity;
+        
+        // Selling out closes the auction at this price
+        if bond.auction_bid_quantity == bond.total_supply {
+            bond.auction_clearing_price = price;
+        }
+        
+        emit!(AuctionBidPlaced {
             bond: bond.key(),
-            liquidator: ctx.accounts.liquidator.key(),
-            collateral_seized: liquidator_reward,
+            bidder: bid.bidder,
+            quantity,
+            price,
+            locked_amount,
+            total_bid_quantity: bond.auction_bid_quantity,
         });
         
         Ok(())
     }
 
-    /// Create a sell order for secondary market
-    pub fn create_sell_order(
-        ctx: Context<CreateSellOrder>,
-        quantity: u64,
-        price_per_bond: u64,
-    ) -> Result<()> {
-        require!(quantity > 0, BtrustError::InvalidAmount);
-        require!(price_per_bond > 0, BtrustError::InvalidAmount);
-        
-        let order = &mut ctx.accounts.order;
-        let bond = &ctx.accounts.bond;
-        
-        require!(bond.is_active, BtrustError::BondNotActive);
-        
-        // Transfer bonds to escrow
-        token::transfer(
-            CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.seller_bond_account.to_account_info(),
-                    to: ctx.accounts.order_escrow.to_account_info(),
-                    authority: ctx.accounts.seller.to_account_info(),
-                },
-            ),
-            quantity,
-        )?;
-        
-        order.seller = ctx.accounts.seller.key();
-        order.bond = bond.key();
-        order.quantity = quantity;
-        order.price_per_bond = price_per_bond;
-        order.created_at = Clock::get()?.unix_timestamp;
-        order.is_active = true;
-        order.bump = ctx.bumps.order;
-        
-        emit!(SellOrderCreated {
-            order: order.key(),
-            bond: bond.key(),
-            seller: order.seller,
-            quantity,
-            price_per_bond,
-        });
+    /// Close an auction at its clearing price and pay the issuer
+    pub fn settle_auction(ctx: Context<SettleAuction>) -> Result<()> {
+        let bond = &mut ctx.accounts.bond;
+        let platform = &ctx.accounts.platform;
         
-        Ok(())
-    }
-
-    /// Fill a sell order (buy from secondary market)
-    pub fn fill_order(ctx: Context<FillOrder>, quantity: u64) -> Result<()> {
-        require!(quantity > 0, BtrustError::InvalidAmount);
+        require_not_paused(platform, bond, PAUSE_ISSUANCE)?;
+        require!(bond.has_auction(), BtrustError::NotAuctionBond);
+        require!(!bond.is_settled, BtrustError::AuctionSettled);
+        let now = Clock::get()?.unix_timestamp;
+        require!(bond.is_auction_closed(now), BtrustError::AuctionOpen);
+        // Bidders may withdraw once the settlement window has passed
+        require!(now < bond.auction_settlement_deadline(), BtrustError::AuctionExpired);
         
-        let order = &mut ctx.accounts.order;
-        let platform = &ctx.accounts.platform;
+        // Unsold auctions clear at the floor
+        if bond.auction_clearing_price == 0 {
+            bond.auction_clearing_price = bond.auction_floor_price;
+        }
         
-        require!(order.is_active, BtrustError::OrderNotActive);
-        require!(quantity <= order.quantity, BtrustError::ExceedsOrderQuantity);
+        // Collateral must still cover the bonds about to be issued
+        require_collateral_covers(bond, &ctx.accounts.price_oracle, 0)?;
         
-        let payment_amount = order.price_per_bond
-            .checked_mul(quantity)
+        let proceeds = bond.auction_clearing_price
+            .checked_mul(bond.auction_bid_quantity)
             .ok_or(BtrustError::MathOverflow)?;
-        
-        // Calculate fee
-        let fee_amount = payment_amount
+        let fee_amount = proceeds
             .checked_mul(platform.fee_bps)
             .ok_or(BtrustError::MathOverflow)?
             .checked_div(BPS_DENOMINATOR)
             .ok_or(BtrustError::MathOverflow)?;
-        
-        let seller_amount = payment_amount
+        let issuer_amount = proceeds
             .checked_sub(fee_amount)
             .ok_or(BtrustError::MathOverflow)?;
         
-        // Transfer payment to seller
-        token::transfer(
-            CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.buyer_payment.to_account_info(),
-                    to: ctx.accounts.seller_payment.to_account_info(),
-                    authority: ctx.accounts.buyer.to_account_info(),
-                },
-            ),
-            seller_amount,
-        )?;
+        let bond_mint = bond.bond_mint;
+        let seeds = &[
+            b"bond",
+            bond_mint.as_ref(),
+            &[bond.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        if issuer_amount > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.subscription_vault.to_account_info(),
+                        to: ctx.accounts.issuer_payment.to_account_info(),
+                        authority: bond.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                issuer_amount,
+            )?;
+        }
         
-        // Transfer fee
         if fee_amount > 0 {
             token::transfer(
-                CpiContext::new(
+                CpiContext::new_with_signer(
                     ctx.accounts.token_program.to_account_info(),
                     Transfer {
-                        from: ctx.accounts.buyer_payment.to_account_info(),
+                        from: ctx.accounts.subscription_vault.to_account_info(),
                         to: ctx.accounts.treasury.to_account_info(),
-                        authority: ctx.accounts.buyer.to_account_info(),
+                        authority: bond.to_account_info(),
                     },
+                    signer_seeds,
                 ),
                 fee_amount,
             )?;
         }
         
-        // Transfer bonds from escrow to buyer
-        let order_key = order.key();
+        // Every winning bid counts as issued from here; bidders earn coupons
+        // recorded after this point even before they claim their bonds
+        bond.outstanding_supply += bond.auction_bid_quantity;
+        bond.total_issued += bond.auction_bid_quantity;
+        bond.auction_unclaimed = bond.auction_bid_quantity;
+        bond.auction_settled_index = bond.yield_index;
+        bond.auction_settled_amortized = bond.amortized_per_bond;
+        bond.is_settled = true;
+        
+        emit!(AuctionSettled {
+            bond: bond.key(),
+            clearing_price: bond.auction_clearing_price,
+            quantity: bond.auction_bid_quantity,
+            proceeds,
+            fee_amount,
+        });
+        
+        Ok(())
+    }
+
+    /// Claim bonds won at auction and the refund of any excess locked funds
+    pub fn claim_auction_bid(ctx: Context<ClaimAuctionBid>) -> Result<()> {
+        let bond = &mut ctx.accounts.bond;
+        let bid = &ctx.accounts.bid;
+        
+        require!(bond.is_settled, BtrustError::AuctionNotSettled);
+        
+        let quantity = bid.quantity;
+        let cost = bond.auction_clearing_price
+            .checked_mul(quantity)
+            .ok_or(BtrustError::MathOverflow)?;
+        let refund_amount = bid.locked_amount
+            .checked_sub(cost)
+            .ok_or(BtrustError::MathOverflow)?;
+        
+        let bond_mint = bond.bond_mint;
         let seeds = &[
-            b"order",
-            order_key.as_ref(),
-            &[order.bump],
+            b"bond",
+            bond_mint.as_ref(),
+            &[bond.bump],
         ];
         let signer_seeds = &[&seeds[..]];
         
-        token::transfer(
+        thaw_holder_account(
+            &ctx.accounts.token_program,
+            bond,
+            &ctx.accounts.bond_mint,
+            &ctx.accounts.bidder_bond_account,
+        )?;
+        
+        token::mint_to(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.order_escrow.to_account_info(),
-                    to: ctx.accounts.buyer_bond_account.to_account_info(),
-                    authority: order.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.bond_mint.to_account_info(),
+                    to: ctx.accounts.bidder_bond_account.to_account_info(),
+                    authority: bond.to_account_info(),
                 },
                 signer_seeds,
             ),
             quantity,
         )?;
         
-        order.quantity -= quantity;
-        if order.quantity == 0 {
-            order.is_active = false;
+        freeze_holder_account(
+            &ctx.accounts.token_program,
+            bond,
+            &ctx.accounts.bond_mint,
+            &mut ctx.accounts.bidder_bond_account,
+        )?;
+        
+        if refund_amount > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.subscription_vault.to_account_info(),
+                        to: ctx.accounts.bidder_payment.to_account_info(),
+                        authority: bond.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                refund_amount,
+            )?;
         }
         
-        emit!(OrderFilled {
-            order: order.key(),
-            buyer: ctx.accounts.buyer.key(),
+        let position = &mut ctx.accounts.holder_position;
+        if position.holder == Pubkey::default() {
+            position.holder = ctx.accounts.bidder.key();
+            position.bond = bond.key();
+            position.purchase_price = bond.auction_clearing_price;
+            position.purchase_timestamp = Clock::get()?.unix_timestamp;
+            position.total_yield_claimed = 0;
+            position.bump = ctx.bumps.holder_position;
+        }
+        position.settle_yield(bond)?;
+        
+        // Credit coupons recorded between settlement and this claim
+        let (settled_index, settled_amortized) = (bond.auction_settled_index, bond.auction_settled_amortized);
+        position.credit_yield(bond, quantity, settled_index, settled_amortized, true)?;
+        
+        let total_value = position.quantity
+            .checked_mul(position.purchase_price)
+            .ok_or(BtrustError::MathOverflow)?
+            .checked_add(cost)
+            .ok_or(BtrustError::MathOverflow)?;
+        position.quantity += quantity;
+        position.purchase_price = total_value
+            .checked_div(position.quantity)
+            .ok_or(BtrustError::MathOverflow)?;
+        bond.auction_unclaimed -= quantity;
+        
+        emit!(AuctionBidClaimed {
+            bond: bond.key(),
+            bidder: ctx.accounts.bidder.key(),
             quantity,
-            payment_amount,
+            clearing_price: bond.auction_clearing_price,
+            refund_amount,
         });
         
         Ok(())
     }
 
-    /// Cancel a sell order
-    pub fn cancel_order(ctx: Context<CancelOrder>) -> Result<()> {
-        let order = &mut ctx.accounts.order;
+    /// Withdraw a bid from an auction left unsettled past its settlement window
+    pub fn refund_bid(ctx: Context<RefundBid>) -> Result<()> {
+        let bond = &mut ctx.accounts.bond;
+        let bid = &ctx.accounts.bid;
         
-        require!(order.is_active, BtrustError::OrderNotActive);
-        require!(order.seller == ctx.accounts.seller.key(), BtrustError::Unauthorized);
+        require!(!bond.is_settled, BtrustError::AuctionSettled);
+        require!(
+            Clock::get()?.unix_timestamp >= bond.auction_settlement_deadline(),
+            BtrustError::AuctionSettlementOpen
+        );
         
-        // Return bonds from escrow
-        let order_key = order.key();
+        let bond_mint = bond.bond_mint;
         let seeds = &[
-            b"order",
-            order_key.as_ref(),
-            &[order.bump],
+            b"bond",
+            bond_mint.as_ref(),
+            &[bond.bump],
         ];
         let signer_seeds = &[&seeds[..]];
         
@@ -650,523 +1474,6024 @@ pub mod btrust_bond {
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
                 Transfer {
-                    from: ctx.accounts.order_escrow.to_account_info(),
-                    to: ctx.accounts.seller_bond_account.to_account_info(),
-                    authority: order.to_account_info(),
+                    from: ctx.accounts.subscription_vault.to_account_info(),
+                    to: ctx.accounts.bidder_payment.to_account_info(),
+                    authority: bond.to_account_info(),
                 },
                 signer_seeds,
             ),
-            order.quantity,
+            bid.locked_amount,
         )?;
         
-        order.is_active = false;
+        bond.auction_bid_quantity -= bid.quantity;
         
-        emit!(OrderCancelled {
-            order: order.key(),
+        emit!(AuctionBidRefunded {
+            bond: bond.key(),
+            bidder: ctx.accounts.bidder.key(),
+            quantity: bid.quantity,
+            refund_amount: bid.locked_amount,
         });
         
         Ok(())
     }
-}
 
-// ============================================================================
-// Accounts
-// ============================================================================
+    /// Deposit a coupon payment for the next unfunded coupon period
+    pub fn deposit_yield(
+        ctx: Context<DepositYield>,
+        period: u32,
+        amount: u64,
+    ) -> Result<()> {
+        require!(amount > 0, BtrustError::InvalidAmount);
+        
+        let bond = &mut ctx.accounts.bond;
+        require!(bond.is_active, BtrustError::BondNotActive);
+        require!(bond.issuer == ctx.accounts.issuer.key(), BtrustError::Unauthorized);
+        require!(period < bond.coupon_periods, BtrustError::InvalidCouponPeriod);
+        require!(period == bond.coupon_periods_funded, BtrustError::CouponPeriodOutOfOrder);
+        
+        let coupon_period = &mut ctx.accounts.coupon_period;
+        if coupon_period.bond == Pubkey::default() {
+            coupon_period.bond = bond.key();
+            coupon_period.period = period;
+            coupon_period.record_date = bond.coupon_record_date(period);
+            coupon_period.bump = ctx.bumps.coupon_period;
+        }
+        
+        if coupon_period.is_recorded {
+            let remaining_due = coupon_period.amount_due.saturating_sub(coupon_period.amount_funded);
+            require!(amount <= remaining_due, BtrustError::ExceedsCouponDue);
+        }
+        
+        // Transfer yield to yield vault
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.issuer_payment.to_account_info(),
+                    to: ctx.accounts.yield_vault.to_account_info(),
+                    authority: ctx.accounts.issuer.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        
+        coupon_period.amount_funded = coupon_period.amount_funded
+            .checked_add(amount)
+            .ok_or(BtrustError::MathOverflow)?;
+        if coupon_period.is_funded() {
+            bond.mark_period_funded(coupon_period);
+        }
+        bond.last_yield_payment = Clock::get()?.unix_timestamp;
+        
+        emit!(YieldDeposited {
+            bond: bond.key(),
+            period,
+            amount,
+            amount_funded: coupon_period.amount_funded,
+            timestamp: bond.last_yield_payment,
+        });
+        
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct InitializePlatform<'info> {
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + Platform::INIT_SPACE,
-        seeds = [b"platform"],
-        bump,
-    )]
-    pub platform: Account<'info, Platform>,
-    
-    /// CHECK: Treasury account for fees
-    pub treasury: UncheckedAccount<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+    /// Snapshot the coupon rate of a floating-rate bond's period at its fixing date
+    pub fn fix_coupon_rate(ctx: Context<FixCouponRate>, period: u32) -> Result<()> {
+        let bond = &mut ctx.accounts.bond;
+        let reference_rate = &ctx.accounts.reference_rate;
+        
+        require!(bond.is_variable_rate, BtrustError::NotVariableRate);
+        require!(period < bond.coupon_periods, BtrustError::InvalidCouponPeriod);
+        require!(period >= bond.coupon_periods_recorded, BtrustError::CouponPeriodOutOfOrder);
+        require!(!ctx.accounts.coupon_period.is_fixed, BtrustError::CouponRateAlreadyFixed);
+        
+        let now = Clock::get()?.unix_timestamp;
+        let fixing_date = bond.coupon_start_date(period);
+        require!(now >= fixing_date, BtrustError::FixingDateNotReached);
+        
+        // Fix from the rate in effect on the fixing date, whenever this runs
+        let (reference_rate_bps, published_at) = reference_rate
+            .rate_at(fixing_date)
+            .ok_or(BtrustError::StaleReferenceRate)?;
+        require!(
+            fixing_date - published_at <= MAX_RATE_STALENESS_SECS,
+            BtrustError::StaleReferenceRate
+        );
+        
+        let coupon_rate_bps = bond.floating_rate_bps(reference_rate_bps);
+        
+        let coupon_period = &mut ctx.accounts.coupon_period;
+        if coupon_period.bond == Pubkey::default() {
+            coupon_period.bond = bond.key();
+            coupon_period.period = period;
+            coupon_period.record_date = bond.coupon_record_date(period);
+            coupon_period.bump = ctx.bumps.coupon_period;
+        }
+        coupon_period.coupon_rate_bps = coupon_rate_bps;
+        coupon_period.is_fixed = true;
+        
+        emit!(CouponRateFixed {
+            bond: bond.key(),
+            period,
+            fixing_date,
+            reference_rate_bps,
+            coupon_rate_bps,
+        });
+        
+        Ok(())
+    }
 
-#[derive(Accounts)]
-#[instruction(args: CreateBondArgs)]
-pub struct CreateBond<'info> {
-    #[account(mut)]
-    pub issuer: Signer<'info>,
-    
-    #[account(
-        mut,
-        seeds = [b"platform"],
-        bump = platform.bump,
+    /// Record a coupon period once its record date has passed
+    ///
+    /// Snapshots the outstanding supply and credits the period's coupon to the
+    /// yield index, so holders earn it on their record-date balance.
+    /// Permissionless; must be called for each period in order.
+    pub fn record_coupon(ctx: Context<RecordCoupon>, period: u32) -> Result<()> {
+        let bond = &mut ctx.accounts.bond;
+        require_not_paused(&ctx.accounts.platform, bond, PAUSE_YIELD)?;
+        require!(
+            !bond.has_subscription() || bond.is_settled,
+            BtrustError::SubscriptionNotSettled
+        );
+        require!(period < bond.coupon_periods, BtrustError::InvalidCouponPeriod);
+        require!(period == bond.coupon_periods_recorded, BtrustError::CouponPeriodOutOfOrder);
+        
+        let record_date = bond.coupon_record_date(period);
+        require!(
+            Clock::get()?.unix_timestamp >= record_date,
+            BtrustError::CouponRecordDateNotReached
+        );
+        
+        // Floating-rate coupons use the rate fixed at the start of the period,
+        // or the previous period's rate if no fresh reference rate was fixed
+        let coupon_rate_bps = if bond.is_variable_rate && ctx.accounts.coupon_period.is_fixed {
+            ctx.accounts.coupon_period.coupon_rate_bps
+        } else {
+            bond.period_rate_bps(period)
+        };
+        bond.last_fixed_rate_bps = coupon_rate_bps;
+        
+        // An amended rate takes over once its first period is recorded
+        if bond.pending_rate_period > 0 && period >= bond.pending_rate_period {
+            bond.coupon_rate_bps = bond.pending_coupon_rate_bps;
+            bond.pending_rate_period = 0;
+        }
+        
+        // Amortizing bonds repay an installment of principal with each coupon
+        let coupon_per_bond = bond.coupon_per_bond(period, coupon_rate_bps)?;
+        let principal_per_bond = bond.installment_per_bond(period)?;
+        let amount_due = coupon_per_bond
+            .checked_add(principal_per_bond)
+            .ok_or(BtrustError::MathOverflow)?
+            .checked_mul(bond.outstanding_supply)
+            .ok_or(BtrustError::MathOverflow)?;
+        
+        let coupon_period = &mut ctx.accounts.coupon_period;
+        if coupon_period.bond == Pubkey::default() {
+            coupon_period.bond = bond.key();
+            coupon_period.period = period;
+            coupon_period.record_date = record_date;
+            coupon_period.bump = ctx.bumps.coupon_period;
+        }
+        coupon_period.coupon_per_bond = coupon_per_bond;
+        coupon_period.principal_per_bond = principal_per_bond;
+        coupon_period.supply_at_record = bond.outstanding_supply;
+        coupon_period.amount_due = amount_due;
+        coupon_period.is_recorded = true;
+        
+        bond.yield_index = bond.yield_index
+            .checked_add((coupon_per_bond as u128) * YIELD_INDEX_SCALE)
+            .ok_or(BtrustError::MathOverflow)?;
+        bond.amortized_per_bond = bond.amortized_per_bond
+            .checked_add(principal_per_bond)
+            .ok_or(BtrustError::MathOverflow)?;
+        bond.yield_owed = bond.yield_owed
+            .checked_add(amount_due)
+            .ok_or(BtrustError::MathOverflow)?;
+        bond.coupon_periods_recorded += 1;
+        coupon_period.yield_index = bond.yield_index;
+        coupon_period.amortized_per_bond = bond.amortized_per_bond;
+        
+        // A prefunded period becomes funded as soon as its amount due is known
+        if period == bond.coupon_periods_funded && coupon_period.is_funded() {
+            bond.mark_period_funded(coupon_period);
+        }
+        
+        emit!(CouponRecorded {
+            bond: bond.key(),
+            period,
+            record_date,
+            coupon_per_bond,
+            principal_per_bond,
+            coupon_rate_bps,
+            supply_at_record: coupon_period.supply_at_record,
+            amount_due,
+            amount_funded: coupon_period.amount_funded,
+        });
+        
+        Ok(())
+    }
+
+    /// Claim accrued yield
+    pub fn claim_yield(ctx: Context<ClaimYield>) -> Result<()> {
+        let bond = &mut ctx.accounts.bond;
+        let position = &mut ctx.accounts.holder_position;
+        
+        require_not_paused(&ctx.accounts.platform, bond, PAUSE_YIELD)?;
+        require!(position.holder == ctx.accounts.holder.key(), BtrustError::Unauthorized);
+        
+        // Claims are backed by funded coupon periods only
+        position.settle_funded_yield(bond)?;
+        let yield_owed = position.accrued_yield;
+        
+        require!(yield_owed > 0, BtrustError::NoYieldToClaim);
+        
+        // Check yield vault balance
+        let vault_balance = ctx.accounts.yield_vault.amount;
+        let claimable = yield_owed.min(vault_balance);
+        
+        require!(claimable > 0, BtrustError::InsufficientYieldBalance);
+        
+        // Transfer yield to holder
+        let bond_mint = bond.bond_mint;
+        let seeds = &[
+            b"bond",
+            bond_mint.as_ref(),
+            &[bond.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.yield_vault.to_account_info(),
+                    to: ctx.accounts.holder_payment.to_account_info(),
+                    authority: bond.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            claimable,
+        )?;
+        
+        position.accrued_yield -= claimable;
+        position.total_yield_claimed += claimable;
+        bond.total_yield_paid += claimable;
+        bond.yield_owed = bond.yield_owed.saturating_sub(claimable);
+        
+        emit!(YieldClaimed {
+            bond: bond.key(),
+            holder: ctx.accounts.holder.key(),
+            amount: claimable,
+        });
+        
+        Ok(())
+    }
+
+    /// Claim amortized principal installments
+    pub fn claim_principal(ctx: Context<ClaimYield>) -> Result<()> {
+        let bond = &mut ctx.accounts.bond;
+        let position = &mut ctx.accounts.holder_position;
+        
+        require_not_paused(&ctx.accounts.platform, bond, PAUSE_REDEMPTION)?;
+        require!(position.holder == ctx.accounts.holder.key(), BtrustError::Unauthorized);
+        
+        // Installments are funded with their coupon period
+        position.settle_funded_yield(bond)?;
+        let principal_owed = position.accrued_principal;
+        
+        require!(principal_owed > 0, BtrustError::NoPrincipalToClaim);
+        
+        let claimable = principal_owed.min(ctx.accounts.yield_vault.amount);
+        require!(claimable > 0, BtrustError::InsufficientYieldBalance);
+        
+        let bond_mint = bond.bond_mint;
+        let seeds = &[
+            b"bond",
+            bond_mint.as_ref(),
+            &[bond.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.yield_vault.to_account_info(),
+                    to: ctx.accounts.holder_payment.to_account_info(),
+                    authority: bond.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            claimable,
+        )?;
+        
+        position.accrued_principal -= claimable;
+        position.total_principal_claimed += claimable;
+        bond.yield_owed = bond.yield_owed.saturating_sub(claimable);
+        
+        emit!(PrincipalClaimed {
+            bond: bond.key(),
+            holder: ctx.accounts.holder.key(),
+            amount: claimable,
+        });
+        
+        Ok(())
+    }
+
+    /// Flag a bond as defaulted when a recorded coupon stays unfunded past the grace period
+    pub fn mark_default(ctx: Context<MarkDefault>) -> Result<()> {
+        let bond = &mut ctx.accounts.bond;
+        let coupon_period = &ctx.accounts.coupon_period;
+        
+        require!(bond.is_active, BtrustError::BondNotActive);
+        require!(!bond.is_defaulted, BtrustError::BondDefaulted);
+        require!(
+            !bond.has_subscription() || bond.is_settled,
+            BtrustError::SubscriptionNotSettled
+        );
+        require!(
+            coupon_period.is_recorded && !coupon_period.is_funded(),
+            BtrustError::CouponNotMissed
+        );
+        
+        let now = Clock::get()?.unix_timestamp;
+        let deadline = coupon_period.record_date
+            .checked_add(bond.grace_period)
+            .ok_or(BtrustError::MathOverflow)?;
+        require!(now > deadline, BtrustError::GracePeriodNotElapsed);
+        
+        bond.is_defaulted = true;
+        bond.is_active = false;
+        bond.defaulted_at = now;
+        
+        emit!(BondDefaulted {
+            bond: bond.key(),
+            period: coupon_period.period,
+            amount_due: coupon_period.amount_due,
+            amount_funded: coupon_period.amount_funded,
+            last_yield_payment: bond.last_yield_payment,
+            collateral_deposited: bond.collateral_deposited,
+            defaulted_at: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Flag a bond as defaulted when its sinking fund falls behind schedule
+    /// past the grace period
+    pub fn mark_sinking_fund_default(ctx: Context<MarkSinkingFundDefault>) -> Result<()> {
+        let bond = &mut ctx.accounts.bond;
+        
+        require!(bond.is_active, BtrustError::BondNotActive);
+        require!(!bond.is_defaulted, BtrustError::BondDefaulted);
+        require!(
+            !bond.has_subscription() || bond.is_settled,
+            BtrustError::SubscriptionNotSettled
+        );
+        
+        // Each period's deposit is due by its record date plus the grace period
+        let now = Clock::get()?.unix_timestamp;
+        let deadline = now
+            .checked_sub(bond.grace_period)
+            .ok_or(BtrustError::MathOverflow)?;
+        let scheduled = bond.sinking_fund_scheduled(deadline)?;
+        require!(
+            bond.sinking_fund_deposited < scheduled,
+            BtrustError::SinkingFundOnSchedule
+        );
+        
+        bond.is_defaulted = true;
+        bond.is_active = false;
+        bond.defaulted_at = now;
+        
+        emit!(SinkingFundDefaulted {
+            bond: bond.key(),
+            scheduled,
+            deposited: bond.sinking_fund_deposited,
+            defaulted_at: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Flag a bond as defaulted when principal stays unescrowed past the grace
+    /// period after it fell due
+    pub fn mark_principal_default(ctx: Context<MarkPrincipalDefault>) -> Result<()> {
+        let bond = &mut ctx.accounts.bond;
+        
+        require!(bond.is_active, BtrustError::BondNotActive);
+        require!(!bond.is_defaulted, BtrustError::BondDefaulted);
+        require!(
+            !bond.has_subscription() || bond.is_settled,
+            BtrustError::SubscriptionNotSettled
+        );
+        
+        let now = Clock::get()?.unix_timestamp;
+        let deadline = now
+            .checked_sub(bond.grace_period)
+            .ok_or(BtrustError::MathOverflow)?;
+        require!(bond.principal_overdue(deadline)?, BtrustError::PrincipalNotOverdue);
+        
+        emit!(PrincipalDefaulted {
+            bond: bond.key(),
+            redemption_date: bond.redemption_date(),
+            outstanding_debt: bond.outstanding_debt()?,
+            defaulted_at: now,
+        });
+        
+        bond.is_defaulted = true;
+        bond.is_active = false;
+        bond.defaulted_at = now;
+        
+        Ok(())
+    }
+
+    /// Burn bonds of a defaulted or liquidated bond for a pro-rata share of what remains
+    pub fn claim_recovery(ctx: Context<ClaimRecovery>, quantity: u64) -> Result<()> {
+        let bond = &mut ctx.accounts.bond;
+        let position = &mut ctx.accounts.holder_position;
+        
+        require_not_paused(&ctx.accounts.platform, bond, PAUSE_REDEMPTION)?;
+        require!(bond.in_recovery(), BtrustError::BondNotInRecovery);
+        require!(
+            !bond.has_subscription() || bond.is_settled,
+            BtrustError::SubscriptionNotSettled
+        );
+        require!(
+            ctx.accounts.sinking_fund.is_some() == bond.has_sinking_fund,
+            BtrustError::VaultMissing
+        );
+        
+        // Settle yield on the pre-burn balance before it changes
+        position.settle_yield(bond)?;
+        require!(position.quantity >= quantity, BtrustError::InsufficientBalance);
+        
+        let owed = position.accrued_yield
+            .checked_add(position.accrued_principal)
+            .ok_or(BtrustError::MathOverflow)?;
+        require!(quantity > 0 || owed > 0, BtrustError::InvalidAmount);
+        
+        let yield_vault = ctx.accounts.yield_vault.amount;
+        let owed_amount = if bond.yield_owed > yield_vault {
+            pro_rata(yield_vault, owed.min(bond.yield_owed), bond.yield_owed)?
+        } else {
+            owed
+        };
+        let unallocated = yield_vault.saturating_sub(bond.yield_owed);
+        
+        let supply = bond.outstanding_supply;
+        let (collateral_amount, principal_amount, unallocated_amount) = if quantity > 0 {
+            (
+                pro_rata(ctx.accounts.collateral_vault.amount, quantity, supply)?,
+                pro_rata(ctx.accounts.redemption_vault.amount, quantity, supply)?,
+                pro_rata(unallocated, quantity, supply)?,
+            )
+        } else {
+            (0, 0, 0)
+        };
+        // Draws lapse in recovery, so the whole sinking fund is shared out
+        let sinking_fund_amount = match &ctx.accounts.sinking_fund {
+            Some(sinking_fund) if quantity > 0 => pro_rata(sinking_fund.amount, quantity, supply)?,
+            _ => 0,
+        };
+        let yield_amount = owed_amount
+            .checked_add(unallocated_amount)
+            .ok_or(BtrustError::MathOverflow)?;
+        
+        // Burn bond tokens
+        thaw_holder_account(
+            &ctx.accounts.token_program,
+            bond,
+            &ctx.accounts.bond_mint,
+            &ctx.accounts.holder_bond_account,
+        )?;
+        
+        token::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.bond_mint.to_account_info(),
+                    from: ctx.accounts.holder_bond_account.to_account_info(),
+                    authority: ctx.accounts.holder.to_account_info(),
+                },
+            ),
+            quantity,
+        )?;
+        
+        freeze_holder_account(
+            &ctx.accounts.token_program,
+            bond,
+            &ctx.accounts.bond_mint,
+            &mut ctx.accounts.holder_bond_account,
+        )?;
+        
+        let bond_mint = bond.bond_mint;
+        let seeds = &[
+            b"bond",
+            bond_mint.as_ref(),
+            &[bond.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        let sinking_fund_payout = ctx.accounts.sinking_fund
+            .as_ref()
+            .map(|sinking_fund| (sinking_fund, &ctx.accounts.holder_payment, sinking_fund_amount));
+        for (from, to, amount) in [
+            (&ctx.accounts.collateral_vault, &ctx.accounts.holder_collateral, collateral_amount),
+            (&ctx.accounts.redemption_vault, &ctx.accounts.holder_payment, principal_amount),
+            (&ctx.accounts.yield_vault, &ctx.accounts.holder_payment, yield_amount),
+        ]
+        .into_iter()
+        .chain(sinking_fund_payout)
+        {
+            if amount == 0 {
+                continue;
+            }
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: from.to_account_info(),
+                        to: to.to_account_info(),
+                        authority: bond.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                amount,
+            )?;
+        }
+        
+        bond.outstanding_supply = bond.outstanding_supply
+            .checked_sub(quantity)
+            .ok_or(BtrustError::MathOverflow)?;
+        bond.recovery_claimed = true;
+        bond.collateral_deposited = bond.collateral_deposited.saturating_sub(collateral_amount);
+        bond.principal_repaid = bond.principal_repaid.saturating_sub(principal_amount);
+        bond.total_yield_paid += yield_amount;
+        bond.yield_owed = bond.yield_owed.saturating_sub(owed);
+        position.accrued_yield = 0;
+        position.accrued_principal = 0;
+        position.quantity -= quantity;
+        position.release_drawn(bond);
+        
+        emit!(RecoveryClaimed {
+            bond: bond.key(),
+            holder: ctx.accounts.holder.key(),
+            quantity,
+            collateral_amount,
+            principal_amount,
+            yield_amount,
+            sinking_fund_amount,
+        });
+        
+        Ok(())
+    }
+
+    /// Announce an early redemption of a callable bond
+    pub fn call_bond(ctx: Context<CallBond>, call_date: i64) -> Result<()> {
+        let bond = &mut ctx.accounts.bond;
+        
+        require_not_paused(&ctx.accounts.platform, bond, PAUSE_REDEMPTION)?;
+        require!(bond.is_active, BtrustError::BondNotActive);
+        require!(bond.first_call_date > 0, BtrustError::NotCallable);
+        require!(!bond.is_called(), BtrustError::BondCalled);
+        require!(!bond.in_recovery(), BtrustError::BondInRecovery);
+        require!(
+            !(bond.has_subscription() || bond.has_auction()) || bond.is_settled,
+            BtrustError::PrimarySaleOpen
+        );
+        
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            call_date >= bond.first_call_date && call_date < bond.maturity_timestamp,
+            BtrustError::InvalidCallDate
+        );
+        require!(
+            call_date >= now.checked_add(bond.call_notice_period).ok_or(BtrustError::MathOverflow)?,
+            BtrustError::CallNoticeTooShort
+        );
+        
+        // Only coupon periods that end by the call date are still paid as coupons
+        let interval = bond.coupon_interval();
+        let remaining_periods = ((call_date - bond.created_at) / interval) as u32;
+        let coupon_periods = remaining_periods.min(bond.coupon_periods);
+        
+        // The stub period up to the call date is paid with the call price
+        let stub_start = bond.coupon_start_date(coupon_periods);
+        let face_value = bond.face_value_at(coupon_periods)?;
+        let full_coupon = bond.full_coupon(face_value, bond.period_rate_bps(coupon_periods))?;
+        let accrued_coupon = ((full_coupon as u128) * ((call_date - stub_start) as u128)
+            / (interval as u128)) as u64;
+        
+        let call_price = face_value
+            .checked_mul(bond.call_price_bps(call_date))
+            .ok_or(BtrustError::MathOverflow)?
+            .checked_div(BPS_DENOMINATOR)
+            .ok_or(BtrustError::MathOverflow)?
+            .checked_add(accrued_coupon)
+            .ok_or(BtrustError::MathOverflow)?;
+        
+        let required = call_price
+            .checked_mul(bond.outstanding_supply)
+            .ok_or(BtrustError::MathOverflow)?;
+        let amount_funded = required.saturating_sub(bond.principal_repaid);
+        
+        if amount_funded > 0 {
+            token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.issuer_payment.to_account_info(),
+                        to: ctx.accounts.redemption_vault.to_account_info(),
+                        authority: ctx.accounts.issuer.to_account_info(),
+                    },
+                ),
+                amount_funded,
+            )?;
+        }
+        
+        bond.principal_repaid = bond.principal_repaid
+            .checked_add(amount_funded)
+            .ok_or(BtrustError::MathOverflow)?;
+        bond.coupon_periods = coupon_periods;
+        bond.call_date = call_date;
+        bond.call_price = call_price;
+        
+        emit!(BondCalled {
+            bond: bond.key(),
+            call_date,
+            call_price,
+            accrued_coupon,
+            amount_funded,
+            outstanding_supply: bond.outstanding_supply,
+        });
+        
+        Ok(())
+    }
+
+    /// Tender bonds for redemption at the put price during a put window
+    pub fn exercise_put(ctx: Context<ExercisePut>, quantity: u64) -> Result<()> {
+        require!(quantity > 0, BtrustError::InvalidAmount);
+        
+        let bond = &mut ctx.accounts.bond;
+        let position = &mut ctx.accounts.holder_position;
+        
+        require_not_paused(&ctx.accounts.platform, bond, PAUSE_REDEMPTION)?;
+        require!(bond.is_active, BtrustError::BondNotActive);
+        require!(!bond.in_recovery(), BtrustError::BondInRecovery);
+        require!(!bond.is_called(), BtrustError::BondCalled);
+        
+        let now = Clock::get()?.unix_timestamp;
+        let window_end = bond.put_window_end(now).ok_or(BtrustError::PutWindowClosed)?;
+        require!(
+            position.put_tendered == 0 || position.put_window_end == window_end,
+            BtrustError::PutPending
+        );
+        
+        position.settle_yield(bond)?;
+        require!(quantity <= position.free_quantity(), BtrustError::InsufficientBalance);
+        
+        thaw_holder_account(
+            &ctx.accounts.token_program,
+            bond,
+            &ctx.accounts.bond_mint,
+            &ctx.accounts.holder_bond_account,
+        )?;
+        
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.holder_bond_account.to_account_info(),
+                    to: ctx.accounts.put_escrow.to_account_info(),
+                    authority: ctx.accounts.holder.to_account_info(),
+                },
+            ),
+            quantity,
+        )?;
+        
+        freeze_holder_account(
+            &ctx.accounts.token_program,
+            bond,
+            &ctx.accounts.bond_mint,
+            &mut ctx.accounts.holder_bond_account,
+        )?;
+        
+        bond.roll_put_window(now);
+        bond.put_tendered = bond.put_tendered
+            .checked_add(quantity)
+            .ok_or(BtrustError::MathOverflow)?;
+        bond.put_window_end = window_end;
+        position.put_tendered += quantity;
+        position.put_window_end = window_end;
+        
+        emit!(PutTendered {
+            bond: bond.key(),
+            holder: ctx.accounts.holder.key(),
+            quantity,
+            window_end,
+        });
+        
+        Ok(())
+    }
+
+    /// Collect a put tender once its window has closed
+    pub fn claim_put(ctx: Context<ClaimPut>) -> Result<()> {
+        let bond = &mut ctx.accounts.bond;
+        let position = &mut ctx.accounts.holder_position;
+        
+        require_not_paused(&ctx.accounts.platform, bond, PAUSE_REDEMPTION)?;
+        
+        let quantity = position.put_tendered;
+        require!(quantity > 0, BtrustError::NothingToRedeem);
+        
+        let now = Clock::get()?.unix_timestamp;
+        require!(now >= position.put_window_end, BtrustError::PutWindowOpen);
+        bond.roll_put_window(now);
+        
+        position.settle_yield(bond)?;
+        
+        let put_price = bond.put_price()?;
+        let vault_balance = ctx.accounts.redemption_vault.amount;
+        if !bond.in_recovery() {
+            let put_due = put_price
+                .checked_mul(bond.put_due)
+                .ok_or(BtrustError::MathOverflow)?;
+            if vault_balance < put_due {
+                // The issuer has the grace period after the window to fund puts
+                let deadline = position.put_window_end
+                    .checked_add(bond.grace_period)
+                    .ok_or(BtrustError::MathOverflow)?;
+                require!(now > deadline, BtrustError::PutPaymentPending);
+                
+                bond.is_defaulted = true;
+                bond.is_active = false;
+                bond.defaulted_at = now;
+                
+                emit!(PutDefaulted {
+                    bond: bond.key(),
+                    holder: ctx.accounts.holder.key(),
+                    quantity: bond.put_due,
+                    put_amount: put_due,
+                    vault_balance,
+                    defaulted_at: now,
+                });
+            }
+        }
+        
+        let bond_mint = bond.bond_mint;
+        let seeds = &[
+            b"bond",
+            bond_mint.as_ref(),
+            &[bond.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        bond.put_due = bond.put_due
+            .checked_sub(quantity)
+            .ok_or(BtrustError::MathOverflow)?;
+        position.put_tendered = 0;
+        
+        // A defaulted bond hands tendered bonds back for recovery
+        if bond.in_recovery() {
+            thaw_holder_account(
+                &ctx.accounts.token_program,
+                bond,
+                &ctx.accounts.bond_mint,
+                &ctx.accounts.holder_bond_account,
+            )?;
+            
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.put_escrow.to_account_info(),
+                        to: ctx.accounts.holder_bond_account.to_account_info(),
+                        authority: bond.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                quantity,
+            )?;
+            
+            freeze_holder_account(
+                &ctx.accounts.token_program,
+                bond,
+                &ctx.accounts.bond_mint,
+                &mut ctx.accounts.holder_bond_account,
+            )?;
+            
+            emit!(PutReturned {
+                bond: bond.key(),
+                holder: ctx.accounts.holder.key(),
+                quantity,
+            });
+            
+            return Ok(());
+        }
+        
+        let put_amount = put_price
+            .checked_mul(quantity)
+            .ok_or(BtrustError::MathOverflow)?;
+        
+        token::burn(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.bond_mint.to_account_info(),
+                    from: ctx.accounts.put_escrow.to_account_info(),
+                    authority: bond.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            quantity,
+        )?;
+        
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.redemption_vault.to_account_info(),
+                    to: ctx.accounts.holder_payment.to_account_info(),
+                    authority: bond.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            put_amount,
+        )?;
+        
+        bond.outstanding_supply = bond.outstanding_supply
+            .checked_sub(quantity)
+            .ok_or(BtrustError::MathOverflow)?;
+        bond.principal_repaid = bond.principal_repaid.saturating_sub(put_amount);
+        position.quantity -= quantity;
+        position.release_drawn(bond);
+        
+        emit!(PutExercised {
+            bond: bond.key(),
+            holder: ctx.accounts.holder.key(),
+            quantity,
+            put_amount,
+        });
+        
+        Ok(())
+    }
+
+    /// Escrow conversion tokens for a convertible bond
+    pub fn fund_conversion(ctx: Context<FundConversion>, amount: u64) -> Result<()> {
+        require!(amount > 0, BtrustError::InvalidAmount);
+        
+        let bond = &mut ctx.accounts.bond;
+        require!(bond.is_convertible(), BtrustError::NotConvertible);
+        bond.has_conversion_vault = true;
+        
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.issuer_conversion_account.to_account_info(),
+                    to: ctx.accounts.conversion_vault.to_account_info(),
+                    authority: ctx.accounts.issuer.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        
+        emit!(ConversionFunded {
+            bond: bond.key(),
+            amount,
+            total_escrowed: ctx.accounts.conversion_vault.amount + amount,
+        });
+        
+        Ok(())
+    }
+
+    /// Convert bonds into the conversion token at the bond's fixed ratio
+    pub fn convert_bond(ctx: Context<ConvertBond>, quantity: u64) -> Result<()> {
+        require!(quantity > 0, BtrustError::InvalidAmount);
+        
+        let bond = &mut ctx.accounts.bond;
+        let position = &mut ctx.accounts.holder_position;
+        
+        require_not_paused(&ctx.accounts.platform, bond, PAUSE_REDEMPTION)?;
+        require!(bond.is_convertible(), BtrustError::NotConvertible);
+        require!(bond.is_active, BtrustError::BondNotActive);
+        require!(!bond.in_recovery(), BtrustError::BondInRecovery);
+        require!(!bond.is_called(), BtrustError::BondCalled);
+        require!(
+            !bond.has_subscription() || bond.is_settled,
+            BtrustError::SubscriptionNotSettled
+        );
+        
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now >= bond.conversion_start && now < bond.conversion_end,
+            BtrustError::ConversionWindowClosed
+        );
+        
+        // Settle yield on the pre-conversion balance before it changes
+        position.settle_yield(bond)?;
+        require!(quantity <= position.free_quantity(), BtrustError::InsufficientBalance);
+        
+        let conversion_amount = bond.conversion_ratio
+            .checked_mul(quantity)
+            .ok_or(BtrustError::MathOverflow)?;
+        require!(
+            ctx.accounts.conversion_vault.amount >= conversion_amount,
+            BtrustError::InsufficientConversionBalance
+        );
+        
+        thaw_holder_account(
+            &ctx.accounts.token_program,
+            bond,
+            &ctx.accounts.bond_mint,
+            &ctx.accounts.holder_bond_account,
+        )?;
+        
+        token::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.bond_mint.to_account_info(),
+                    from: ctx.accounts.holder_bond_account.to_account_info(),
+                    authority: ctx.accounts.holder.to_account_info(),
+                },
+            ),
+            quantity,
+        )?;
+        
+        freeze_holder_account(
+            &ctx.accounts.token_program,
+            bond,
+            &ctx.accounts.bond_mint,
+            &mut ctx.accounts.holder_bond_account,
+        )?;
+        
+        let bond_mint = bond.bond_mint;
+        let seeds = &[
+            b"bond",
+            bond_mint.as_ref(),
+            &[bond.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.conversion_vault.to_account_info(),
+                    to: ctx.accounts.holder_conversion_account.to_account_info(),
+                    authority: bond.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            conversion_amount,
+        )?;
+        
+        bond.outstanding_supply -= quantity;
+        position.quantity -= quantity;
+        position.release_drawn(bond);
+        
+        emit!(BondConverted {
+            bond: bond.key(),
+            holder: ctx.accounts.holder.key(),
+            quantity,
+            conversion_amount,
+        });
+        
+        Ok(())
+    }
+
+    /// Return unused conversion tokens to the issuer once the window has closed
+    pub fn reclaim_conversion(ctx: Context<ReclaimConversion>) -> Result<()> {
+        let bond = &ctx.accounts.bond;
+        require_not_paused(&ctx.accounts.platform, bond, PAUSE_REDEMPTION)?;
+        require!(bond.is_convertible(), BtrustError::NotConvertible);
+        require!(
+            Clock::get()?.unix_timestamp >= bond.conversion_end,
+            BtrustError::ConversionWindowOpen
+        );
+        
+        let amount = ctx.accounts.conversion_vault.amount;
+        require!(amount > 0, BtrustError::NothingToReclaim);
+        
+        let bond_mint = bond.bond_mint;
+        let seeds = &[
+            b"bond",
+            bond_mint.as_ref(),
+            &[bond.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.conversion_vault.to_account_info(),
+                    to: ctx.accounts.issuer_conversion_account.to_account_info(),
+                    authority: bond.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+        
+        emit!(ConversionReclaimed {
+            bond: bond.key(),
+            amount,
+        });
+        
+        Ok(())
+    }
+
+    /// Deposit into a bond's sinking fund
+    pub fn fund_sinking_fund(ctx: Context<FundSinkingFund>, amount: u64) -> Result<()> {
+        require!(amount > 0, BtrustError::InvalidAmount);
+        
+        let bond = &mut ctx.accounts.bond;
+        require!(!bond.in_recovery(), BtrustError::BondInRecovery);
+        
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.issuer_payment.to_account_info(),
+                    to: ctx.accounts.sinking_fund.to_account_info(),
+                    authority: ctx.accounts.issuer.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        
+        bond.sinking_fund_deposited = bond.sinking_fund_deposited
+            .checked_add(amount)
+            .ok_or(BtrustError::MathOverflow)?;
+        bond.has_sinking_fund = true;
+        
+        emit!(SinkingFundDeposited {
+            bond: bond.key(),
+            amount,
+            total_deposited: bond.sinking_fund_deposited,
+            scheduled_to_date: bond.sinking_fund_scheduled(Clock::get()?.unix_timestamp)?,
+        });
+        
+        Ok(())
+    }
+
+    /// Drop filled and cancelled orders from the front of the retire queue
+    pub fn prune_retire_queue(ctx: Context<PruneRetireQueue>) -> Result<()> {
+        let bond = &mut ctx.accounts.bond;
+        require!(!ctx.remaining_accounts.is_empty(), BtrustError::InvalidRetireQueue);
+        
+        for info in ctx.remaining_accounts {
+            require!(
+                info.key() == bond.retire_queue_head
+                    && info.owner == &crate::ID
+                    && info.is_writable,
+                BtrustError::InvalidRetireQueue
+            );
+            let mut stale = Order::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+            require!(!stale.is_active, BtrustError::InvalidRetireQueue);
+            
+            bond.retire_queue_head = stale.next_retirable;
+            stale.is_retirable = false;
+            stale.next_retirable = Pubkey::default();
+            stale.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+        }
+        
+        emit!(RetireQueuePruned {
+            bond: bond.key(),
+            pruned: ctx.remaining_accounts.len() as u32,
+            head: bond.retire_queue_head,
+        });
+        
+        Ok(())
+    }
+
+    /// Retire bonds by buying them out of the cheapest sell order with sinking fund money
+    pub fn retire_from_sinking_fund(
+        ctx: Context<RetireFromSinkingFund>,
+        quantity: u64,
+    ) -> Result<()> {
+        require!(quantity > 0, BtrustError::InvalidAmount);
+        
+        let bond = &mut ctx.accounts.bond;
+        let order = &mut ctx.accounts.order;
+        
+        require_not_paused(&ctx.accounts.platform, bond, PAUSE_REDEMPTION)?;
+        require!(!bond.in_recovery(), BtrustError::BondInRecovery);
+        require!(
+            order.key() == bond.retire_queue_head,
+            BtrustError::NotLowestPricedOrder
+        );
+        require!(order.is_active, BtrustError::OrderNotActive);
+        require!(quantity <= order.quantity, BtrustError::ExceedsOrderQuantity);
+        require!(
+            order.price_per_bond <= bond.face_value(),
+            BtrustError::OrderAbovePar
+        );
+        
+        let payment_amount = order.price_per_bond
+            .checked_mul(quantity)
+            .ok_or(BtrustError::MathOverflow)?;
+        bond.expire_sinking_draw(Clock::get()?.unix_timestamp);
+        let available = ctx.accounts.sinking_fund.amount.saturating_sub(bond.sinking_fund_reserved()?);
+        require!(payment_amount <= available, BtrustError::InsufficientSinkingFund);
+        
+        let bond_mint = bond.bond_mint;
+        let seeds = &[
+            b"bond",
+            bond_mint.as_ref(),
+            &[bond.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.sinking_fund.to_account_info(),
+                    to: ctx.accounts.seller_payment.to_account_info(),
+                    authority: bond.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            payment_amount,
+        )?;
+        
+        // Burn the retired bonds straight out of the order escrow
+        let created_at = order.created_at.to_le_bytes();
+        let order_seeds = &[
+            b"order",
+            order.seller.as_ref(),
+            order.bond.as_ref(),
+            created_at.as_ref(),
+            &[order.bump],
+        ];
+        let order_signer_seeds = &[&order_seeds[..]];
+        
+        token::burn(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.bond_mint.to_account_info(),
+                    from: ctx.accounts.order_escrow.to_account_info(),
+                    authority: order.to_account_info(),
+                },
+                order_signer_seeds,
+            ),
+            quantity,
+        )?;
+        
+        order.quantity -= quantity;
+        if order.quantity == 0 {
+            order.is_active = false;
+            order.is_retirable = false;
+            bond.retire_queue_head = order.next_retirable;
+        }
+        
+        let seller_position = &mut ctx.accounts.seller_position;
+        seller_position.settle_yield(bond)?;
+        seller_position.quantity -= quantity;
+        seller_position.escrowed_quantity -= quantity;
+        bond.escrowed_supply -= quantity;
+        
+        bond.outstanding_supply = bond.outstanding_supply
+            .checked_sub(quantity)
+            .ok_or(BtrustError::MathOverflow)?;
+        bond.sinking_fund_retired += quantity;
+        
+        emit!(SinkingFundRetired {
+            bond: bond.key(),
+            holder: order.seller,
+            order: Some(order.key()),
+            quantity,
+            payment_amount,
+        });
+        
+        Ok(())
+    }
+
+    /// Commit to drawing bonds for pro-rata retirement at face value from the sinking fund
+    pub fn draw_sinking_fund(ctx: Context<DrawSinkingFund>, quantity: u64) -> Result<()> {
+        require!(quantity > 0, BtrustError::InvalidAmount);
+        
+        let bond = &mut ctx.accounts.bond;
+        require_not_paused(&ctx.accounts.platform, bond, PAUSE_REDEMPTION)?;
+        require!(!bond.in_recovery(), BtrustError::BondInRecovery);
+        
+        // An expired draw releases what it left unallocated
+        let now = Clock::get()?.unix_timestamp;
+        bond.expire_sinking_draw(now);
+        require!(
+            bond.sinking_draw_slot == 0 && bond.sinking_draw_remaining == 0,
+            BtrustError::SinkingDrawInProgress
+        );
+        require!(quantity <= bond.sinking_draw_supply()?, BtrustError::ExceedsSupply);
+        
+        let reserve = bond.face_value()
+            .checked_mul(quantity)
+            .ok_or(BtrustError::MathOverflow)?;
+        let available = ctx.accounts.sinking_fund.amount.saturating_sub(bond.sinking_fund_reserved()?);
+        require!(reserve <= available, BtrustError::InsufficientSinkingFund);
+        
+        // The seed comes from a slot hash the issuer cannot know yet
+        let reveal_slot = Clock::get()?.slot + SINKING_DRAW_DELAY_SLOTS;
+        bond.sinking_draw_remaining = quantity;
+        bond.sinking_draw_slot = reveal_slot;
+        
+        emit!(SinkingDrawCommitted {
+            bond: bond.key(),
+            draw: bond.sinking_fund_draws,
+            quantity,
+            reveal_slot,
+        });
+        
+        Ok(())
+    }
+
+    /// Seed a committed sinking fund draw from its slot hash and open it for allocation
+    pub fn reveal_sinking_draw(ctx: Context<RevealSinkingDraw>) -> Result<()> {
+        let bond = &mut ctx.accounts.bond;
+        let clock = Clock::get()?;
+        
+        require!(!bond.in_recovery(), BtrustError::BondInRecovery);
+        require!(bond.sinking_draw_slot > 0, BtrustError::NoSinkingDrawCommitted);
+        require!(clock.slot > bond.sinking_draw_slot, BtrustError::SinkingDrawNotRevealable);
+        
+        // A slot hash that aged out of the sysvar moves the draw to a later slot
+        let Some(slot_hash) = find_slot_hash(&ctx.accounts.slot_hashes, bond.sinking_draw_slot)? else {
+            bond.sinking_draw_slot = clock.slot + SINKING_DRAW_DELAY_SLOTS;
+            emit!(SinkingDrawCommitted {
+                bond: bond.key(),
+                draw: bond.sinking_fund_draws,
+                quantity: bond.sinking_draw_remaining,
+                reveal_slot: bond.sinking_draw_slot,
+            });
+            return Ok(());
+        };
+        
+        // Escrow may have grown since the commit; only free bonds can be drawn
+        let eligible_supply = bond.sinking_draw_supply()?;
+        let quantity = bond.sinking_draw_remaining.min(eligible_supply);
+        
+        let draw = bond.sinking_fund_draws;
+        bond.sinking_draw_seed = hashv(&[
+            bond.key().as_ref(),
+            &draw.to_le_bytes(),
+            &slot_hash,
+        ])
+        .to_bytes();
+        bond.sinking_draw_slot = 0;
+        bond.sinking_draw_revealed_at = Clock::get()?.unix_timestamp;
+        bond.sinking_draw_remaining = quantity;
+        bond.sinking_draw_eligible = eligible_supply;
+        bond.sinking_fund_draws += 1;
+        
+        emit!(SinkingFundDrawn {
+            bond: bond.key(),
+            draw,
+            quantity,
+            eligible_supply,
+            price: bond.face_value(),
+        });
+        
+        Ok(())
+    }
+
+    /// Allocate a holder's share of the latest sinking fund draw
+    pub fn settle_sinking_draw(ctx: Context<SettleSinkingDraw>) -> Result<()> {
+        let bond = &mut ctx.accounts.bond;
+        let position = &mut ctx.accounts.holder_position;
+        
+        require!(!bond.in_recovery(), BtrustError::BondInRecovery);
+        require!(bond.sinking_draw_slot == 0, BtrustError::SinkingDrawNotRevealed);
+        require!(
+            position.sinking_draw_checkpoint < bond.sinking_fund_draws,
+            BtrustError::SinkingDrawSettled
+        );
+        
+        let drawn_before = position.drawn_quantity;
+        position.settle_yield(bond)?;
+        
+        emit!(SinkingDrawSettled {
+            bond: bond.key(),
+            holder: position.holder,
+            draw: bond.sinking_fund_draws - 1,
+            quantity: position.drawn_quantity - drawn_before,
+            remaining: bond.sinking_draw_remaining,
+        });
+        
+        Ok(())
+    }
+
+    /// Redeem the holder's bonds drawn by the sinking fund at face value
+    pub fn redeem_sinking_fund_draw(ctx: Context<RedeemSinkingFundDraw>) -> Result<()> {
+        let bond = &mut ctx.accounts.bond;
+        let position = &mut ctx.accounts.holder_position;
+        
+        require_not_paused(&ctx.accounts.platform, bond, PAUSE_REDEMPTION)?;
+        require!(!bond.in_recovery(), BtrustError::BondInRecovery);
+        
+        // Settle yield on the pre-retirement balance before it changes
+        position.settle_yield(bond)?;
+        
+        let quantity = position.drawn_quantity;
+        require!(quantity > 0, BtrustError::NothingToRedeem);
+        
+        let payment_amount = bond.face_value()
+            .checked_mul(quantity)
+            .ok_or(BtrustError::MathOverflow)?;
+        
+        thaw_holder_account(
+            &ctx.accounts.token_program,
+            bond,
+            &ctx.accounts.bond_mint,
+            &ctx.accounts.holder_bond_account,
+        )?;
+        
+        token::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.bond_mint.to_account_info(),
+                    from: ctx.accounts.holder_bond_account.to_account_info(),
+                    authority: ctx.accounts.holder.to_account_info(),
+                },
+            ),
+            quantity,
+        )?;
+        
+        freeze_holder_account(
+            &ctx.accounts.token_program,
+            bond,
+            &ctx.accounts.bond_mint,
+            &mut ctx.accounts.holder_bond_account,
+        )?;
+        
+        let bond_mint = bond.bond_mint;
+        let seeds = &[
+            b"bond",
+            bond_mint.as_ref(),
+            &[bond.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.sinking_fund.to_account_info(),
+                    to: ctx.accounts.holder_payment.to_account_info(),
+                    authority: bond.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            payment_amount,
+        )?;
+        
+        position.quantity -= quantity;
+        position.drawn_quantity = 0;
+        bond.outstanding_supply = bond.outstanding_supply
+            .checked_sub(quantity)
+            .ok_or(BtrustError::MathOverflow)?;
+        bond.sinking_drawn_pending = bond.sinking_drawn_pending.saturating_sub(quantity);
+        bond.sinking_fund_retired += quantity;
+        
+        emit!(SinkingFundRetired {
+            bond: bond.key(),
+            holder: ctx.accounts.holder.key(),
+            order: None,
+            quantity,
+            payment_amount,
+        });
+        
+        Ok(())
+    }
+
+    /// Return what is left in the sinking fund once no bonds are outstanding
+    pub fn withdraw_sinking_fund(ctx: Context<WithdrawSinkingFund>) -> Result<()> {
+        let bond = &ctx.accounts.bond;
+        
+        require!(bond.outstanding_supply == 0, BtrustError::BondsOutstanding);
+        require!(bond.pending_auction_debt()? == 0, BtrustError::BondsOutstanding);
+        
+        let amount = ctx.accounts.sinking_fund.amount;
+        require!(amount > 0, BtrustError::NothingToReclaim);
+        
+        let bond_mint = bond.bond_mint;
+        let seeds = &[
+            b"bond",
+            bond_mint.as_ref(),
+            &[bond.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.sinking_fund.to_account_info(),
+                    to: ctx.accounts.issuer_payment.to_account_info(),
+                    authority: bond.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+        
+        emit!(SinkingFundWithdrawn {
+            bond: bond.key(),
+            amount,
+        });
+        
+        Ok(())
+    }
+
+    /// Redeem bonds at maturity
+    pub fn redeem_bond(ctx: Context<RedeemBond>, quantity: u64) -> Result<()> {
+        require!(quantity > 0, BtrustError::InvalidAmount);
+        
+        let bond = &mut ctx.accounts.bond;
+        let position = &mut ctx.accounts.holder_position;
+        
+        require_not_paused(&ctx.accounts.platform, bond, PAUSE_REDEMPTION)?;
+        require!(!bond.in_recovery(), BtrustError::BondInRecovery);
+        require!(
+            Clock::get()?.unix_timestamp >= bond.redemption_date(),
+            BtrustError::BondNotMatured
+        );
+        
+        // Settle yield on the pre-redemption balance before it changes
+        position.settle_yield(bond)?;
+        require!(position.quantity >= quantity, BtrustError::InsufficientBalance);
+        
+        let redemption_amount = bond.redemption_price()
+            .checked_mul(quantity)
+            .ok_or(BtrustError::MathOverflow)?;
+        
+        // Burn bond tokens
+        thaw_holder_account(
+            &ctx.accounts.token_program,
+            bond,
+            &ctx.accounts.bond_mint,
+            &ctx.accounts.holder_bond_account,
+        )?;
+        
+        token::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.bond_mint.to_account_info(),
+                    from: ctx.accounts.holder_bond_account.to_account_info(),
+                    authority: ctx.accounts.holder.to_account_info(),
+                },
+            ),
+            quantity,
+        )?;
+        
+        freeze_holder_account(
+            &ctx.accounts.token_program,
+            bond,
+            &ctx.accounts.bond_mint,
+            &mut ctx.accounts.holder_bond_account,
+        )?;
+        
+        // Transfer principal from redemption vault
+        let bond_mint = bond.bond_mint;
+        let seeds = &[
+            b"bond",
+            bond_mint.as_ref(),
+            &[bond.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.redemption_vault.to_account_info(),
+                    to: ctx.accounts.holder_payment.to_account_info(),
+                    authority: bond.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            redemption_amount,
+        )?;
+        
+        bond.outstanding_supply -= quantity;
+        bond.principal_repaid = bond.principal_repaid.saturating_sub(redemption_amount);
+        position.quantity -= quantity;
+        position.release_drawn(bond);
+        
+        if bond.outstanding_supply == 0 {
+            bond.is_matured = true;
+            bond.is_active = false;
+        }
+        
+        emit!(BondRedeemed {
+            bond: bond.key(),
+            holder: ctx.accounts.holder.key(),
+            quantity,
+            redemption_amount,
+        });
+        
+        Ok(())
+    }
+
+    /// Partially liquidate an undercollateralized bond
+    pub fn liquidate(ctx: Context<Liquidate>, repay_amount: u64) -> Result<()> {
+        require!(repay_amount > 0, BtrustError::InvalidAmount);
+        
+        let bond = &mut ctx.accounts.bond;
+        
+        require_not_paused(&ctx.accounts.platform, bond, PAUSE_LIQUIDATION)?;
+        require!(bond.is_active, BtrustError::BondNotActive);
+        require!(!bond.is_unsecured, BtrustError::NotLiquidatable);
+        require!(bond.collateral_deposited > 0, BtrustError::NoCollateral);
+        
+        // Principal still owed to holders after earlier repayments
+        let outstanding_value = bond.outstanding_debt()?;
+        
+        let required_collateral = outstanding_value
+            .checked_mul(LIQUIDATION_THRESHOLD_BPS)
+            .ok_or(BtrustError::MathOverflow)?
+            .checked_div(BPS_DENOMINATOR)
+            .ok_or(BtrustError::MathOverflow)?;
+        
+        // Value collateral in payment token units using the oracle price
+        let price = load_oracle_price(
+            &ctx.accounts.price_oracle,
+            Clock::get()?.unix_timestamp,
+        )?;
+        let collateral_value = collateral_value(bond, bond.collateral_deposited, &price)?;
+        
+        require!(
+            collateral_value < required_collateral,
+            BtrustError::NotLiquidatable
+        );
+        
+        // Cap the repayment by the close factor
+        let max_repay = outstanding_value
+            .checked_mul(LIQUIDATION_CLOSE_FACTOR_BPS)
+            .ok_or(BtrustError::MathOverflow)?
+            .checked_div(BPS_DENOMINATOR)
+            .ok_or(BtrustError::MathOverflow)?;
+        let mut repay_amount = repay_amount.min(max_repay);
+        require!(repay_amount > 0, BtrustError::InvalidAmount);
+        
+        // Collateral owed to the liquidator, at a discount of the penalty
+        let seize_value = apply_liquidation_penalty(repay_amount)?;
+        let mut collateral_seized = payment_to_collateral(bond, seize_value, &price)?;
+        
+        // Not enough collateral left: seize it all and shrink the repayment to match
+        if collateral_seized > bond.collateral_deposited {
+            collateral_seized = bond.collateral_deposited;
+            repay_amount = (collateral_value as u128)
+                .checked_mul(BPS_DENOMINATOR as u128)
+                .ok_or(BtrustError::MathOverflow)?
+                .checked_div((BPS_DENOMINATOR + LIQUIDATION_PENALTY_BPS) as u128)
+                .ok_or(BtrustError::MathOverflow)? as u64;
+            require!(repay_amount > 0, BtrustError::InvalidAmount);
+        }
+        
+        // Liquidator repays principal into the redemption vault
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.liquidator_payment.to_account_info(),
+                    to: ctx.accounts.redemption_vault.to_account_info(),
+                    authority: ctx.accounts.liquidator.to_account_info(),
+                },
+            ),
+            repay_amount,
+        )?;
+        
+        // Transfer discounted collateral to liquidator
+        let bond_mint = bond.bond_mint;
+        let seeds = &[
+            b"bond",
+            bond_mint.as_ref(),
+            &[bond.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.collateral_vault.to_account_info(),
+                    to: ctx.accounts.liquidator_collateral.to_account_info(),
+                    authority: bond.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            collateral_seized,
+        )?;
+        
+        bond.collateral_deposited -= collateral_seized;
+        bond.principal_repaid = bond.principal_repaid
+            .checked_add(repay_amount)
+            .ok_or(BtrustError::MathOverflow)?;
+        
+        // Collateral exhausted with debt outstanding: hand what is left to holders
+        if bond.collateral_deposited == 0 && bond.outstanding_debt()? > 0 {
+            bond.is_active = false;
+            bond.is_liquidated = true;
+        }
+        
+        emit!(BondLiquidated {
+            bond: bond.key(),
+            liquidator: ctx.accounts.liquidator.key(),
+            repay_amount,
+            collateral_seized,
+            remaining_collateral: bond.collateral_deposited,
+        });
+        
+        Ok(())
+    }
+
+    /// Create a sell order for secondary market
+    pub fn create_sell_order(
+        ctx: Context<CreateSellOrder>,
+        quantity: u64,
+        price_per_bond: u64,
+    ) -> Result<()> {
+        require!(quantity > 0, BtrustError::InvalidAmount);
+        require!(price_per_bond > 0, BtrustError::InvalidAmount);
+        
+        let order = &mut ctx.accounts.order;
+        let bond = &mut ctx.accounts.bond;
+        
+        require_not_paused(&ctx.accounts.platform, bond, PAUSE_TRADING)?;
+        require!(bond.is_active, BtrustError::BondNotActive);
+        require!(
+            !bond.has_subscription() || bond.is_settled,
+            BtrustError::SubscriptionNotSettled
+        );
+        
+        // Settle yield before bonds move into escrow; the seller keeps
+        // earning on escrowed bonds until the order fills
+        let position = &mut ctx.accounts.seller_position;
+        position.settle_yield(bond)?;
+        require!(quantity <= position.free_quantity(), BtrustError::InsufficientBalance);
+        position.escrowed_quantity = position.escrowed_quantity
+            .checked_add(quantity)
+            .ok_or(BtrustError::MathOverflow)?;
+        bond.escrowed_supply = bond.escrowed_supply
+            .checked_add(quantity)
+            .ok_or(BtrustError::MathOverflow)?;
+        
+        // Transfer bonds to escrow
+        thaw_holder_account(
+            &ctx.accounts.token_program,
+            bond,
+            &ctx.accounts.bond_mint,
+            &ctx.accounts.seller_bond_account,
+        )?;
+        
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.seller_bond_account.to_account_info(),
+                    to: ctx.accounts.order_escrow.to_account_info(),
+                    authority: ctx.accounts.seller.to_account_info(),
+                },
+            ),
+            quantity,
+        )?;
+        
+        freeze_holder_account(
+            &ctx.accounts.token_program,
+            bond,
+            &ctx.accounts.bond_mint,
+            &mut ctx.accounts.seller_bond_account,
+        )?;
+        
+        order.seller = ctx.accounts.seller.key();
+        order.bond = bond.key();
+        order.quantity = quantity;
+        order.price_per_bond = price_per_bond;
+        order.created_at = Clock::get()?.unix_timestamp;
+        order.is_active = true;
+        order.is_retirable = price_per_bond <= bond.face_value();
+        order.next_retirable = Pubkey::default();
+        order.bump = ctx.bumps.order;
+        
+        if order.is_retirable {
+            let next = match ctx.accounts.queue_prev.as_mut() {
+                Some(prev) => {
+                    require!(
+                        prev.bond == bond.key()
+                            && prev.is_retirable
+                            && prev.price_per_bond <= price_per_bond,
+                        BtrustError::InvalidRetireQueue
+                    );
+                    let next = prev.next_retirable;
+                    prev.next_retirable = order.key();
+                    next
+                }
+                None => {
+                    let next = bond.retire_queue_head;
+                    bond.retire_queue_head = order.key();
+                    next
+                }
+            };
+            if next != Pubkey::default() {
+                let next_order = ctx.accounts.queue_next
+                    .as_ref()
+                    .ok_or(BtrustError::InvalidRetireQueue)?;
+                require!(
+                    next_order.key() == next && next_order.price_per_bond > price_per_bond,
+                    BtrustError::InvalidRetireQueue
+                );
+            }
+            order.next_retirable = next;
+        }
+        
+        emit!(SellOrderCreated {
+            order: order.key(),
+            bond: bond.key(),
+            seller: order.seller,
+            quantity,
+            price_per_bond,
+        });
+        
+        Ok(())
+    }
+
+    /// Fill a sell order (buy from secondary market)
+    pub fn fill_order(ctx: Context<FillOrder>, quantity: u64) -> Result<()> {
+        require!(quantity > 0, BtrustError::InvalidAmount);
+        
+        let order = &mut ctx.accounts.order;
+        let platform = &ctx.accounts.platform;
+        
+        require_not_paused(platform, &ctx.accounts.bond, PAUSE_TRADING)?;
+        require!(ctx.accounts.bond.is_active, BtrustError::BondNotActive);
+        require!(!ctx.accounts.bond.in_recovery(), BtrustError::BondInRecovery);
+        require!(order.is_active, BtrustError::OrderNotActive);
+        require!(quantity <= order.quantity, BtrustError::ExceedsOrderQuantity);
+        
+        let payment_amount = order.price_per_bond
+            .checked_mul(quantity)
+            .ok_or(BtrustError::MathOverflow)?;
+        
+        // Calculate fee
+        let fee_amount = payment_amount
+            .checked_mul(platform.fee_bps)
+            .ok_or(BtrustError::MathOverflow)?
+            .checked_div(BPS_DENOMINATOR)
+            .ok_or(BtrustError::MathOverflow)?;
+        
+        let seller_amount = payment_amount
+            .checked_sub(fee_amount)
+            .ok_or(BtrustError::MathOverflow)?;
+        
+        // Transfer payment to seller
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.buyer_payment.to_account_info(),
+                    to: ctx.accounts.seller_payment.to_account_info(),
+                    authority: ctx.accounts.buyer.to_account_info(),
+                },
+            ),
+            seller_amount,
+        )?;
+        
+        // Transfer fee
+        if fee_amount > 0 {
+            token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.buyer_payment.to_account_info(),
+                        to: ctx.accounts.treasury.to_account_info(),
+                        authority: ctx.accounts.buyer.to_account_info(),
+                    },
+                ),
+                fee_amount,
+            )?;
+        }
+        
+        // Transfer bonds from escrow to buyer
+        let created_at = order.created_at.to_le_bytes();
+        let seeds = &[
+            b"order",
+            order.seller.as_ref(),
+            order.bond.as_ref(),
+            created_at.as_ref(),
+            &[order.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        thaw_holder_account(
+            &ctx.accounts.token_program,
+            &ctx.accounts.bond,
+            &ctx.accounts.bond_mint,
+            &ctx.accounts.buyer_bond_account,
+        )?;
+        
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.order_escrow.to_account_info(),
+                    to: ctx.accounts.buyer_bond_account.to_account_info(),
+                    authority: order.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            quantity,
+        )?;
+        
+        order.quantity -= quantity;
+        if order.quantity == 0 {
+            order.is_active = false;
+        }
+        
+        // Move yield entitlement from seller to buyer
+        let bond = &mut ctx.accounts.bond;
+        let seller_position = &mut ctx.accounts.seller_position;
+        seller_position.settle_yield(bond)?;
+        seller_position.quantity -= quantity;
+        seller_position.escrowed_quantity -= quantity;
+        bond.escrowed_supply -= quantity;
+        
+        let buyer_position = &mut ctx.accounts.buyer_position;
+        if buyer_position.holder == Pubkey::default() {
+            buyer_position.holder = ctx.accounts.buyer.key();
+            buyer_position.bond = bond.key();
+            buyer_position.purchase_price = order.price_per_bond;
+            buyer_position.purchase_timestamp = Clock::get()?.unix_timestamp;
+            buyer_position.total_yield_claimed = 0;
+            buyer_position.bump = ctx.bumps.buyer_position;
+        }
+        buyer_position.settle_yield(bond)?;
+        
+        let total_value = buyer_position.quantity
+            .checked_mul(buyer_position.purchase_price)
+            .ok_or(BtrustError::MathOverflow)?
+            .checked_add(payment_amount)
+            .ok_or(BtrustError::MathOverflow)?;
+        buyer_position.quantity += quantity;
+        buyer_position.purchase_price = total_value
+            .checked_div(buyer_position.quantity)
+            .ok_or(BtrustError::MathOverflow)?;
+        
+        freeze_holder_account(
+            &ctx.accounts.token_program,
+            bond,
+            &ctx.accounts.bond_mint,
+            &mut ctx.accounts.buyer_bond_account,
+        )?;
+        
+        emit!(OrderFilled {
+            order: order.key(),
+            buyer: ctx.accounts.buyer.key(),
+            quantity,
+            payment_amount,
+        });
+        
+        Ok(())
+    }
+
+    /// Cancel a sell order
+    pub fn cancel_order(ctx: Context<CancelOrder>) -> Result<()> {
+        let order = &mut ctx.accounts.order;
+        
+        require!(order.is_active, BtrustError::OrderNotActive);
+        require!(order.seller == ctx.accounts.seller.key(), BtrustError::Unauthorized);
+        
+        // Settle yield before escrowed bonds return to the wallet
+        let position = &mut ctx.accounts.seller_position;
+        position.settle_yield(&mut ctx.accounts.bond)?;
+        position.escrowed_quantity -= order.quantity;
+        ctx.accounts.bond.escrowed_supply -= order.quantity;
+        
+        // Return bonds from escrow
+        let created_at = order.created_at.to_le_bytes();
+        let seeds = &[
+            b"order",
+            order.seller.as_ref(),
+            order.bond.as_ref(),
+            created_at.as_ref(),
+            &[order.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        thaw_holder_account(
+            &ctx.accounts.token_program,
+            &ctx.accounts.bond,
+            &ctx.accounts.bond_mint,
+            &ctx.accounts.seller_bond_account,
+        )?;
+        
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.order_escrow.to_account_info(),
+                    to: ctx.accounts.seller_bond_account.to_account_info(),
+                    authority: order.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            order.quantity,
+        )?;
+        
+        freeze_holder_account(
+            &ctx.accounts.token_program,
+            &ctx.accounts.bond,
+            &ctx.accounts.bond_mint,
+            &mut ctx.accounts.seller_bond_account,
+        )?;
+        
+        order.is_active = false;
+        
+        emit!(OrderCancelled {
+            order: order.key(),
+        });
+        
+        Ok(())
+    }
+
+    /// Transfer bonds to another wallet
+    pub fn transfer_bond(ctx: Context<TransferBond>, quantity: u64) -> Result<()> {
+        require!(quantity > 0, BtrustError::InvalidAmount);
+        
+        let bond = &mut ctx.accounts.bond;
+        
+        require_not_paused(&ctx.accounts.platform, bond, PAUSE_TRADING)?;
+        require!(bond.is_active, BtrustError::BondNotActive);
+        require!(
+            !bond.has_subscription() || bond.is_settled,
+            BtrustError::SubscriptionNotSettled
+        );
+        
+        // Settle both sides before the balances change
+        let sender_position = &mut ctx.accounts.sender_position;
+        sender_position.settle_yield(bond)?;
+        require!(
+            quantity <= sender_position.free_quantity(),
+            BtrustError::InsufficientBalance
+        );
+        
+        let recipient_position = &mut ctx.accounts.recipient_position;
+        if recipient_position.holder == Pubkey::default() {
+            recipient_position.holder = ctx.accounts.recipient.key();
+            recipient_position.bond = bond.key();
+            recipient_position.purchase_price = sender_position.purchase_price;
+            recipient_position.purchase_timestamp = Clock::get()?.unix_timestamp;
+            recipient_position.total_yield_claimed = 0;
+            recipient_position.bump = ctx.bumps.recipient_position;
+        }
+        recipient_position.settle_yield(bond)?;
+        
+        for account in [&ctx.accounts.sender_bond_account, &ctx.accounts.recipient_bond_account] {
+            thaw_holder_account(&ctx.accounts.token_program, bond, &ctx.accounts.bond_mint, account)?;
+        }
+        
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.sender_bond_account.to_account_info(),
+                    to: ctx.accounts.recipient_bond_account.to_account_info(),
+                    authority: ctx.accounts.sender.to_account_info(),
+                },
+            ),
+            quantity,
+        )?;
+        
+        for account in [&mut ctx.accounts.sender_bond_account, &mut ctx.accounts.recipient_bond_account] {
+            freeze_holder_account(&ctx.accounts.token_program, bond, &ctx.accounts.bond_mint, account)?;
+        }
+        
+        // The recipient's cost basis carries over at the sender's average price
+        let total_value = recipient_position.quantity
+            .checked_mul(recipient_position.purchase_price)
+            .ok_or(BtrustError::MathOverflow)?
+            .checked_add(
+                quantity
+                    .checked_mul(sender_position.purchase_price)
+                    .ok_or(BtrustError::MathOverflow)?
+            )
+            .ok_or(BtrustError::MathOverflow)?;
+        sender_position.quantity -= quantity;
+        recipient_position.quantity += quantity;
+        recipient_position.purchase_price = total_value
+            .checked_div(recipient_position.quantity)
+            .ok_or(BtrustError::MathOverflow)?;
+        
+        emit!(BondTransferred {
+            bond: bond.key(),
+            from: ctx.accounts.sender.key(),
+            to: ctx.accounts.recipient.key(),
+            quantity,
+        });
+        
+        Ok(())
+    }
+
+    /// Accelerate a bond on a covenant breach, making all principal due now
+    pub fn trustee_accelerate(ctx: Context<TrusteeEnforce>) -> Result<()> {
+        let bond = &mut ctx.accounts.bond;
+        
+        require!(bond.is_active, BtrustError::BondNotActive);
+        require!(!bond.in_recovery(), BtrustError::BondInRecovery);
+        require!(!bond.is_called(), BtrustError::BondCalled);
+        require!(!bond.is_accelerated, BtrustError::BondAccelerated);
+        
+        let now = Clock::get()?.unix_timestamp;
+        require!(now < bond.maturity_timestamp, BtrustError::BondMatured);
+        require_covenant_breach(
+            bond,
+            &ctx.accounts.price_oracle,
+            ctx.accounts.coupon_period.as_deref(),
+            now,
+        )?;
+        
+        // An accelerated perpetual bond becomes a dated bond maturing now
+        bond.maturity_timestamp = now;
+        bond.is_perpetual = false;
+        if !bond.is_zero_coupon {
+            bond.coupon_periods = bond.scheduled_coupon_periods()?;
+        }
+        bond.is_accelerated = true;
+        
+        emit!(BondAccelerated {
+            bond: bond.key(),
+            trustee: ctx.accounts.trustee.key(),
+            accelerated_at: now,
+            outstanding_debt: bond.outstanding_debt()?,
+        });
+        
+        Ok(())
+    }
+
+    /// Freeze or unfreeze secondary trading of a bond
+    pub fn trustee_set_trading_frozen(ctx: Context<TrusteeEnforce>, frozen: bool) -> Result<()> {
+        let bond = &mut ctx.accounts.bond;
+        
+        if frozen && !bond.is_accelerated && !bond.in_recovery() {
+            require_covenant_breach(
+                bond,
+                &ctx.accounts.price_oracle,
+                ctx.accounts.coupon_period.as_deref(),
+                Clock::get()?.unix_timestamp,
+            )?;
+        }
+        bond.trading_frozen = frozen;
+        
+        emit!(TradingFrozen {
+            bond: bond.key(),
+            trustee: ctx.accounts.trustee.key(),
+            frozen,
+        });
+        
+        Ok(())
+    }
+
+    /// Declare a bond in default on a covenant breach
+    pub fn trustee_declare_default(ctx: Context<TrusteeEnforce>) -> Result<()> {
+        let bond = &mut ctx.accounts.bond;
+        
+        require!(bond.is_active, BtrustError::BondNotActive);
+        require!(!bond.in_recovery(), BtrustError::BondInRecovery);
+        
+        let now = Clock::get()?.unix_timestamp;
+        require_covenant_breach(
+            bond,
+            &ctx.accounts.price_oracle,
+            ctx.accounts.coupon_period.as_deref(),
+            now,
+        )?;
+        
+        bond.is_defaulted = true;
+        bond.is_active = false;
+        bond.defaulted_at = now;
+        
+        emit!(TrusteeDefaultDeclared {
+            bond: bond.key(),
+            trustee: ctx.accounts.trustee.key(),
+            collateral_deposited: bond.collateral_deposited,
+            outstanding_supply: bond.outstanding_supply,
+            defaulted_at: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Sweep the sinking fund and any unowed yield into the redemption vault for recovery
+    pub fn trustee_sweep(ctx: Context<TrusteeSweep>) -> Result<()> {
+        let bond = &mut ctx.accounts.bond;
+        
+        require!(bond.in_recovery(), BtrustError::BondNotInRecovery);
+        require!(!bond.recovery_claimed, BtrustError::RecoveryStarted);
+        require!(
+            ctx.accounts.sinking_fund.is_some() == bond.has_sinking_fund,
+            BtrustError::VaultMissing
+        );
+        
+        let sinking_fund_amount = ctx.accounts.sinking_fund
+            .as_ref()
+            .map_or(0, |sinking_fund| sinking_fund.amount);
+        // Yield already owed to holders stays behind for their yield claims
+        let yield_surplus = ctx.accounts.yield_vault.amount.saturating_sub(bond.yield_owed);
+        require!(
+            sinking_fund_amount > 0 || yield_surplus > 0,
+            BtrustError::NothingToSweep
+        );
+        
+        let bond_mint = bond.bond_mint;
+        let seeds = &[
+            b"bond",
+            bond_mint.as_ref(),
+            &[bond.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        let sinking_fund_sweep = ctx.accounts.sinking_fund
+            .as_ref()
+            .map(|sinking_fund| (sinking_fund, sinking_fund_amount));
+        for (from, amount) in [(&ctx.accounts.yield_vault, yield_surplus)]
+            .into_iter()
+            .chain(sinking_fund_sweep)
+        {
+            if amount == 0 {
+                continue;
+            }
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: from.to_account_info(),
+                        to: ctx.accounts.redemption_vault.to_account_info(),
+                        authority: bond.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                amount,
+            )?;
+        }
+        
+        // Recovery claims pay the redemption vault out pro rata as principal
+        bond.principal_repaid = bond.principal_repaid
+            .checked_add(sinking_fund_amount)
+            .and_then(|repaid| repaid.checked_add(yield_surplus))
+            .ok_or(BtrustError::MathOverflow)?;
+        
+        emit!(RecoverySwept {
+            bond: bond.key(),
+            trustee: ctx.accounts.trustee.key(),
+            sinking_fund_amount,
+            yield_surplus,
+        });
+        
+        Ok(())
+    }
+
+    /// Propose an amendment to a bond's terms for bondholder vote
+    pub fn propose_amendment(
+        ctx: Context<ProposeAmendment>,
+        amendment: Amendment,
+    ) -> Result<()> {
+        let bond = &mut ctx.accounts.bond;
+        
+        require!(bond.is_active, BtrustError::BondNotActive);
+        require!(!bond.in_recovery(), BtrustError::BondInRecovery);
+        require!(bond.outstanding_supply > 0, BtrustError::InvalidSupply);
+        validate_amendment(bond, &amendment)?;
+        
+        let now = Clock::get()?.unix_timestamp;
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.bond = bond.key();
+        proposal.index = bond.proposal_count;
+        proposal.amendment = amendment.clone();
+        proposal.created_at = now;
+        proposal.voting_end = now + VOTING_PERIOD_SECS;
+        proposal.supply_snapshot = bond.outstanding_supply;
+        proposal.votes_for = 0;
+        proposal.votes_against = 0;
+        proposal.is_finalized = false;
+        proposal.is_approved = false;
+        proposal.bump = ctx.bumps.proposal;
+        
+        bond.proposal_count += 1;
+        
+        emit!(AmendmentProposed {
+            bond: bond.key(),
+            proposal: proposal.index,
+            amendment,
+            supply_snapshot: proposal.supply_snapshot,
+            voting_end: proposal.voting_end,
+        });
+        
+        Ok(())
+    }
+
+    /// Vote on an amendment with the bonds held when it was proposed
+    pub fn cast_vote(ctx: Context<CastVote>, approve: bool) -> Result<()> {
+        let bond = &ctx.accounts.bond;
+        let proposal = &mut ctx.accounts.proposal;
+        let position = &ctx.accounts.holder_position;
+        
+        require!(
+            Clock::get()?.unix_timestamp < proposal.voting_end,
+            BtrustError::VotingClosed
+        );
+        
+        let weight = position.voting_power(proposal.index)?;
+        require!(weight > 0, BtrustError::NoVotingPower);
+        
+        if approve {
+            proposal.votes_for += weight;
+        } else {
+            proposal.votes_against += weight;
+        }
+        
+        let vote = &mut ctx.accounts.vote;
+        vote.proposal = proposal.key();
+        vote.voter = ctx.accounts.holder.key();
+        vote.weight = weight;
+        vote.approve = approve;
+        vote.bump = ctx.bumps.vote;
+        
+        emit!(VoteCast {
+            bond: bond.key(),
+            proposal: proposal.index,
+            voter: vote.voter,
+            weight,
+            approve,
+        });
+        
+        Ok(())
+    }
+
+    /// Close voting on an amendment and apply it if it passed
+    pub fn finalize_amendment(ctx: Context<FinalizeAmendment>) -> Result<()> {
+        let bond = &mut ctx.accounts.bond;
+        let proposal = &mut ctx.accounts.proposal;
+        
+        require!(!proposal.is_finalized, BtrustError::ProposalFinalized);
+        let now = Clock::get()?.unix_timestamp;
+        require!(now >= proposal.voting_end, BtrustError::VotingOpen);
+        
+        let votes_cast = proposal.votes_for + proposal.votes_against;
+        let quorum = (proposal.supply_snapshot as u128) * (VOTE_QUORUM_BPS as u128)
+            / (BPS_DENOMINATOR as u128);
+        let has_quorum = votes_cast as u128 >= quorum;
+        let has_supermajority = (proposal.votes_for as u128) * (BPS_DENOMINATOR as u128)
+            >= (votes_cast as u128) * (VOTE_SUPERMAJORITY_BPS as u128);
+        
+        // Terms may have moved on since the vote opened
+        let approved = has_quorum
+            && has_supermajority
+            && !bond.in_recovery()
+            && validate_amendment(bond, &proposal.amendment).is_ok();
+        
+        if approved {
+            match proposal.amendment {
+                Amendment::ExtendMaturity { maturity_timestamp } => {
+                    bond.maturity_timestamp = maturity_timestamp;
+                    if !bond.is_zero_coupon {
+                        bond.coupon_periods = bond.scheduled_coupon_periods()?;
+                    }
+                }
+                Amendment::ChangeCouponRate { coupon_rate_bps } => {
+                    // Every elapsed period must be recorded at its own rate first
+                    let current_period = bond.elapsed_coupon_periods(now);
+                    require!(
+                        bond.coupon_periods_recorded >= current_period,
+                        BtrustError::CouponRecordPending
+                    );
+                    bond.coupon_rate_bps = bond.period_rate_bps(current_period);
+                    bond.pending_coupon_rate_bps = coupon_rate_bps;
+                    bond.pending_rate_period = current_period + 1;
+                }
+                Amendment::WaiveCollateralRatio { collateral_ratio_bps } => {
+                    bond.collateral_ratio_bps = collateral_ratio_bps;
+                }
+            }
+        }
+        
+        proposal.is_finalized = true;
+        proposal.is_approved = approved;
+        
+        emit!(AmendmentFinalized {
+            bond: bond.key(),
+            proposal: proposal.index,
+            amendment: proposal.amendment.clone(),
+            votes_for: proposal.votes_for,
+            votes_against: proposal.votes_against,
+            supply_snapshot: proposal.supply_snapshot,
+            approved,
+        });
+        
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Accounts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct InitializePlatform<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Platform::INIT_SPACE,
+        seeds = [b"platform"],
+        bump,
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    /// CHECK: Treasury wallet that owns the per-mint fee accounts
+    pub treasury: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdatePlatform<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"platform"],
+        bump = platform.bump,
+        constraint = platform.authority == authority.key() @ BtrustError::Unauthorized,
+    )]
+    pub platform: Account<'info, Platform>,
+}
+
+#[derive(Accounts)]
+pub struct SetTreasury<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"platform"],
+        bump = platform.bump,
+        constraint = platform.authority == authority.key() @ BtrustError::Unauthorized,
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    /// CHECK: New treasury wallet that owns the per-mint fee accounts
+    pub new_treasury: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetPlatformPause<'info> {
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"platform"],
+        bump = platform.bump,
+        constraint = platform.is_pauser(&admin.key()) @ BtrustError::Unauthorized,
+    )]
+    pub platform: Account<'info, Platform>,
+}
+
+#[derive(Accounts)]
+pub struct SetBondPause<'info> {
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [b"platform"],
+        bump = platform.bump,
+        constraint = platform.is_pauser(&admin.key()) @ BtrustError::Unauthorized,
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(mut)]
+    pub bond: Account<'info, Bond>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    pub pending_authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"platform"],
+        bump = platform.bump,
+        constraint = platform.pending_authority == pending_authority.key() @ BtrustError::Unauthorized,
+    )]
+    pub platform: Account<'info, Platform>,
+}
+
+#[derive(Accounts)]
+pub struct InitMockPriceFeed<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"platform"],
+        bump = platform.bump,
+        constraint = platform.authority == authority.key() @ BtrustError::Unauthorized,
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + MockPriceFeed::INIT_SPACE,
+    )]
+    pub price_feed: Account<'info, MockPriceFeed>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetMockPrice<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = price_feed.authority == authority.key() @ BtrustError::Unauthorized,
+    )]
+    pub price_feed: Account<'info, MockPriceFeed>,
+}
+
+#[derive(Accounts)]
+pub struct InitReferenceRate<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"platform"],
+        bump = platform.bump,
+        constraint = platform.authority == authority.key() @ BtrustError::Unauthorized,
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ReferenceRate::INIT_SPACE,
+    )]
+    pub reference_rate: Account<'info, ReferenceRate>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PublishReferenceRate<'info> {
+    pub publisher: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = reference_rate.publisher == publisher.key() @ BtrustError::Unauthorized,
+    )]
+    pub reference_rate: Account<'info, ReferenceRate>,
+}
+
+#[derive(Accounts)]
+pub struct SyncBondOracle<'info> {
+    #[account(
+        mut,
+        seeds = [b"bond", bond.bond_mint.as_ref()],
+        bump = bond.bump,
+    )]
+    pub bond: Account<'info, Bond>,
+    
+    #[account(
+        seeds = [b"collateral_oracle", bond.collateral_mint.as_ref()],
+        bump = collateral_oracle.bump,
+    )]
+    pub collateral_oracle: Account<'info, CollateralOracle>,
+}
+
+#[derive(Accounts)]
+pub struct SetCollateralOracle<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"platform"],
+        bump = platform.bump,
+        constraint = platform.authority == authority.key() @ BtrustError::Unauthorized,
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    pub collateral_mint: Account<'info, Mint>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + CollateralOracle::INIT_SPACE,
+        seeds = [b"collateral_oracle", collateral_mint.key().as_ref()],
+        bump,
+    )]
+    pub collateral_oracle: Account<'info, CollateralOracle>,
+    
+    /// CHECK: Validated by `load_oracle_price`
+    pub price_oracle: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(args: CreateBondArgs)]
+pub struct CreateBond<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"platform"],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        init,
+        payer = issuer,
+        space = 8 + Bond::INIT_SPACE,
+        seeds = [b"bond", bond_mint.key().as_ref()],
+        bump,
+    )]
+    pub bond: Account<'info, Bond>,
+    
+    #[account(
+        init,
+        payer = issuer,
+        mint::decimals = 0,
+        mint::authority = bond,
+        mint::freeze_authority = bond,
+    )]
+    pub bond_mint: Account<'info, Mint>,
+    
+    pub collateral_mint: Account<'info, Mint>,
+    
+    #[account(constraint = payment_mint.key() == args.payment_mint @ BtrustError::InvalidPaymentMint)]
+    pub payment_mint: Account<'info, Mint>,
+    
+    // Required for secured bonds
+    #[account(
+        seeds = [b"collateral_oracle", collateral_mint.key().as_ref()],
+        bump = collateral_oracle.bump,
+    )]
+    pub collateral_oracle: Option<Account<'info, CollateralOracle>>,
+    
+    /// CHECK: Must match `collateral_oracle`; validated by `load_oracle_price`
+    pub price_oracle: UncheckedAccount<'info>,
+    
+    #[account(
+        init,
+        payer = issuer,
+        token::mint = collateral_mint,
+        token::authority = bond,
+    )]
+    pub collateral_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct DepositCollateral<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = bond.issuer == issuer.key() @ BtrustError::Unauthorized,
+    )]
+    pub bond: Account<'info, Bond>,
+    
+    #[account(
+        mut,
+        constraint = issuer_collateral.mint == bond.collateral_mint,
+    )]
+    pub issuer_collateral: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = collateral_vault.key() == bond.collateral_vault,
+    )]
+    pub collateral_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct InitBondVaults<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"bond", bond.bond_mint.as_ref()],
+        bump = bond.bump,
+        constraint = bond.issuer == issuer.key() @ BtrustError::Unauthorized,
+    )]
+    pub bond: Account<'info, Bond>,
+    
+    #[account(constraint = payment_mint.key() == bond.payment_mint @ BtrustError::InvalidPaymentMint)]
+    pub payment_mint: Account<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = issuer,
+        seeds = [b"redemption_vault", bond.key().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = bond,
+    )]
+    pub redemption_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        init,
+        payer = issuer,
+        seeds = [b"yield_vault", bond.key().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = bond,
+    )]
+    pub yield_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        init,
+        payer = issuer,
+        seeds = [b"subscription_vault", bond.key().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = bond,
+    )]
+    pub subscription_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct FundRedemption<'info> {
+    pub funder: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"bond", bond.bond_mint.as_ref()],
+        bump = bond.bump,
+    )]
+    pub bond: Account<'info, Bond>,
+    
+    #[account(
+        mut,
+        constraint = funder_payment.mint == bond.payment_mint @ BtrustError::InvalidPaymentMint,
+    )]
+    pub funder_payment: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"redemption_vault", bond.key().as_ref()],
+        bump,
+    )]
+    pub redemption_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CallBond<'info> {
+    pub issuer: Signer<'info>,
+    
+    #[account(
+        seeds = [b"platform"],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"bond", bond.bond_mint.as_ref()],
+        bump = bond.bump,
+        constraint = bond.issuer == issuer.key() @ BtrustError::Unauthorized,
+    )]
+    pub bond: Account<'info, Bond>,
+    
+    #[account(
+        mut,
+        constraint = issuer_payment.mint == bond.payment_mint @ BtrustError::InvalidPaymentMint,
+    )]
+    pub issuer_payment: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"redemption_vault", bond.key().as_ref()],
+        bump,
+    )]
+    pub redemption_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct FundConversion<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"bond", bond.bond_mint.as_ref()],
+        bump = bond.bump,
+        constraint = bond.issuer == issuer.key() @ BtrustError::Unauthorized,
+    )]
+    pub bond: Account<'info, Bond>,
+    
+    #[account(constraint = conversion_mint.key() == bond.conversion_mint @ BtrustError::InvalidConversionMint)]
+    pub conversion_mint: Account<'info, Mint>,
+    
+    #[account(
+        init_if_needed,
+        payer = issuer,
+        seeds = [b"conversion_vault", bond.key().as_ref()],
+        bump,
+        token::mint = conversion_mint,
+        token::authority = bond,
+    )]
+    pub conversion_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = issuer_conversion_account.mint == bond.conversion_mint @ BtrustError::InvalidConversionMint,
+    )]
+    pub issuer_conversion_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ConvertBond<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+    
+    #[account(
+        seeds = [b"platform"],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"bond", bond.bond_mint.as_ref()],
+        bump = bond.bump,
+    )]
+    pub bond: Account<'info, Bond>,
+    
+    #[account(
+        mut,
+        constraint = bond_mint.key() == bond.bond_mint,
+    )]
+    pub bond_mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [b"position", bond.key().as_ref(), holder.key().as_ref()],
+        bump = holder_position.bump,
+    )]
+    pub holder_position: Account<'info, HolderPosition>,
+    
+    #[account(
+        mut,
+        constraint = holder_bond_account.mint == bond.bond_mint,
+        constraint = holder_bond_account.owner == holder.key(),
+    )]
+    pub holder_bond_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"conversion_vault", bond.key().as_ref()],
+        bump,
+    )]
+    pub conversion_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = holder_conversion_account.mint == bond.conversion_mint @ BtrustError::InvalidConversionMint,
+    )]
+    pub holder_conversion_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimConversion<'info> {
+    pub issuer: Signer<'info>,
+    
+    #[account(
+        seeds = [b"platform"],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        seeds = [b"bond", bond.bond_mint.as_ref()],
+        bump = bond.bump,
+        constraint = bond.issuer == issuer.key() @ BtrustError::Unauthorized,
+    )]
+    pub bond: Account<'info, Bond>,
+    
+    #[account(
+        mut,
+        seeds = [b"conversion_vault", bond.key().as_ref()],
+        bump,
+    )]
+    pub conversion_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = issuer_conversion_account.mint == bond.conversion_mint @ BtrustError::InvalidConversionMint,
+    )]
+    pub issuer_conversion_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct FundSinkingFund<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"bond", bond.bond_mint.as_ref()],
+        bump = bond.bump,
+        constraint = bond.issuer == issuer.key() @ BtrustError::Unauthorized,
+    )]
+    pub bond: Account<'info, Bond>,
+    
+    #[account(constraint = payment_mint.key() == bond.payment_mint @ BtrustError::InvalidPaymentMint)]
+    pub payment_mint: Account<'info, Mint>,
+    
+    #[account(
+        init_if_needed,
+        payer = issuer,
+        seeds = [b"sinking_fund", bond.key().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = bond,
+    )]
+    pub sinking_fund: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = issuer_payment.mint == bond.payment_mint @ BtrustError::InvalidPaymentMint,
+    )]
+    pub issuer_payment: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct RetireFromSinkingFund<'info> {
+    pub issuer: Signer<'info>,
+    
+    #[account(
+        seeds = [b"platform"],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"bond", bond.bond_mint.as_ref()],
+        bump = bond.bump,
+        constraint = bond.issuer == issuer.key() @ BtrustError::Unauthorized,
+    )]
+    pub bond: Account<'info, Bond>,
+    
+    #[account(
+        mut,
+        constraint = bond_mint.key() == bond.bond_mint,
+    )]
+    pub bond_mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = order.bond == bond.key(),
+    )]
+    pub order: Account<'info, Order>,
+    
+    #[account(
+        mut,
+        constraint = order_escrow.mint == bond.bond_mint,
+        constraint = order_escrow.owner == order.key(),
+    )]
+    pub order_escrow: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"sinking_fund", bond.key().as_ref()],
+        bump,
+    )]
+    pub sinking_fund: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = seller_payment.mint == bond.payment_mint @ BtrustError::InvalidPaymentMint,
+        constraint = seller_payment.owner == order.seller @ BtrustError::Unauthorized,
+    )]
+    pub seller_payment: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"position", bond.key().as_ref(), order.seller.as_ref()],
+        bump = seller_position.bump,
+    )]
+    pub seller_position: Account<'info, HolderPosition>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct DrawSinkingFund<'info> {
+    pub issuer: Signer<'info>,
+    
+    #[account(
+        seeds = [b"platform"],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"bond", bond.bond_mint.as_ref()],
+        bump = bond.bump,
+        constraint = bond.issuer == issuer.key() @ BtrustError::Unauthorized,
+    )]
+    pub bond: Account<'info, Bond>,
+    
+    #[account(
+        seeds = [b"sinking_fund", bond.key().as_ref()],
+        bump,
+    )]
+    pub sinking_fund: Account<'info, TokenAccount>,
+}
+
+#[derive(Accounts)]
+pub struct RevealSinkingDraw<'info> {
+    #[account(
+        mut,
+        seeds = [b"bond", bond.bond_mint.as_ref()],
+        bump = bond.bump,
+    )]
+    pub bond: Account<'info, Bond>,
+    
+    /// CHECK: The SlotHashes sysvar, read by `find_slot_hash`
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SettleSinkingDraw<'info> {
+    #[account(
+        mut,
+        seeds = [b"bond", bond.bond_mint.as_ref()],
+        bump = bond.bump,
+    )]
+    pub bond: Account<'info, Bond>,
+    
+    #[account(
+        mut,
+        seeds = [b"position", bond.key().as_ref(), holder_position.holder.as_ref()],
+        bump = holder_position.bump,
+    )]
+    pub holder_position: Account<'info, HolderPosition>,
+}
+
+#[derive(Accounts)]
+pub struct PruneRetireQueue<'info> {
+    #[account(
+        mut,
+        seeds = [b"bond", bond.bond_mint.as_ref()],
+        bump = bond.bump,
+    )]
+    pub bond: Account<'info, Bond>,
+}
+
+#[derive(Accounts)]
+pub struct RedeemSinkingFundDraw<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+    
+    #[account(
+        seeds = [b"platform"],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"bond", bond.bond_mint.as_ref()],
+        bump = bond.bump,
+    )]
+    pub bond: Account<'info, Bond>,
+    
+    #[account(
+        mut,
+        constraint = bond_mint.key() == bond.bond_mint,
+    )]
+    pub bond_mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [b"position", bond.key().as_ref(), holder.key().as_ref()],
+        bump = holder_position.bump,
+    )]
+    pub holder_position: Account<'info, HolderPosition>,
+    
+    #[account(
+        mut,
+        constraint = holder_bond_account.mint == bond.bond_mint,
+        constraint = holder_bond_account.owner == holder.key(),
+    )]
+    pub holder_bond_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"sinking_fund", bond.key().as_ref()],
+        bump,
+    )]
+    pub sinking_fund: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = holder_payment.mint == bond.payment_mint @ BtrustError::InvalidPaymentMint,
+    )]
+    pub holder_payment: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawSinkingFund<'info> {
+    pub issuer: Signer<'info>,
+    
+    #[account(
+        seeds = [b"bond", bond.bond_mint.as_ref()],
+        bump = bond.bump,
+        constraint = bond.issuer == issuer.key() @ BtrustError::Unauthorized,
+    )]
+    pub bond: Account<'info, Bond>,
+    
+    #[account(
+        mut,
+        seeds = [b"sinking_fund", bond.key().as_ref()],
+        bump,
+    )]
+    pub sinking_fund: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = issuer_payment.mint == bond.payment_mint @ BtrustError::InvalidPaymentMint,
+    )]
+    pub issuer_payment: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CancelOffering<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = issuer,
+        seeds = [b"bond", bond.bond_mint.as_ref()],
+        bump = bond.bump,
+        constraint = bond.issuer == issuer.key() @ BtrustError::Unauthorized,
+    )]
+    pub bond: Account<'info, Bond>,
+    
+    #[account(
+        mut,
+        constraint = collateral_vault.key() == bond.collateral_vault,
+    )]
+    pub collateral_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = issuer_collateral.mint == bond.collateral_mint,
+    )]
+    pub issuer_collateral: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = issuer_payment.mint == bond.payment_mint @ BtrustError::InvalidPaymentMint,
+    )]
+    pub issuer_payment: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"redemption_vault", bond.key().as_ref()],
+        bump,
+    )]
+    pub redemption_vault: Option<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [b"yield_vault", bond.key().as_ref()],
+        bump,
+    )]
+    pub yield_vault: Option<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [b"subscription_vault", bond.key().as_ref()],
+        bump,
+    )]
+    pub subscription_vault: Option<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [b"sinking_fund", bond.key().as_ref()],
+        bump,
+    )]
+    pub sinking_fund: Option<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [b"conversion_vault", bond.key().as_ref()],
+        bump,
+    )]
+    pub conversion_vault: Option<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = issuer_conversion_account.mint == bond.conversion_mint @ BtrustError::InvalidConversionMint,
+    )]
+    pub issuer_conversion_account: Option<Account<'info, TokenAccount>>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawCollateral<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+    
+    #[account(
+        seeds = [b"platform"],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"bond", bond.bond_mint.as_ref()],
+        bump = bond.bump,
+        constraint = bond.issuer == issuer.key() @ BtrustError::Unauthorized,
+    )]
+    pub bond: Account<'info, Bond>,
+    
+    #[account(
+        mut,
+        constraint = issuer_collateral.mint == bond.collateral_mint,
+    )]
+    pub issuer_collateral: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = collateral_vault.key() == bond.collateral_vault,
+    )]
+    pub collateral_vault: Account<'info, TokenAccount>,
+    
+    /// CHECK: Must match the bond's oracle, validated by `load_oracle_price`
+    #[account(constraint = price_oracle.key() == bond.price_oracle @ BtrustError::InvalidOracle)]
+    pub price_oracle: UncheckedAccount<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct PurchaseBond<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    
+    #[account(
+        seeds = [b"platform"],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(mut)]
+    pub bond: Account<'info, Bond>,
+    
+    #[account(
+        mut,
+        constraint = bond_mint.key() == bond.bond_mint,
+    )]
+    pub bond_mint: Account<'info, Mint>,
+    
+    /// CHECK: Must match the bond's oracle, validated by `load_oracle_price`
+    #[account(constraint = price_oracle.key() == bond.price_oracle @ BtrustError::InvalidOracle)]
+    pub price_oracle: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        constraint = buyer_payment.mint == bond.payment_mint @ BtrustError::InvalidPaymentMint,
+    )]
+    pub buyer_payment: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = issuer_payment.mint == bond.payment_mint @ BtrustError::InvalidPaymentMint,
+        constraint = issuer_payment.owner == bond.issuer @ BtrustError::Unauthorized,
+    )]
+    pub issuer_payment: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = treasury.owner == platform.treasury @ BtrustError::InvalidTreasury,
+        constraint = treasury.mint == bond.payment_mint @ BtrustError::InvalidPaymentMint,
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"subscription_vault", bond.key().as_ref()],
+        bump,
+    )]
+    pub subscription_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = bond_mint,
+        associated_token::authority = buyer,
+    )]
+    pub buyer_bond_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + HolderPosition::INIT_SPACE,
+        seeds = [b"position", bond.key().as_ref(), buyer.key().as_ref()],
+        bump,
+    )]
+    pub holder_position: Account<'info, HolderPosition>,
+    
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SettleSubscription<'info> {
+    #[account(
+        seeds = [b"platform"],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"bond", bond.bond_mint.as_ref()],
+        bump = bond.bump,
+    )]
+    pub bond: Account<'info, Bond>,
+    
+    #[account(
+        mut,
+        seeds = [b"subscription_vault", bond.key().as_ref()],
+        bump,
+    )]
+    pub subscription_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = issuer_payment.owner == bond.issuer @ BtrustError::Unauthorized,
+        constraint = issuer_payment.mint == bond.payment_mint @ BtrustError::InvalidPaymentMint,
+    )]
+    pub issuer_payment: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = treasury.owner == platform.treasury @ BtrustError::InvalidTreasury,
+        constraint = treasury.mint == bond.payment_mint @ BtrustError::InvalidPaymentMint,
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct RefundSubscription<'info> {
+    pub holder: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"bond", bond.bond_mint.as_ref()],
+        bump = bond.bump,
+    )]
+    pub bond: Account<'info, Bond>,
+    
+    #[account(
+        mut,
+        constraint = bond_mint.key() == bond.bond_mint,
+    )]
+    pub bond_mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [b"position", bond.key().as_ref(), holder.key().as_ref()],
+        bump = holder_position.bump,
+    )]
+    pub holder_position: Account<'info, HolderPosition>,
+    
+    #[account(
+        mut,
+        constraint = holder_bond_account.mint == bond.bond_mint,
+        constraint = holder_bond_account.owner == holder.key(),
+    )]
+    pub holder_bond_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"subscription_vault", bond.key().as_ref()],
+        bump,
+    )]
+    pub subscription_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = holder_payment.mint == bond.payment_mint @ BtrustError::InvalidPaymentMint,
+    )]
+    pub holder_payment: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct PlaceBid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    
+    #[account(
+        seeds = [b"platform"],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"bond", bond.bond_mint.as_ref()],
+        bump = bond.bump,
+    )]
+    pub bond: Account<'info, Bond>,
+    
+    /// CHECK: Must match the bond's oracle, validated by `load_oracle_price`
+    #[account(constraint = price_oracle.key() == bond.price_oracle @ BtrustError::InvalidOracle)]
+    pub price_oracle: UncheckedAccount<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        space = 8 + Bid::INIT_SPACE,
+        seeds = [b"bid", bond.key().as_ref(), bidder.key().as_ref()],
+        bump,
+    )]
+    pub bid: Account<'info, Bid>,
+    
+    #[account(
+        mut,
+        constraint = bidder_payment.mint == bond.payment_mint @ BtrustError::InvalidPaymentMint,
+    )]
+    pub bidder_payment: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"subscription_vault", bond.key().as_ref()],
+        bump,
+    )]
+    pub subscription_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SettleAuction<'info> {
+    #[account(
+        seeds = [b"platform"],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"bond", bond.bond_mint.as_ref()],
+        bump = bond.bump,
+    )]
+    pub bond: Account<'info, Bond>,
+    
+    #[account(
+        mut,
+        seeds = [b"subscription_vault", bond.key().as_ref()],
+        bump,
+    )]
+    pub subscription_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = issuer_payment.mint == bond.payment_mint @ BtrustError::InvalidPaymentMint,
+        constraint = issuer_payment.owner == bond.issuer @ BtrustError::Unauthorized,
+    )]
+    pub issuer_payment: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = treasury.owner == platform.treasury @ BtrustError::InvalidTreasury,
+        constraint = treasury.mint == bond.payment_mint @ BtrustError::InvalidPaymentMint,
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+    
+    /// CHECK: Must match the bond's oracle, validated by `load_oracle_price`
+    #[account(constraint = price_oracle.key() == bond.price_oracle @ BtrustError::InvalidOracle)]
+    pub price_oracle: UncheckedAccount<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimAuctionBid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"bond", bond.bond_mint.as_ref()],
+        bump = bond.bump,
+    )]
+    pub bond: Account<'info, Bond>,
+    
+    #[account(
+        mut,
+        constraint = bond_mint.key() == bond.bond_mint,
+    )]
+    pub bond_mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        close = bidder,
+        seeds = [b"bid", bond.key().as_ref(), bidder.key().as_ref()],
+        bump = bid.bump,
+    )]
+    pub bid: Account<'info, Bid>,
+    
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        associated_token::mint = bond_mint,
+        associated_token::authority = bidder,
+    )]
+    pub bidder_bond_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        space = 8 + HolderPosition::INIT_SPACE,
+        seeds = [b"position", bond.key().as_ref(), bidder.key().as_ref()],
+        bump,
+    )]
+    pub holder_position: Account<'info, HolderPosition>,
+    
+    #[account(
+        mut,
+        seeds = [b"subscription_vault", bond.key().as_ref()],
+        bump,
+    )]
+    pub subscription_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = bidder_payment.mint == bond.payment_mint @ BtrustError::InvalidPaymentMint,
+    )]
+    pub bidder_payment: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RefundBid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"bond", bond.bond_mint.as_ref()],
+        bump = bond.bump,
+    )]
+    pub bond: Account<'info, Bond>,
+    
+    #[account(
+        mut,
+        close = bidder,
+        seeds = [b"bid", bond.key().as_ref(), bidder.key().as_ref()],
+        bump = bid.bump,
+    )]
+    pub bid: Account<'info, Bid>,
+    
+    #[account(
+        mut,
+        seeds = [b"subscription_vault", bond.key().as_ref()],
+        bump,
+    )]
+    pub subscription_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = bidder_payment.mint == bond.payment_mint @ BtrustError::InvalidPaymentMint,
+    )]
+    pub bidder_payment: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(period: u32)]
+pub struct DepositYield<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = bond.issuer == issuer.key() @ BtrustError::Unauthorized,
+    )]
+    pub bond: Account<'info, Bond>,
+    
+    #[account(
+        init_if_needed,
+        payer = issuer,
+        space = 8 + CouponPeriod::INIT_SPACE,
+        seeds = [b"coupon_period", bond.key().as_ref(), &period.to_le_bytes()],
+        bump,
+    )]
+    pub coupon_period: Account<'info, CouponPeriod>,
+    
+    #[account(
+        mut,
+        constraint = issuer_payment.mint == bond.payment_mint @ BtrustError::InvalidPaymentMint,
+    )]
+    pub issuer_payment: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"yield_vault", bond.key().as_ref()],
+        bump,
+    )]
+    pub yield_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(period: u32)]
+pub struct FixCouponRate<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"bond", bond.bond_mint.as_ref()],
+        bump = bond.bump,
+    )]
+    pub bond: Account<'info, Bond>,
+    
+    #[account(constraint = reference_rate.key() == bond.reference_rate @ BtrustError::InvalidReferenceRate)]
+    pub reference_rate: Account<'info, ReferenceRate>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + CouponPeriod::INIT_SPACE,
+        seeds = [b"coupon_period", bond.key().as_ref(), &period.to_le_bytes()],
+        bump,
+    )]
+    pub coupon_period: Account<'info, CouponPeriod>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(period: u32)]
+pub struct RecordCoupon<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        seeds = [b"platform"],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"bond", bond.bond_mint.as_ref()],
+        bump = bond.bump,
+    )]
+    pub bond: Account<'info, Bond>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + CouponPeriod::INIT_SPACE,
+        seeds = [b"coupon_period", bond.key().as_ref(), &period.to_le_bytes()],
+        bump,
+    )]
+    pub coupon_period: Account<'info, CouponPeriod>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimYield<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+    
+    #[account(
+        seeds = [b"platform"],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"bond", bond.bond_mint.as_ref()],
+        bump = bond.bump,
+    )]
+    pub bond: Account<'info, Bond>,
+    
+    #[account(
+        mut,
+        seeds = [b"position", bond.key().as_ref(), holder.key().as_ref()],
+        bump = holder_position.bump,
+    )]
+    pub holder_position: Account<'info, HolderPosition>,
+    
+    #[account(
+        mut,
+        seeds = [b"yield_vault", bond.key().as_ref()],
+        bump,
+    )]
+    pub yield_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = holder_payment.mint == bond.payment_mint @ BtrustError::InvalidPaymentMint,
+    )]
+    pub holder_payment: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct MarkDefault<'info> {
+    #[account(
+        mut,
+        seeds = [b"bond", bond.bond_mint.as_ref()],
+        bump = bond.bump,
+    )]
+    pub bond: Account<'info, Bond>,
+    
+    /// The earliest coupon period that is not fully funded
+    #[account(
+        seeds = [b"coupon_period", bond.key().as_ref(), &bond.coupon_periods_funded.to_le_bytes()],
+        bump = coupon_period.bump,
+    )]
+    pub coupon_period: Account<'info, CouponPeriod>,
+}
+
+#[derive(Accounts)]
+pub struct MarkSinkingFundDefault<'info> {
+    #[account(
+        mut,
+        seeds = [b"bond", bond.bond_mint.as_ref()],
+        bump = bond.bump,
+    )]
+    pub bond: Account<'info, Bond>,
+}
+
+#[derive(Accounts)]
+pub struct MarkPrincipalDefault<'info> {
+    #[account(
+        mut,
+        seeds = [b"bond", bond.bond_mint.as_ref()],
+        bump = bond.bump,
+    )]
+    pub bond: Account<'info, Bond>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRecovery<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+    
+    #[account(
+        seeds = [b"platform"],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"bond", bond.bond_mint.as_ref()],
+        bump = bond.bump,
+    )]
+    pub bond: Account<'info, Bond>,
+    
+    #[account(
+        mut,
+        constraint = bond_mint.key() == bond.bond_mint,
+    )]
+    pub bond_mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [b"position", bond.key().as_ref(), holder.key().as_ref()],
+        bump = holder_position.bump,
+    )]
+    pub holder_position: Account<'info, HolderPosition>,
+    
+    #[account(
+        mut,
+        constraint = holder_bond_account.mint == bond.bond_mint,
+        constraint = holder_bond_account.owner == holder.key(),
+    )]
+    pub holder_bond_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = collateral_vault.key() == bond.collateral_vault,
+    )]
+    pub collateral_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"redemption_vault", bond.key().as_ref()],
+        bump,
+    )]
+    pub redemption_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"yield_vault", bond.key().as_ref()],
+        bump,
+    )]
+    pub yield_vault: Account<'info, TokenAccount>,
+    
+    /// Required once the bond has a sinking fund
+    #[account(
+        mut,
+        seeds = [b"sinking_fund", bond.key().as_ref()],
+        bump,
+    )]
+    pub sinking_fund: Option<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = holder_collateral.mint == bond.collateral_mint,
+    )]
+    pub holder_collateral: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = holder_payment.mint == bond.payment_mint @ BtrustError::InvalidPaymentMint,
+    )]
+    pub holder_payment: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct RedeemBond<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+    
+    #[account(
+        seeds = [b"platform"],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"bond", bond.bond_mint.as_ref()],
+        bump = bond.bump,
+    )]
+    pub bond: Account<'info, Bond>,
+    
+    #[account(
+        mut,
+        constraint = bond_mint.key() == bond.bond_mint,
+    )]
+    pub bond_mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [b"position", bond.key().as_ref(), holder.key().as_ref()],
+        bump = holder_position.bump,
+    )]
+    pub holder_position: Account<'info, HolderPosition>,
+    
+    #[account(
+        mut,
+        constraint = holder_bond_account.mint == bond.bond_mint,
+        constraint = holder_bond_account.owner == holder.key(),
+    )]
+    pub holder_bond_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"redemption_vault", bond.key().as_ref()],
+        bump,
+    )]
+    pub redemption_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = holder_payment.mint == bond.payment_mint @ BtrustError::InvalidPaymentMint,
+    )]
+    pub holder_payment: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ExercisePut<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+    
+    #[account(
+        seeds = [b"platform"],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"bond", bond.bond_mint.as_ref()],
+        bump = bond.bump,
+    )]
+    pub bond: Account<'info, Bond>,
+    
+    #[account(constraint = bond_mint.key() == bond.bond_mint)]
+    pub bond_mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [b"position", bond.key().as_ref(), holder.key().as_ref()],
+        bump = holder_position.bump,
+    )]
+    pub holder_position: Account<'info, HolderPosition>,
+    
+    #[account(
+        mut,
+        constraint = holder_bond_account.mint == bond.bond_mint,
+        constraint = holder_bond_account.owner == holder.key(),
+    )]
+    pub holder_bond_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = holder,
+        seeds = [b"put_escrow", bond.key().as_ref()],
+        bump,
+        token::mint = bond_mint,
+        token::authority = bond,
+    )]
+    pub put_escrow: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimPut<'info> {
+    pub holder: Signer<'info>,
+    
+    #[account(
+        seeds = [b"platform"],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"bond", bond.bond_mint.as_ref()],
+        bump = bond.bump,
+    )]
+    pub bond: Account<'info, Bond>,
+    
+    #[account(
+        mut,
+        constraint = bond_mint.key() == bond.bond_mint,
+    )]
+    pub bond_mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [b"position", bond.key().as_ref(), holder.key().as_ref()],
+        bump = holder_position.bump,
+    )]
+    pub holder_position: Account<'info, HolderPosition>,
+    
+    #[account(
+        mut,
+        constraint = holder_bond_account.mint == bond.bond_mint,
+        constraint = holder_bond_account.owner == holder.key(),
+    )]
+    pub holder_bond_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"put_escrow", bond.key().as_ref()],
+        bump,
+    )]
+    pub put_escrow: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"redemption_vault", bond.key().as_ref()],
+        bump,
+    )]
+    pub redemption_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = holder_payment.mint == bond.payment_mint @ BtrustError::InvalidPaymentMint,
+    )]
+    pub holder_payment: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct Liquidate<'info> {
+    #[account(mut)]
+    pub liquidator: Signer<'info>,
+    
+    #[account(
+        seeds = [b"platform"],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"bond", bond.bond_mint.as_ref()],
+        bump = bond.bump,
+    )]
+    pub bond: Account<'info, Bond>,
+    
+    #[account(
+        mut,
+        constraint = collateral_vault.key() == bond.collateral_vault,
+    )]
+    pub collateral_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = liquidator_collateral.mint == bond.collateral_mint,
+    )]
+    pub liquidator_collateral: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = liquidator_payment.mint == bond.payment_mint @ BtrustError::InvalidPaymentMint,
+    )]
+    pub liquidator_payment: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"redemption_vault", bond.key().as_ref()],
+        bump,
+    )]
+    pub redemption_vault: Account<'info, TokenAccount>,
+    
+    /// CHECK: Must match the bond's oracle, validated by `load_oracle_price`
+    #[account(constraint = price_oracle.key() == bond.price_oracle @ BtrustError::InvalidOracle)]
+    pub price_oracle: UncheckedAccount<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CreateSellOrder<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+    
+    #[account(
+        seeds = [b"platform"],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(mut)]
+    pub bond: Account<'info, Bond>,
+    
+    #[account(
+        init,
+        payer = seller,
+        space = 8 + Order::INIT_SPACE,
+        seeds = [b"order", seller.key().as_ref(), bond.key().as_ref(), &Clock::get().unwrap().unix_timestamp.to_le_bytes()],
+        bump,
+    )]
+    pub order: Account<'info, Order>,
+    
+    #[account(
+        mut,
+        constraint = seller_bond_account.mint == bond.bond_mint,
+        constraint = seller_bond_account.owner == seller.key(),
+    )]
+    pub seller_bond_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"position", bond.key().as_ref(), seller.key().as_ref()],
+        bump = seller_position.bump,
+    )]
+    pub seller_position: Account<'info, HolderPosition>,
+    
+    #[account(constraint = bond_mint.key() == bond.bond_mint)]
+    pub bond_mint: Account<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = seller,
+        token::mint = bond_mint,
+        token::authority = order,
+    )]
+    pub order_escrow: Account<'info, TokenAccount>,
+    
+    /// Order the new one follows in the retire queue, unless it goes first
+    #[account(mut)]
+    pub queue_prev: Option<Account<'info, Order>>,
+    
+    /// Order the new one goes in front of in the retire queue, if any
+    pub queue_next: Option<Account<'info, Order>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct FillOrder<'info> {
+    #[account(
+        mut,
+        constraint = buyer.key() != order.seller @ BtrustError::InvalidRecipient,
+    )]
+    pub buyer: Signer<'info>,
+    
+    #[account(
+        seeds = [b"platform"],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(mut)]
+    pub order: Account<'info, Order>,
+    
+    #[account(
+        mut,
+        constraint = bond.key() == order.bond,
+    )]
+    pub bond: Account<'info, Bond>,
+    
+    #[account(
+        mut,
+        constraint = buyer_payment.mint == bond.payment_mint @ BtrustError::InvalidPaymentMint,
+    )]
+    pub buyer_payment: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = seller_payment.mint == bond.payment_mint @ BtrustError::InvalidPaymentMint,
+        constraint = seller_payment.owner == order.seller @ BtrustError::Unauthorized,
+    )]
+    pub seller_payment: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = treasury.owner == platform.treasury @ BtrustError::InvalidTreasury,
+        constraint = treasury.mint == bond.payment_mint @ BtrustError::InvalidPaymentMint,
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = order_escrow.mint == bond.bond_mint,
+        constraint = order_escrow.owner == order.key(),
+    )]
+    pub order_escrow: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = buyer_bond_account.mint == bond.bond_mint,
+        constraint = buyer_bond_account.owner == buyer.key(),
+    )]
+    pub buyer_bond_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + HolderPosition::INIT_SPACE,
+        seeds = [b"position", bond.key().as_ref(), buyer.key().as_ref()],
+        bump,
+    )]
+    pub buyer_position: Account<'info, HolderPosition>,
+    
+    #[account(
+        mut,
+        seeds = [b"position", bond.key().as_ref(), order.seller.as_ref()],
+        bump = seller_position.bump,
+    )]
+    pub seller_position: Account<'info, HolderPosition>,
+    
+    #[account(constraint = bond_mint.key() == bond.bond_mint)]
+    pub bond_mint: Account<'info, Mint>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelOrder<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+    
+    #[account(mut)]
+    pub order: Account<'info, Order>,
+    
+    #[account(
+        mut,
+        constraint = bond.key() == order.bond,
+    )]
+    pub bond: Account<'info, Bond>,
+    
+    #[account(
+        mut,
+        constraint = order_escrow.mint == bond.bond_mint,
+        constraint = order_escrow.owner == order.key(),
+    )]
+    pub order_escrow: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = seller_bond_account.mint == bond.bond_mint,
+        constraint = seller_bond_account.owner == seller.key(),
+    )]
+    pub seller_bond_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"position", bond.key().as_ref(), seller.key().as_ref()],
+        bump = seller_position.bump,
+    )]
+    pub seller_position: Account<'info, HolderPosition>,
+    
+    #[account(constraint = bond_mint.key() == bond.bond_mint)]
+    pub bond_mint: Account<'info, Mint>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct TransferBond<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+    
+    /// CHECK: Any wallet can receive bonds
+    #[account(constraint = recipient.key() != sender.key() @ BtrustError::InvalidRecipient)]
+    pub recipient: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [b"platform"],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"bond", bond.bond_mint.as_ref()],
+        bump = bond.bump,
     )]
-    pub platform: Account<'info, Platform>,
+    pub bond: Account<'info, Bond>,
+    
+    #[account(constraint = bond_mint.key() == bond.bond_mint)]
+    pub bond_mint: Account<'info, Mint>,
     
     #[account(
-        init,
-        payer = issuer,
-        space = 8 + Bond::INIT_SPACE,
-        seeds = [b"bond", bond_mint.key().as_ref()],
+        mut,
+        constraint = sender_bond_account.mint == bond.bond_mint,
+        constraint = sender_bond_account.owner == sender.key(),
+    )]
+    pub sender_bond_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"position", bond.key().as_ref(), sender.key().as_ref()],
+        bump = sender_position.bump,
+    )]
+    pub sender_position: Account<'info, HolderPosition>,
+    
+    #[account(
+        init_if_needed,
+        payer = sender,
+        associated_token::mint = bond_mint,
+        associated_token::authority = recipient,
+    )]
+    pub recipient_bond_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = sender,
+        space = 8 + HolderPosition::INIT_SPACE,
+        seeds = [b"position", bond.key().as_ref(), recipient.key().as_ref()],
         bump,
     )]
+    pub recipient_position: Account<'info, HolderPosition>,
+    
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct TrusteeEnforce<'info> {
+    pub trustee: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"bond", bond.bond_mint.as_ref()],
+        bump = bond.bump,
+        constraint = bond.trustee == trustee.key() @ BtrustError::Unauthorized,
+    )]
     pub bond: Account<'info, Bond>,
     
+    /// CHECK: Validated by `load_oracle_price`; only read for secured bonds
+    #[account(constraint = price_oracle.key() == bond.price_oracle @ BtrustError::InvalidOracle)]
+    pub price_oracle: UncheckedAccount<'info>,
+    
+    /// The earliest coupon period that is not fully funded, when a coupon was missed
     #[account(
-        init,
-        payer = issuer,
-        mint::decimals = 0,
-        mint::authority = bond,
+        seeds = [b"coupon_period", bond.key().as_ref(), &bond.coupon_periods_funded.to_le_bytes()],
+        bump = coupon_period.bump,
     )]
-    pub bond_mint: Account<'info, Mint>,
+    pub coupon_period: Option<Account<'info, CouponPeriod>>,
+}
+
+#[derive(Accounts)]
+pub struct TrusteeSweep<'info> {
+    pub trustee: Signer<'info>,
     
-    pub collateral_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"bond", bond.bond_mint.as_ref()],
+        bump = bond.bump,
+        constraint = bond.trustee == trustee.key() @ BtrustError::Unauthorized,
+    )]
+    pub bond: Account<'info, Bond>,
     
     #[account(
-        init,
-        payer = issuer,
-        token::mint = collateral_mint,
-        token::authority = bond,
+        mut,
+        seeds = [b"redemption_vault", bond.key().as_ref()],
+        bump,
     )]
-    pub collateral_vault: Account<'info, TokenAccount>,
+    pub redemption_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"yield_vault", bond.key().as_ref()],
+        bump,
+    )]
+    pub yield_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"sinking_fund", bond.key().as_ref()],
+        bump,
+    )]
+    pub sinking_fund: Option<Account<'info, TokenAccount>>,
     
     pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
 }
 
 #[derive(Accounts)]
-pub struct DepositCollateral<'info> {
+pub struct ProposeAmendment<'info> {
     #[account(mut)]
     pub issuer: Signer<'info>,
     
     #[account(
         mut,
+        seeds = [b"bond", bond.bond_mint.as_ref()],
+        bump = bond.bump,
         constraint = bond.issuer == issuer.key() @ BtrustError::Unauthorized,
     )]
     pub bond: Account<'info, Bond>,
     
+    #[account(
+        init,
+        payer = issuer,
+        space = 8 + Proposal::INIT_SPACE,
+        seeds = [b"proposal", bond.key().as_ref(), &bond.proposal_count.to_le_bytes()],
+        bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CastVote<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+    
+    #[account(
+        seeds = [b"bond", bond.bond_mint.as_ref()],
+        bump = bond.bump,
+    )]
+    pub bond: Account<'info, Bond>,
+    
     #[account(
         mut,
-        constraint = issuer_collateral.mint == bond.collateral_mint,
+        seeds = [b"proposal", bond.key().as_ref(), &proposal.index.to_le_bytes()],
+        bump = proposal.bump,
     )]
-    pub issuer_collateral: Account<'info, TokenAccount>,
+    pub proposal: Account<'info, Proposal>,
+    
+    #[account(
+        init,
+        payer = holder,
+        space = 8 + VoteRecord::INIT_SPACE,
+        seeds = [b"vote", proposal.key().as_ref(), holder.key().as_ref()],
+        bump,
+    )]
+    pub vote: Account<'info, VoteRecord>,
+    
+    #[account(
+        seeds = [b"position", bond.key().as_ref(), holder.key().as_ref()],
+        bump = holder_position.bump,
+    )]
+    pub holder_position: Account<'info, HolderPosition>,
     
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeAmendment<'info> {
     #[account(
         mut,
-        constraint = collateral_vault.key() == bond.collateral_vault,
+        seeds = [b"bond", bond.bond_mint.as_ref()],
+        bump = bond.bump,
     )]
-    pub collateral_vault: Account<'info, TokenAccount>,
+    pub bond: Account<'info, Bond>,
+    
+    #[account(
+        mut,
+        seeds = [b"proposal", bond.key().as_ref(), &proposal.index.to_le_bytes()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+// ============================================================================
+// State
+// ============================================================================
+
+#[account]
+#[derive(InitSpace)]
+pub struct Platform {
+    pub authority: Pubkey,
+    pub treasury: Pubkey,
+    pub total_bonds_issued: u64,
+    pub total_volume: u64,
+    pub fee_bps: u64,
+    pub pending_authority: Pubkey,
+    pub guardian: Pubkey,
+    pub is_paused: bool,
+    pub bump: u8,
+}
+
+impl Platform {
+    /// Whether `key` may toggle platform or bond pause switches
+    pub fn is_pauser(&self, key: &Pubkey) -> bool {
+        *key == self.authority || (self.guardian != Pubkey::default() && *key == self.guardian)
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Bond {
+    pub issuer: Pubkey,
+    pub bond_mint: Pubkey,
+    pub collateral_mint: Pubkey,
+    pub collateral_vault: Pubkey,
+    pub payment_mint: Pubkey,
+    pub price_oracle: Pubkey,
+    pub collateral_decimals: u8,
+    pub payment_decimals: u8,
+    #[max_len(64)]
+    pub name: String,
+    #[max_len(10)]
+    pub symbol: String,
+    #[max_len(500)]
+    pub description: String,
+    #[max_len(200)]
+    pub image_uri: String,
+    #[max_len(100)]
+    pub website: String,
+    #[max_len(100)]
+    pub twitter: String,
+    #[max_len(100)]
+    pub discord: String,
+    pub principal_amount: u64,
+    pub coupon_rate_bps: u64,
+    pub amortization_bps: u64,
+    pub amortized_per_bond: u64,
+    pub funded_amortized_per_bond: u64,
+    pub is_zero_coupon: bool,
+    pub discount_yield_bps: u64,
+    pub is_variable_rate: bool,
+    pub reference_rate: Pubkey,
+    pub rate_spread_bps: i64,
+    pub rate_floor_bps: u64,
+    pub rate_cap_bps: u64,
+    pub last_fixed_rate_bps: u64,
+    pub payment_frequency: u8, // 1=annual, 2=semi, 4=quarterly, 12=monthly
+    pub maturity_timestamp: i64,
+    pub is_perpetual: bool,
+    pub created_at: i64,
+    pub total_supply: u64,
+    pub outstanding_supply: u64,
+    pub total_issued: u64,
+    pub is_capped: bool,
+    pub collateral_ratio_bps: u64,
+    pub is_unsecured: bool,
+    pub collateral_deposited: u64,
+    pub total_yield_paid: u64,
+    pub yield_owed: u64,
+    pub principal_repaid: u64,
+    pub yield_index: u128,
+    pub funded_yield_index: u128,
+    pub coupon_periods: u32,
+    pub coupon_periods_recorded: u32,
+    pub coupon_periods_funded: u32,
+    pub grace_period: i64,
+    pub subscription_start: i64,
+    pub subscription_end: i64,
+    pub soft_cap: u64,
+    pub subscription_raised: u64,
+    pub auction_start: i64,
+    pub auction_end: i64,
+    pub auction_start_price: u64,
+    pub auction_floor_price: u64,
+    pub auction_bid_quantity: u64,
+    pub auction_unclaimed: u64,
+    pub auction_clearing_price: u64,
+    pub auction_settled_index: u128,
+    pub auction_settled_amortized: u64,
+    pub is_settled: bool,
+    pub first_call_date: i64,
+    pub call_schedule_bps: [u64; MAX_CALL_SCHEDULE_STEPS],
+    pub call_notice_period: i64,
+    pub call_date: i64,
+    pub call_price: u64,
+    pub put_dates: [i64; MAX_PUT_WINDOWS],
+    pub put_window_length: i64,
+    pub put_price_bps: u64,
+    pub put_tendered: u64,
+    pub put_window_end: i64,
+    pub put_due: u64,
+    pub sinking_fund_per_period: u64,
+    pub sinking_fund_deposited: u64,
+    pub sinking_drawn_pending: u64,
+    pub sinking_draw_remaining: u64,
+    pub sinking_draw_eligible: u64,
+    pub sinking_draw_seed: [u8; 32],
+    pub sinking_draw_slot: u64,
+    pub sinking_draw_revealed_at: i64,
+    pub sinking_fund_draws: u32,
+    pub sinking_fund_retired: u64,
+    pub escrowed_supply: u64,
+    pub retire_queue_head: Pubkey,
+    pub proposal_count: u32,
+    pub pending_coupon_rate_bps: u64,
+    pub pending_rate_period: u32,
+    pub trustee: Pubkey,
+    pub is_accelerated: bool,
+    pub trading_frozen: bool,
+    pub recovery_claimed: bool,
+    pub conversion_mint: Pubkey,
+    pub conversion_ratio: u64,
+    pub conversion_start: i64,
+    pub conversion_end: i64,
+    pub has_vaults: bool,
+    pub has_sinking_fund: bool,
+    pub has_conversion_vault: bool,
+    pub last_yield_payment: i64,
+    pub is_active: bool,
+    pub is_matured: bool,
+    pub is_defaulted: bool,
+    pub defaulted_at: i64,
+    pub is_liquidated: bool,
+    pub pause_flags: u8,
+    pub bump: u8,
+}
+
+impl Bond {
+    /// Length of one coupon period in seconds
+    pub fn coupon_interval(&self) -> i64 {
+        SECONDS_PER_YEAR / self.payment_frequency as i64
+    }
+    
+    /// Number of coupon periods between creation and maturity; the last one may be short
+    pub fn scheduled_coupon_periods(&self) -> Result<u32> {
+        if self.maturity_timestamp == PERPETUAL_MATURITY {
+            return Ok(u32::MAX);
+        }
+        let term = self.maturity_timestamp
+            .checked_sub(self.created_at)
+            .ok_or(BtrustError::MathOverflow)?;
+        let interval = self.coupon_interval();
+        u32::try_from((term + interval - 1) / interval).map_err(|_| error!(BtrustError::MathOverflow))
+    }
+    
+    /// Start of coupon period `period`
+    pub fn coupon_start_date(&self, period: u32) -> i64 {
+        self.created_at + period as i64 * self.coupon_interval()
+    }
+    
+    /// Record (and payment) date of coupon period `period`
+    pub fn coupon_record_date(&self, period: u32) -> i64 {
+        (self.coupon_start_date(period) + self.coupon_interval()).min(self.maturity_timestamp)
+    }
+    
+    /// Number of coupon periods whose record date has passed
+    pub fn elapsed_coupon_periods(&self, now: i64) -> u32 {
+        if now >= self.maturity_timestamp {
+            return self.coupon_periods;
+        }
+        let elapsed = now.saturating_sub(self.created_at) / self.coupon_interval();
+        (elapsed as u32).min(self.coupon_periods)
+    }
+    
+    /// Coupon owed per bond for a full coupon period on `face_value` at `rate_bps`
+    pub fn full_coupon(&self, face_value: u64, rate_bps: u64) -> Result<u64> {
+        face_value
+            .checked_mul(rate_bps)
+            .ok_or(BtrustError::MathOverflow)?
+            .checked_div(BPS_DENOMINATOR * self.payment_frequency as u64)
+            .ok_or(error!(BtrustError::MathOverflow))
+    }
+    
+    /// Coupon rate of a floating-rate bond for a reference rate of `reference_bps`
+    pub fn floating_rate_bps(&self, reference_bps: u64) -> u64 {
+        let rate = (reference_bps as i64).saturating_add(self.rate_spread_bps).max(0) as u64;
+        rate.clamp(self.rate_floor_bps, self.rate_cap_bps)
+    }
+    
+    /// Coupon rate of the unrecorded `period`
+    pub fn period_rate_bps(&self, period: u32) -> u64 {
+        if self.is_variable_rate {
+            self.last_fixed_rate_bps
+        } else if self.pending_rate_period > 0 && period >= self.pending_rate_period {
+            self.pending_coupon_rate_bps
+        } else {
+            self.coupon_rate_bps
+        }
+    }
+    
+    /// Primary sale price per bond of a zero-coupon bond at `now`
+    pub fn discount_price(&self, now: i64) -> Result<u64> {
+        let remaining = self.maturity_timestamp.saturating_sub(now).max(0);
+        let rate = self.discount_yield_bps as u128 * YIELD_INDEX_SCALE / BPS_DENOMINATOR as u128;
+        
+        let mut factor = YIELD_INDEX_SCALE;
+        for _ in 0..remaining / SECONDS_PER_YEAR {
+            factor = factor
+                .checked_mul(YIELD_INDEX_SCALE + rate)
+                .ok_or(BtrustError::MathOverflow)?
+                / YIELD_INDEX_SCALE;
+        }
+        let partial = rate * (remaining % SECONDS_PER_YEAR) as u128 / SECONDS_PER_YEAR as u128;
+        factor = factor
+            .checked_mul(YIELD_INDEX_SCALE + partial)
+            .ok_or(BtrustError::MathOverflow)?
+            / YIELD_INDEX_SCALE;
+        
+        let price = (self.principal_amount as u128)
+            .checked_mul(YIELD_INDEX_SCALE)
+            .ok_or(BtrustError::MathOverflow)?
+            / factor;
+        Ok(price as u64)
+    }
+    
+    /// Face value per bond at the start of coupon period `period`
+    pub fn face_value_at(&self, period: u32) -> Result<u64> {
+        let installment = self.principal_amount
+            .checked_mul(self.amortization_bps)
+            .ok_or(BtrustError::MathOverflow)?
+            / BPS_DENOMINATOR;
+        Ok(self.principal_amount.saturating_sub(installment.saturating_mul(period as u64)))
+    }
+    
+    /// Face value per bond after every recorded installment
+    pub fn face_value(&self) -> u64 {
+        self.principal_amount.saturating_sub(self.amortized_per_bond)
+    }
+    
+    /// Principal installment per bond paid on the record date of `period`
+    pub fn installment_per_bond(&self, period: u32) -> Result<u64> {
+        Ok(self.face_value_at(period)? - self.face_value_at(period + 1)?)
+    }
+    
+    /// Coupon owed per bond for `period` on the face value outstanding during it
+    pub fn coupon_per_bond(&self, period: u32, rate_bps: u64) -> Result<u64> {
+        let full_coupon = self.full_coupon(self.face_value_at(period)?, rate_bps)?;
+        
+        let length = self.coupon_record_date(period) - self.coupon_start_date(period);
+        let interval = self.coupon_interval();
+        if length >= interval {
+            return Ok(full_coupon);
+        }
+        
+        Ok(((full_coupon as u128) * (length as u128) / (interval as u128)) as u64)
+    }
+    
+    /// Whether primary sales go through an escrowed subscription window
+    pub fn has_subscription(&self) -> bool {
+        self.subscription_end > 0
+    }
+    
+    /// Whether primary sales go through a descending-price auction
+    pub fn has_auction(&self) -> bool {
+        self.auction_end > 0
+    }
+    
+    /// Face value of auction bids not yet issued by settlement
+    pub fn pending_auction_debt(&self) -> Result<u64> {
+        if self.is_settled {
+            return Ok(0);
+        }
+        self.auction_bid_quantity
+            .checked_mul(self.face_value())
+            .ok_or(error!(BtrustError::MathOverflow))
+    }
+    
+    /// Whether the auction has sold out or its window has ended
+    pub fn is_auction_closed(&self, now: i64) -> bool {
+        self.auction_bid_quantity >= self.total_supply || now >= self.auction_end
+    }
+    
+    /// When bidders may withdraw from an auction still unsettled
+    pub fn auction_settlement_deadline(&self) -> i64 {
+        self.auction_end.saturating_add(AUCTION_SETTLEMENT_WINDOW_SECS)
+    }
+    
+    /// Auction price per bond at `now`, decaying linearly from start to floor
+    pub fn auction_price(&self, now: i64) -> u64 {
+        if now <= self.auction_start {
+            return self.auction_start_price;
+        }
+        if now >= self.auction_end {
+            return self.auction_floor_price;
+        }
+        let elapsed = (now - self.auction_start) as u128;
+        let duration = (self.auction_end - self.auction_start) as u128;
+        let decay = (self.auction_start_price - self.auction_floor_price) as u128 * elapsed / duration;
+        self.auction_start_price - decay as u64
+    }
+    
+    /// Whether the issuer has called the bond for early redemption
+    pub fn is_called(&self) -> bool {
+        self.call_date > 0
+    }
+    
+    /// Call price in bps of par for a call on `call_date`
+    pub fn call_price_bps(&self, call_date: i64) -> u64 {
+        let step = ((call_date - self.first_call_date) / self.coupon_interval()) as usize;
+        self.call_schedule_bps
+            .iter()
+            .take_while(|bps| **bps > 0)
+            .take(step + 1)
+            .last()
+            .copied()
+            .unwrap_or(BPS_DENOMINATOR)
+    }
+    
+    /// Sinking fund deposits the schedule calls for by `now`
+    pub fn sinking_fund_scheduled(&self, now: i64) -> Result<u64> {
+        self.sinking_fund_per_period
+            .checked_mul(self.elapsed_coupon_periods(now) as u64)
+            .ok_or(error!(BtrustError::MathOverflow))
+    }
+    
+    /// Whether holders can convert into `conversion_mint`
+    pub fn is_convertible(&self) -> bool {
+        self.conversion_mint != Pubkey::default()
+    }
+    
+    /// Close of the put window `now` falls inside, if any
+    pub fn put_window_end(&self, now: i64) -> Option<i64> {
+        if self.put_price_bps == 0 {
+            return None;
+        }
+        self.put_dates
+            .iter()
+            .take_while(|date| **date > 0)
+            .map(|open| open + self.put_window_length)
+            .find(|end| now >= end - self.put_window_length && now < *end)
+    }
+    
+    /// Bonds a sinking fund draw can fall on, outside escrows and earlier draws
+    pub fn sinking_draw_supply(&self) -> Result<u64> {
+        self.outstanding_supply
+            .checked_sub(self.auction_unclaimed)
+            .and_then(|supply| supply.checked_sub(self.sinking_drawn_pending))
+            .and_then(|supply| supply.checked_sub(self.escrowed_supply))
+            .and_then(|supply| supply.checked_sub(self.put_tendered))
+            .and_then(|supply| supply.checked_sub(self.put_due))
+            .ok_or(error!(BtrustError::MathOverflow))
+    }
+    
+    /// Release what a revealed draw left unallocated once it has expired
+    pub fn expire_sinking_draw(&mut self, now: i64) {
+        if self.sinking_draw_slot == 0
+            && self.sinking_draw_remaining > 0
+            && now >= self.sinking_draw_revealed_at.saturating_add(SINKING_DRAW_EXPIRY_SECS)
+        {
+            self.sinking_draw_remaining = 0;
+            self.sinking_draw_eligible = 0;
+        }
+    }
     
-    pub token_program: Program<'info, Token>,
-}
+    /// Sinking fund money held back for drawn bonds awaiting allocation or redemption
+    pub fn sinking_fund_reserved(&self) -> Result<u64> {
+        self.sinking_drawn_pending
+            .checked_add(self.sinking_draw_remaining)
+            .ok_or(BtrustError::MathOverflow)?
+            .checked_mul(self.face_value())
+            .ok_or(error!(BtrustError::MathOverflow))
+    }
 
-#[derive(Accounts)]
-pub struct PurchaseBond<'info> {
-    #[account(mut)]
-    pub buyer: Signer<'info>,
-    
-    #[account(
-        seeds = [b"platform"],
-        bump = platform.bump,
-    )]
-    pub platform: Account<'info, Platform>,
+    /// Allocate the latest draw to a position's `undrawn` free bonds, rounding at random
+    pub fn allocate_draw(&mut self, holder: &Pubkey, undrawn: u64) -> Result<u64> {
+        let eligible = self.sinking_draw_eligible;
+        let undrawn = undrawn.min(eligible);
+        if undrawn == 0 {
+            return Ok(0);
+        }
+        
+        let share = (undrawn as u128) * (self.sinking_draw_remaining as u128);
+        let mut drawn = (share / eligible as u128) as u64;
+        let remainder = (share % eligible as u128) as u64;
+        
+        let mut roll = [0u8; 8];
+        roll.copy_from_slice(&hashv(&[&self.sinking_draw_seed, holder.as_ref()]).to_bytes()[..8]);
+        if u64::from_le_bytes(roll) % eligible < remainder {
+            drawn += 1;
+        }
+        
+        self.sinking_draw_remaining -= drawn;
+        self.sinking_draw_eligible -= undrawn;
+        self.sinking_drawn_pending = self.sinking_drawn_pending
+            .checked_add(drawn)
+            .ok_or(BtrustError::MathOverflow)?;
+        Ok(drawn)
+    }
     
-    #[account(mut)]
-    pub bond: Account<'info, Bond>,
+    /// Put price per bond at the current face value
+    pub fn put_price(&self) -> Result<u64> {
+        Ok(self.face_value()
+            .checked_mul(self.put_price_bps)
+            .ok_or(BtrustError::MathOverflow)?
+            / BPS_DENOMINATOR)
+    }
     
-    #[account(
-        mut,
-        constraint = bond_mint.key() == bond.bond_mint,
-    )]
-    pub bond_mint: Account<'info, Mint>,
+    /// Move tenders from a put window that has closed into `put_due`
+    pub fn roll_put_window(&mut self, now: i64) {
+        if self.put_tendered > 0 && now >= self.put_window_end {
+            self.put_due += self.put_tendered;
+            self.put_tendered = 0;
+        }
+    }
     
-    #[account(mut)]
-    pub buyer_payment: Account<'info, TokenAccount>,
+    /// Date from which holders can redeem
+    pub fn redemption_date(&self) -> i64 {
+        if self.is_called() {
+            self.call_date
+        } else {
+            self.maturity_timestamp
+        }
+    }
     
-    #[account(mut)]
-    pub issuer_payment: Account<'info, TokenAccount>,
+    /// Amount paid per bond on redemption
+    pub fn redemption_price(&self) -> u64 {
+        if self.is_called() {
+            self.call_price
+        } else {
+            self.face_value()
+        }
+    }
     
-    #[account(
-        mut,
-        constraint = treasury.key() == platform.treasury,
-    )]
-    pub treasury: Account<'info, TokenAccount>,
+    /// Whether holders recover pro-rata from the vaults instead of redeeming at par
+    pub fn in_recovery(&self) -> bool {
+        self.is_defaulted || self.is_liquidated
+    }
     
-    #[account(
-        init_if_needed,
-        payer = buyer,
-        associated_token::mint = bond_mint,
-        associated_token::authority = buyer,
-    )]
-    pub buyer_bond_account: Account<'info, TokenAccount>,
+    /// Advance the funded indexes past a period whose amount due is deposited
+    pub fn mark_period_funded(&mut self, coupon_period: &CouponPeriod) {
+        self.coupon_periods_funded += 1;
+        self.funded_yield_index = coupon_period.yield_index;
+        self.funded_amortized_per_bond = coupon_period.amortized_per_bond;
+    }
     
-    #[account(
-        init_if_needed,
-        payer = buyer,
-        space = 8 + HolderPosition::INIT_SPACE,
-        seeds = [b"position", bond.key().as_ref(), buyer.key().as_ref()],
-        bump,
-    )]
-    pub holder_position: Account<'info, HolderPosition>,
+    /// Yield index and amortized principal backed by funded coupon periods.
+    /// A bond in recovery pays out everything recorded instead.
+    pub fn funded_indexes(&self) -> (u128, u64) {
+        if self.in_recovery() {
+            (self.yield_index, self.amortized_per_bond)
+        } else {
+            (self.funded_yield_index, self.funded_amortized_per_bond)
+        }
+    }
     
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub system_program: Program<'info, System>,
+    /// Whether principal fell due by `now` and is not fully escrowed
+    pub fn principal_overdue(&self, now: i64) -> Result<bool> {
+        Ok(now >= self.redemption_date() && self.outstanding_debt()? > 0)
+    }
+
+    /// Principal owed to holders not yet escrowed in the redemption vault
+    pub fn outstanding_debt(&self) -> Result<u64> {
+        Ok(self.outstanding_supply
+            .checked_mul(self.redemption_price())
+            .ok_or(BtrustError::MathOverflow)?
+            .saturating_sub(self.principal_repaid))
+    }
 }
 
-#[derive(Accounts)]
-pub struct DepositYield<'info> {
-    #[account(mut)]
-    pub issuer: Signer<'info>,
-    
-    #[account(
-        mut,
-        constraint = bond.issuer == issuer.key() @ BtrustError::Unauthorized,
-    )]
-    pub bond: Account<'info, Bond>,
+#[account]
+#[derive(InitSpace)]
+pub struct HolderPosition {
+    pub holder: Pubkey,
+    pub bond: Pubkey,
+    pub quantity: u64,
+    pub purchase_price: u64,
+    pub purchase_timestamp: i64,
+    pub total_yield_claimed: u64,
+    pub escrowed_quantity: u64,
+    pub put_tendered: u64,
+    pub put_window_end: i64,
+    pub subscribed_quantity: u64,
+    pub subscription_paid: u64,
+    pub yield_index_checkpoint: u128,
+    pub accrued_yield: u64,
+    pub sinking_draw_checkpoint: u32,
+    pub drawn_quantity: u64,
+    pub vote_snapshot_quantity: u64,
+    pub vote_snapshot_from: u32,
+    pub vote_snapshot_count: u32,
+    pub amortized_checkpoint: u64,
+    pub accrued_principal: u64,
+    pub total_principal_claimed: u64,
+    pub pending_yield: u64,
+    pub pending_principal: u64,
+    pub pending_until_period: u32,
+    pub bump: u8,
+}
+
+impl HolderPosition {
+    /// Bonds the holder can still move, outside escrows and sinking fund draws
+    pub fn free_quantity(&self) -> u64 {
+        self.quantity
+            .saturating_sub(self.escrowed_quantity)
+            .saturating_sub(self.put_tendered)
+            .saturating_sub(self.drawn_quantity)
+    }
     
-    #[account(mut)]
-    pub issuer_payment: Account<'info, TokenAccount>,
+    /// Release draws on bonds that left the position some other way
+    pub fn release_drawn(&mut self, bond: &mut Bond) {
+        if self.drawn_quantity > self.quantity {
+            let released = self.drawn_quantity - self.quantity;
+            self.drawn_quantity = self.quantity;
+            bond.sinking_drawn_pending = bond.sinking_drawn_pending.saturating_sub(released);
+        }
+    }
     
-    #[account(
-        mut,
-        seeds = [b"yield_vault", bond.key().as_ref()],
-        bump,
-    )]
-    pub yield_vault: Account<'info, TokenAccount>,
+    /// Keep the voting power of proposals created since the last change
+    pub fn snapshot_votes(&mut self, bond: &Bond) {
+        if bond.proposal_count > self.vote_snapshot_count {
+            self.vote_snapshot_quantity = self.quantity;
+            self.vote_snapshot_from = self.vote_snapshot_count;
+            self.vote_snapshot_count = bond.proposal_count;
+        }
+    }
     
-    pub token_program: Program<'info, Token>,
-}
-
-#[derive(Accounts)]
-pub struct ClaimYield<'info> {
-    #[account(mut)]
-    pub holder: Signer<'info>,
+    /// Bonds held when a proposal was created
+    pub fn voting_power(&self, proposal: u32) -> Result<u64> {
+        if proposal >= self.vote_snapshot_count {
+            Ok(self.quantity)
+        } else if proposal >= self.vote_snapshot_from {
+            Ok(self.vote_snapshot_quantity)
+        } else {
+            err!(BtrustError::PositionChangedSinceSnapshot)
+        }
+    }
     
-    #[account(
-        seeds = [b"bond", bond.bond_mint.as_ref()],
-        bump = bond.bump,
-    )]
-    pub bond: Account<'info, Bond>,
+    /// Accrue yield and amortized principal up to the bond's current index
+    pub fn settle_yield(&mut self, bond: &mut Bond) -> Result<()> {
+        self.settle(bond, true)
+    }
     
-    #[account(
-        mut,
-        seeds = [b"position", bond.key().as_ref(), holder.key().as_ref()],
-        bump = holder_position.bump,
-    )]
-    pub holder_position: Account<'info, HolderPosition>,
+    /// Accrue only what funded coupon periods back, for claims that leave the
+    /// balance unchanged. Unfunded coupons accrue on a later settle instead.
+    pub fn settle_funded_yield(&mut self, bond: &mut Bond) -> Result<()> {
+        self.settle(bond, false)
+    }
     
-    #[account(
-        mut,
-        seeds = [b"yield_vault", bond.key().as_ref()],
-        bump,
-    )]
-    pub yield_vault: Account<'info, TokenAccount>,
+    /// Credit coupons recorded since a checkpoint on `quantity` bonds, up to the
+    /// funded indexes or, with `include_unfunded`, the current ones. What is
+    /// not yet funded is held pending until its coupon periods are.
+    /// Returns the indexes credited up to.
+    pub fn credit_yield(
+        &mut self,
+        bond: &Bond,
+        quantity: u64,
+        yield_from: u128,
+        amortized_from: u64,
+        include_unfunded: bool,
+    ) -> Result<(u128, u64)> {
+        let (funded_index, funded_amortized) = bond.funded_indexes();
+        let funded_index = funded_index.max(yield_from);
+        let funded_amortized = funded_amortized.max(amortized_from);
+        
+        let (yield_amount, principal) = accrual(quantity, yield_from, funded_index, amortized_from, funded_amortized)?;
+        self.accrued_yield = self.accrued_yield
+            .checked_add(yield_amount)
+            .ok_or(BtrustError::MathOverflow)?;
+        self.accrued_principal = self.accrued_principal
+            .checked_add(principal)
+            .ok_or(BtrustError::MathOverflow)?;
+        
+        if !include_unfunded {
+            return Ok((funded_index, funded_amortized));
+        }
+        
+        // A later credit before the pending amount is funded merges into it,
+        // so both are released once the later periods are funded too
+        let (yield_amount, principal) = accrual(
+            quantity,
+            funded_index,
+            bond.yield_index,
+            funded_amortized,
+            bond.amortized_per_bond,
+        )?;
+        if yield_amount > 0 || principal > 0 {
+            self.pending_yield = self.pending_yield
+                .checked_add(yield_amount)
+                .ok_or(BtrustError::MathOverflow)?;
+            self.pending_principal = self.pending_principal
+                .checked_add(principal)
+                .ok_or(BtrustError::MathOverflow)?;
+            self.pending_until_period = bond.coupon_periods_recorded;
+        }
+        
+        Ok((bond.yield_index, bond.amortized_per_bond))
+    }
     
-    #[account(mut)]
-    pub holder_payment: Account<'info, TokenAccount>,
+    fn settle(&mut self, bond: &mut Bond, include_unfunded: bool) -> Result<()> {
+        // Balances may only change once every elapsed record date is recorded.
+        // A bond in recovery pays out what is already recorded instead.
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            bond.in_recovery() || bond.coupon_periods_recorded >= bond.elapsed_coupon_periods(now),
+            BtrustError::CouponRecordPending
+        );
+        
+        if bond.in_recovery() || self.pending_until_period <= bond.coupon_periods_funded {
+            self.accrued_yield = self.accrued_yield
+                .checked_add(self.pending_yield)
+                .ok_or(BtrustError::MathOverflow)?;
+            self.accrued_principal = self.accrued_principal
+                .checked_add(self.pending_principal)
+                .ok_or(BtrustError::MathOverflow)?;
+            self.pending_yield = 0;
+            self.pending_principal = 0;
+        }
+        
+        let (yield_index, amortized) = self.credit_yield(
+            bond,
+            self.quantity,
+            self.yield_index_checkpoint,
+            self.amortized_checkpoint,
+            include_unfunded,
+        )?;
+        self.yield_index_checkpoint = yield_index;
+        self.amortized_checkpoint = amortized;
+        
+        // Only the latest draw can still be unallocated: a new draw waits
+        // until the previous one is allocated or expires, and positions
+        // settled late got nothing from it. Draws lapse once the bond is in
+        // recovery.
+        bond.expire_sinking_draw(now);
+        if self.sinking_draw_checkpoint < bond.sinking_fund_draws {
+            if !bond.in_recovery() {
+                let undrawn = self.free_quantity();
+                self.drawn_quantity += bond.allocate_draw(&self.holder, undrawn)?;
+            }
+            self.sinking_draw_checkpoint = bond.sinking_fund_draws;
+        }
+        
+        self.snapshot_votes(bond);
+        
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Order {
+    pub seller: Pubkey,
+    pub bond: Pubkey,
+    pub quantity: u64,
+    pub price_per_bond: u64,
+    pub created_at: i64,
+    pub is_active: bool,
+    pub is_retirable: bool,
+    pub next_retirable: Pubkey,
+    pub bump: u8,
+}
+
+/// Platform-approved price feed for a collateral mint
+#[account]
+#[derive(InitSpace)]
+pub struct CollateralOracle {
+    pub collateral_mint: Pubkey,
+    pub price_oracle: Pubkey,
+    pub bump: u8,
+}
+
+/// Price feed owned by this program, used in place of Pyth on localnet
+#[account]
+#[derive(InitSpace)]
+pub struct MockPriceFeed {
+    pub authority: Pubkey,
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+}
+
+/// Funding and record-date snapshot for one coupon period of a bond
+#[account]
+#[derive(InitSpace)]
+pub struct CouponPeriod {
+    pub bond: Pubkey,
+    pub period: u32,
+    pub record_date: i64,
+    pub coupon_rate_bps: u64,
+    pub is_fixed: bool,
+    pub coupon_per_bond: u64,
+    pub principal_per_bond: u64,
+    pub supply_at_record: u64,
+    pub amount_due: u64,
+    pub amount_funded: u64,
+    pub is_recorded: bool,
+    pub yield_index: u128,
+    pub amortized_per_bond: u64,
+    pub bump: u8,
+}
+
+impl CouponPeriod {
+    /// Whether the period is recorded and its full amount due has been deposited
+    pub fn is_funded(&self) -> bool {
+        self.is_recorded && self.amount_funded >= self.amount_due
+    }
+}
+
+/// Published reference rate (e.g. a SOFR proxy) for floating-rate coupons
+#[account]
+#[derive(InitSpace)]
+pub struct ReferenceRate {
+    pub publisher: Pubkey,
+    pub rate_bps: u64,
+    pub updated_at: i64,
+    pub history_rate_bps: [u64; RATE_HISTORY_LEN],
+    pub history_published_at: [i64; RATE_HISTORY_LEN],
+    pub history_next: u8,
+}
+
+impl ReferenceRate {
+    /// Set the current rate, overwriting the oldest entry in the history
+    pub fn publish(&mut self, rate_bps: u64, now: i64) {
+        self.rate_bps = rate_bps;
+        self.updated_at = now;
+        
+        let slot = self.history_next as usize;
+        self.history_rate_bps[slot] = rate_bps;
+        self.history_published_at[slot] = now;
+        self.history_next = ((slot + 1) % RATE_HISTORY_LEN) as u8;
+    }
     
-    pub token_program: Program<'info, Token>,
+    /// Latest rate published at or before `timestamp` that is still in the
+    /// history, with its publication time
+    pub fn rate_at(&self, timestamp: i64) -> Option<(u64, i64)> {
+        self.history_published_at
+            .iter()
+            .zip(self.history_rate_bps.iter())
+            .filter(|(published_at, _)| **published_at > 0 && **published_at <= timestamp)
+            .max_by_key(|(published_at, _)| **published_at)
+            .map(|(published_at, rate_bps)| (*rate_bps, *published_at))
+    }
+}
+
+/// A bidder's locked funds in a bond's primary auction
+#[account]
+#[derive(InitSpace)]
+pub struct Bid {
+    pub bond: Pubkey,
+    pub bidder: Pubkey,
+    pub quantity: u64,
+    pub locked_amount: u64,
+    pub bump: u8,
+}
+
+/// Issuer-proposed change to a bond's terms, applied only if holders approve it
+#[account]
+#[derive(InitSpace)]
+pub struct Proposal {
+    pub bond: Pubkey,
+    pub index: u32,
+    pub amendment: Amendment,
+    pub created_at: i64,
+    pub voting_end: i64,
+    pub supply_snapshot: u64,
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub is_finalized: bool,
+    pub is_approved: bool,
+    pub bump: u8,
 }
 
-#[derive(Accounts)]
-pub struct RedeemBond<'info> {
-    #[account(mut)]
-    pub holder: Signer<'info>,
-    
-    #[account(
-        mut,
-        seeds = [b"bond", bond.bond_mint.as_ref()],
-        bump = bond.bump,
-    )]
-    pub bond: Account<'info, Bond>,
-    
-    #[account(
-        mut,
-        constraint = bond_mint.key() == bond.bond_mint,
-    )]
-    pub bond_mint: Account<'info, Mint>,
-    
-    #[account(
-        mut,
-        seeds = [b"position", bond.key().as_ref(), holder.key().as_ref()],
-        bump = holder_position.bump,
-    )]
-    pub holder_position: Account<'info, HolderPosition>,
+/// One holder's vote on a proposal
+#[account]
+#[derive(InitSpace)]
+pub struct VoteRecord {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub weight: u64,
+    pub approve: bool,
+    pub bump: u8,
+}
+
+// ============================================================================
+// Args
+// ============================================================================
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub enum Amendment {
+    ExtendMaturity { maturity_timestamp: i64 },
+    ChangeCouponRate { coupon_rate_bps: u64 },
+    /// Relax the collateral covenant
+    WaiveCollateralRatio { collateral_ratio_bps: u64 },
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateBondArgs {
+    pub name: String,
+    pub symbol: String,
+    pub description: String,
+    pub image_uri: String,
+    pub website: String,
+    pub twitter: String,
+    pub discord: String,
+    pub principal_amount: u64,
+    pub coupon_rate_bps: u64,
+    pub amortization_bps: u64,
+    pub is_zero_coupon: bool,
+    pub discount_yield_bps: u64,
+    pub is_variable_rate: bool,
+    pub reference_rate: Pubkey,
+    pub rate_spread_bps: i64,
+    pub rate_floor_bps: u64,
+    pub rate_cap_bps: u64,
+    pub payment_frequency: u8,
+    pub maturity_timestamp: i64,
+    pub is_perpetual: bool,
+    pub total_supply: u64,
+    pub is_capped: bool,
+    pub collateral_ratio_bps: u64,
+    pub is_unsecured: bool,
+    pub payment_mint: Pubkey,
+    pub grace_period: i64,
+    pub subscription_start: i64,
+    pub subscription_end: i64,
+    pub soft_cap: u64,
+    pub auction_start: i64,
+    pub auction_end: i64,
+    pub auction_start_price: u64,
+    pub auction_floor_price: u64,
+    pub first_call_date: i64,
+    pub call_schedule_bps: [u64; MAX_CALL_SCHEDULE_STEPS],
+    pub call_notice_period: i64,
+    pub put_dates: [i64; MAX_PUT_WINDOWS],
+    pub put_window_length: i64,
+    pub put_price_bps: u64,
+    pub sinking_fund_per_period: u64,
+    pub trustee: Pubkey,
+    pub conversion_mint: Pubkey,
+    pub conversion_ratio: u64,
+    pub conversion_start: i64,
+    pub conversion_end: i64,
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Thaw a holder's bond token account so the program can move bonds in or out
+fn thaw_holder_account<'info>(
+    token_program: &Program<'info, Token>,
+    bond: &Account<'info, Bond>,
+    bond_mint: &Account<'info, Mint>,
+    holder_account: &Account<'info, TokenAccount>,
+) -> Result<()> {
+    if !holder_account.is_frozen() {
+        return Ok(());
+    }
     
-    #[account(mut)]
-    pub holder_bond_account: Account<'info, TokenAccount>,
+    let bond_mint_key = bond.bond_mint;
+    let seeds = &[
+        b"bond",
+        bond_mint_key.as_ref(),
+        &[bond.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
     
-    #[account(
-        mut,
-        seeds = [b"redemption_vault", bond.key().as_ref()],
-        bump,
-    )]
-    pub redemption_vault: Account<'info, TokenAccount>,
+    token::thaw_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        ThawAccount {
+            account: holder_account.to_account_info(),
+            mint: bond_mint.to_account_info(),
+            authority: bond.to_account_info(),
+        },
+        signer_seeds,
+    ))
+}
+
+/// Freeze a holder's bond token account again once the program has moved bonds
+fn freeze_holder_account<'info>(
+    token_program: &Program<'info, Token>,
+    bond: &Account<'info, Bond>,
+    bond_mint: &Account<'info, Mint>,
+    holder_account: &mut Account<'info, TokenAccount>,
+) -> Result<()> {
+    holder_account.reload()?;
+    if holder_account.amount == 0 || holder_account.is_frozen() {
+        return Ok(());
+    }
     
-    #[account(mut)]
-    pub holder_payment: Account<'info, TokenAccount>,
+    let bond_mint_key = bond.bond_mint;
+    let seeds = &[
+        b"bond",
+        bond_mint_key.as_ref(),
+        &[bond.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
     
-    pub token_program: Program<'info, Token>,
+    token::freeze_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        FreezeAccount {
+            account: holder_account.to_account_info(),
+            mint: bond_mint.to_account_info(),
+            authority: bond.to_account_info(),
+        },
+        signer_seeds,
+    ))
 }
 
-#[derive(Accounts)]
-pub struct Liquidate<'info> {
-    #[account(mut)]
-    pub liquidator: Signer<'info>,
-    
-    #[account(
-        mut,
-        seeds = [b"bond", bond.bond_mint.as_ref()],
-        bump = bond.bump,
-    )]
-    pub bond: Account<'info, Bond>,
+/// Reject the call if the platform is paused or the bond has `flag` set
+fn require_not_paused(platform: &Platform, bond: &Bond, flag: u8) -> Result<()> {
+    require!(!platform.is_paused, BtrustError::PlatformPaused);
+    require!(bond.pause_flags & flag == 0, BtrustError::BondPaused);
+    require!(
+        flag & PAUSE_TRADING == 0 || !bond.trading_frozen,
+        BtrustError::TradingFrozen
+    );
+    Ok(())
+}
+
+/// Yield and amortized principal earned by `quantity` bonds between two index points
+fn accrual(
+    quantity: u64,
+    yield_from: u128,
+    yield_to: u128,
+    amortized_from: u64,
+    amortized_to: u64,
+) -> Result<(u64, u64)> {
+    let yield_amount = (quantity as u128)
+        .checked_mul(yield_to.saturating_sub(yield_from))
+        .ok_or(BtrustError::MathOverflow)?
+        / YIELD_INDEX_SCALE;
+    let principal = quantity
+        .checked_mul(amortized_to.saturating_sub(amortized_from))
+        .ok_or(BtrustError::MathOverflow)?;
+    Ok((
+        u64::try_from(yield_amount).map_err(|_| error!(BtrustError::MathOverflow))?,
+        principal,
+    ))
+}
+
+/// Share of `pool` owed to `quantity` out of `supply` bonds, rounded down
+fn pro_rata(pool: u64, quantity: u64, supply: u64) -> Result<u64> {
+    require!(supply > 0 && quantity <= supply, BtrustError::InsufficientBalance);
+    let share = (pool as u128)
+        .checked_mul(quantity as u128)
+        .ok_or(BtrustError::MathOverflow)?
+        / supply as u128;
+    Ok(share as u64)
+}
+
+/// Reject a primary sale of a secured bond its collateral does not cover
+fn require_collateral_covers(
+    bond: &Bond,
+    price_oracle: &AccountInfo,
+    additional_debt: u64,
+) -> Result<()> {
+    if bond.is_unsecured {
+        return Ok(());
+    }
     
-    #[account(
-        mut,
-        constraint = collateral_vault.key() == bond.collateral_vault,
-    )]
-    pub collateral_vault: Account<'info, TokenAccount>,
+    let required_value = bond.outstanding_debt()?
+        .checked_add(bond.pending_auction_debt()?)
+        .ok_or(BtrustError::MathOverflow)?
+        .checked_add(additional_debt)
+        .ok_or(BtrustError::MathOverflow)?
+        .checked_mul(bond.collateral_ratio_bps)
+        .ok_or(BtrustError::MathOverflow)?
+        .checked_div(BPS_DENOMINATOR)
+        .ok_or(BtrustError::MathOverflow)?;
     
-    #[account(mut)]
-    pub liquidator_collateral: Account<'info, TokenAccount>,
+    let price = load_oracle_price(price_oracle, Clock::get()?.unix_timestamp)?;
+    let collateral_value = collateral_value(bond, bond.collateral_deposited, &price)?;
     
-    pub token_program: Program<'info, Token>,
+    require!(
+        collateral_value >= required_value,
+        BtrustError::InsufficientCollateral
+    );
+    Ok(())
 }
 
-#[derive(Accounts)]
-pub struct CreateSellOrder<'info> {
-    #[account(mut)]
-    pub seller: Signer<'info>,
-    
-    pub bond: Account<'info, Bond>,
-    
-    #[account(
-        init,
-        payer = seller,
-        space = 8 + Order::INIT_SPACE,
-        seeds = [b"order", seller.key().as_ref(), bond.key().as_ref(), &Clock::get().unwrap().unix_timestamp.to_le_bytes()],
-        bump,
-    )]
-    pub order: Account<'info, Order>,
+/// Reject a trustee action unless the bond is in breach of its covenants
+fn require_covenant_breach(
+    bond: &Bond,
+    price_oracle: &AccountInfo,
+    coupon_period: Option<&CouponPeriod>,
+    now: i64,
+) -> Result<()> {
+    let deadline = now
+        .checked_sub(bond.grace_period)
+        .ok_or(BtrustError::MathOverflow)?;
+    let coupon_missed = coupon_period.is_some_and(|coupon_period| {
+        coupon_period.is_recorded
+            && !coupon_period.is_funded()
+            && coupon_period.record_date < deadline
+    });
+    if coupon_missed
+        || bond.principal_overdue(deadline)?
+        || bond.sinking_fund_deposited < bond.sinking_fund_scheduled(deadline)?
+    {
+        return Ok(());
+    }
     
-    #[account(mut)]
-    pub seller_bond_account: Account<'info, TokenAccount>,
+    require!(!bond.is_unsecured, BtrustError::NoCovenantBreach);
+    let required_value = bond.outstanding_debt()?
+        .checked_mul(bond.collateral_ratio_bps)
+        .ok_or(BtrustError::MathOverflow)?
+        .checked_div(BPS_DENOMINATOR)
+        .ok_or(BtrustError::MathOverflow)?;
     
-    #[account(
-        init,
-        payer = seller,
-        token::mint = bond.bond_mint,
-        token::authority = order,
-    )]
-    pub order_escrow: Account<'info, TokenAccount>,
+    let price = load_oracle_price(price_oracle, now)?;
+    let collateral_value = collateral_value(bond, bond.collateral_deposited, &price)?;
     
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
+    require!(
+        collateral_value < required_value,
+        BtrustError::NoCovenantBreach
+    );
+    Ok(())
 }
 
-#[derive(Accounts)]
-pub struct FillOrder<'info> {
-    #[account(mut)]
-    pub buyer: Signer<'info>,
-    
-    #[account(
-        seeds = [b"platform"],
-        bump = platform.bump,
-    )]
-    pub platform: Account<'info, Platform>,
+/// Reject an amendment that does not make sense for the bond's current terms
+fn validate_amendment(bond: &Bond, amendment: &Amendment) -> Result<()> {
+    match *amendment {
+        Amendment::ExtendMaturity { maturity_timestamp } => {
+            require!(!bond.is_perpetual && !bond.is_called(), BtrustError::InvalidAmendment);
+            require!(
+                Clock::get()?.unix_timestamp < bond.maturity_timestamp
+                    && maturity_timestamp > bond.maturity_timestamp,
+                BtrustError::InvalidAmendment
+            );
+        }
+        Amendment::ChangeCouponRate { coupon_rate_bps } => {
+            require!(
+                !bond.is_zero_coupon && !bond.is_variable_rate,
+                BtrustError::InvalidAmendment
+            );
+            require!(coupon_rate_bps <= 10000, BtrustError::InvalidCouponRate);
+        }
+        Amendment::WaiveCollateralRatio { collateral_ratio_bps } => {
+            require!(!bond.is_unsecured, BtrustError::InvalidAmendment);
+            require!(
+                collateral_ratio_bps >= LIQUIDATION_THRESHOLD_BPS,
+                BtrustError::InvalidAmendment
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Hash of `slot` from the SlotHashes sysvar, if it is still recorded
+fn find_slot_hash(slot_hashes: &AccountInfo, slot: u64) -> Result<Option<[u8; 32]>> {
+    // Layout: u64 entry count, then (u64 slot, [u8; 32] hash) entries, newest first
+    let data = slot_hashes.try_borrow_data()?;
+    let count = data
+        .get(..8)
+        .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()) as usize)
+        .ok_or(BtrustError::MathOverflow)?;
+    for entry in data[8..].chunks_exact(40).take(count) {
+        let entry_slot = u64::from_le_bytes(entry[..8].try_into().unwrap());
+        if entry_slot == slot {
+            return Ok(Some(entry[8..].try_into().unwrap()));
+        }
+        if entry_slot < slot {
+            break;
+        }
+    }
+    Ok(None)
+}
+
+// ============================================================================
+// Oracle
+// ============================================================================
+
+/// Pyth v2 price account layout
+const PYTH_MAGIC: u32 = 0xa1b2c3d4;
+const PYTH_ACCOUNT_TYPE_PRICE: u32 = 3;
+const PYTH_STATUS_TRADING: u32 = 1;
+const PYTH_EXPO_OFFSET: usize = 20;
+const PYTH_TIMESTAMP_OFFSET: usize = 96;
+const PYTH_AGG_PRICE_OFFSET: usize = 208;
+const PYTH_AGG_CONF_OFFSET: usize = 216;
+const PYTH_AGG_STATUS_OFFSET: usize = 224;
+const PYTH_PRICE_ACCOUNT_MIN_LEN: usize = 240;
+
+/// A validated oracle price: `price * 10^expo` units of payment token per whole collateral token
+pub struct OraclePrice {
+    pub price: u64,
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+}
+
+/// Read a Pyth (or mock) price, enforcing staleness and confidence limits
+fn load_oracle_price(oracle: &AccountInfo, now: i64) -> Result<OraclePrice> {
+    let data = oracle.try_borrow_data()?;
     
-    #[account(mut)]
-    pub order: Account<'info, Order>,
+    let (price, conf, expo, publish_time) = if *oracle.owner == pyth::ID {
+        require!(data.len() >= PYTH_PRICE_ACCOUNT_MIN_LEN, BtrustError::InvalidOracle);
+        require!(read_u32(&data, 0) == PYTH_MAGIC, BtrustError::InvalidOracle);
+        require!(read_u32(&data, 8) == PYTH_ACCOUNT_TYPE_PRICE, BtrustError::InvalidOracle);
+        require!(
+            read_u32(&data, PYTH_AGG_STATUS_OFFSET) == PYTH_STATUS_TRADING,
+            BtrustError::InvalidOraclePrice
+        );
+        (
+            read_u64(&data, PYTH_AGG_PRICE_OFFSET) as i64,
+            read_u64(&data, PYTH_AGG_CONF_OFFSET),
+            read_u32(&data, PYTH_EXPO_OFFSET) as i32,
+            read_u64(&data, PYTH_TIMESTAMP_OFFSET) as i64,
+        )
+    } else {
+        load_mock_price(oracle)?
+    };
     
-    #[account(mut)]
-    pub buyer_payment: Account<'info, TokenAccount>,
+    require!(price > 0, BtrustError::InvalidOraclePrice);
+    require!(
+        now.saturating_sub(publish_time) <= MAX_ORACLE_STALENESS_SECS,
+        BtrustError::StaleOraclePrice
+    );
     
-    #[account(mut)]
-    pub seller_payment: Account<'info, TokenAccount>,
+    let price = price as u64;
+    require!(
+        (conf as u128) * (BPS_DENOMINATOR as u128) <= (price as u128) * (MAX_ORACLE_CONF_BPS as u128),
+        BtrustError::OracleConfidenceTooWide
+    );
     
-    #[account(
-        mut,
-        constraint = treasury.key() == platform.treasury,
-    )]
-    pub treasury: Account<'info, TokenAccount>,
+    Ok(OraclePrice {
+        price,
+        conf,
+        expo,
+        publish_time,
+    })
+}
+
+/// Read the raw price fields of a `MockPriceFeed`
+#[cfg(feature = "mock-oracle")]
+fn load_mock_price(oracle: &AccountInfo) -> Result<(i64, u64, i32, i64)> {
+    require!(*oracle.owner == crate::ID, BtrustError::InvalidOracle);
+    let data = oracle.try_borrow_data()?;
+    let feed = MockPriceFeed::try_deserialize(&mut &data[..])
+        .map_err(|_| error!(BtrustError::InvalidOracle))?;
+    Ok((feed.price, feed.conf, feed.expo, feed.publish_time))
+}
+
+/// Mock feeds are only accepted in `mock-oracle` builds
+#[cfg(not(feature = "mock-oracle"))]
+fn load_mock_price(_oracle: &AccountInfo) -> Result<(i64, u64, i32, i64)> {
+    err!(BtrustError::InvalidOracle)
+}
+
+/// Value `amount` base units of collateral in base units of the payment token
+fn collateral_value(bond: &Bond, amount: u64, price: &OraclePrice) -> Result<u64> {
+    let mut value = (amount as u128)
+        .checked_mul(price.price as u128)
+        .ok_or(BtrustError::MathOverflow)?;
     
-    #[account(mut)]
-    pub order_escrow: Account<'info, TokenAccount>,
+    // Shift by payment decimals, collateral decimals and the price exponent in one step
+    let shift = bond.payment_decimals as i32 - bond.collateral_decimals as i32 + price.expo;
+    let factor = 10u128
+        .checked_pow(shift.unsigned_abs())
+        .ok_or(BtrustError::MathOverflow)?;
+    value = if shift >= 0 {
+        value.checked_mul(factor).ok_or(BtrustError::MathOverflow)?
+    } else {
+        value / factor
+    };
     
-    #[account(mut)]
-    pub buyer_bond_account: Account<'info, TokenAccount>,
+    u64::try_from(value).map_err(|_| error!(BtrustError::MathOverflow))
+}
+
+/// Convert `value` base units of payment token into base units of collateral
+fn payment_to_collateral(bond: &Bond, value: u64, price: &OraclePrice) -> Result<u64> {
+    let shift = bond.payment_decimals as i32 - bond.collateral_decimals as i32 + price.expo;
+    let factor = 10u128
+        .checked_pow(shift.unsigned_abs())
+        .ok_or(BtrustError::MathOverflow)?;
+    let amount = if shift >= 0 {
+        let divisor = (price.price as u128)
+            .checked_mul(factor)
+            .ok_or(BtrustError::MathOverflow)?;
+        (value as u128) / divisor
+    } else {
+        (value as u128)
+            .checked_mul(factor)
+            .ok_or(BtrustError::MathOverflow)?
+            / (price.price as u128)
+    };
     
-    pub token_program: Program<'info, Token>,
+    u64::try_from(amount).map_err(|_| error!(BtrustError::MathOverflow))
+}
+
+/// Add the liquidation penalty on top of a repaid amount
+fn apply_liquidation_penalty(amount: u64) -> Result<u64> {
+    amount
+        .checked_mul(BPS_DENOMINATOR + LIQUIDATION_PENALTY_BPS)
+        .ok_or(BtrustError::MathOverflow)?
+        .checked_div(BPS_DENOMINATOR)
+        .ok_or(BtrustError::MathOverflow.into())
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+fn read_u64(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+// ============================================================================
+// Events
+// ============================================================================
+
+#[event]
+pub struct PlatformInitialized {
+    pub authority: Pubkey,
+    pub treasury: Pubkey,
+}
+
+#[event]
+pub struct PlatformFeeUpdated {
+    pub old_fee_bps: u64,
+    pub new_fee_bps: u64,
+}
+
+#[event]
+pub struct TreasuryUpdated {
+    pub old_treasury: Pubkey,
+    pub new_treasury: Pubkey,
+}
+
+#[event]
+pub struct AuthorityProposed {
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+}
+
+#[event]
+pub struct AuthorityTransferred {
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event]
+pub struct GuardianUpdated {
+    pub old_guardian: Pubkey,
+    pub new_guardian: Pubkey,
+}
+
+#[event]
+pub struct PlatformPauseUpdated {
+    pub paused: bool,
+    pub updated_by: Pubkey,
+}
+
+#[event]
+pub struct BondPauseUpdated {
+    pub bond: Pubkey,
+    pub pause_flags: u8,
+    pub updated_by: Pubkey,
+}
+
+#[event]
+pub struct BondCreated {
+    pub bond: Pubkey,
+    pub issuer: Pubkey,
+    pub name: String,
+    pub principal_amount: u64,
+    pub coupon_rate_bps: u64,
+    pub maturity_timestamp: i64,
+    pub is_perpetual: bool,
+    pub total_supply: u64,
+    pub payment_mint: Pubkey,
+    pub is_unsecured: bool,
+}
+
+#[event]
+pub struct CollateralDeposited {
+    pub bond: Pubkey,
+    pub amount: u64,
+    pub total_collateral: u64,
+}
+
+#[event]
+pub struct BondVaultsInitialized {
+    pub bond: Pubkey,
+    pub payment_mint: Pubkey,
+    pub redemption_vault: Pubkey,
+    pub yield_vault: Pubkey,
+}
+
+#[event]
+pub struct RedemptionFunded {
+    pub bond: Pubkey,
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub total_escrowed: u64,
+}
+
+#[event]
+pub struct OfferingCancelled {
+    pub bond: Pubkey,
+    pub issuer: Pubkey,
+    pub collateral_returned: u64,
+    pub payment_returned: u64,
+}
+
+#[event]
+pub struct CollateralWithdrawn {
+    pub bond: Pubkey,
+    pub amount: u64,
+    pub total_collateral: u64,
+}
+
+#[event]
+pub struct BondPurchased {
+    pub bond: Pubkey,
+    pub buyer: Pubkey,
+    pub quantity: u64,
+    pub payment_amount: u64,
+    pub fee_amount: u64,
+}
+
+#[event]
+pub struct SubscriptionSettled {
+    pub bond: Pubkey,
+    pub raised: u64,
+    pub fee_amount: u64,
+    pub outstanding_supply: u64,
+}
+
+#[event]
+pub struct SubscriptionRefunded {
+    pub bond: Pubkey,
+    pub holder: Pubkey,
+    pub quantity: u64,
+    pub refund_amount: u64,
+}
+
+#[event]
+pub struct AuctionBidPlaced {
+    pub bond: Pubkey,
+    pub bidder: Pubkey,
+    pub quantity: u64,
+    pub price: u64,
+    pub locked_amount: u64,
+    pub total_bid_quantity: u64,
 }
 
-#[derive(Accounts)]
-pub struct CancelOrder<'info> {
-    #[account(mut)]
-    pub seller: Signer<'info>,
-    
-    #[account(mut)]
-    pub order: Account<'info, Order>,
-    
-    #[account(mut)]
-    pub order_escrow: Account<'info, TokenAccount>,
-    
-    #[account(mut)]
-    pub seller_bond_account: Account<'info, TokenAccount>,
-    
-    pub token_program: Program<'info, Token>,
+#[event]
+pub struct AuctionSettled {
+    pub bond: Pubkey,
+    pub clearing_price: u64,
+    pub quantity: u64,
+    pub proceeds: u64,
+    pub fee_amount: u64,
 }
 
-// ============================================================================
-// State
-// ============================================================================
+#[event]
+pub struct AuctionBidClaimed {
+    pub bond: Pubkey,
+    pub bidder: Pubkey,
+    pub quantity: u64,
+    pub clearing_price: u64,
+    pub refund_amount: u64,
+}
 
-#[account]
-#[derive(InitSpace)]
-pub struct Platform {
-    pub authority: Pubkey,
-    pub treasury: Pubkey,
-    pub total_bonds_issued: u64,
-    pub total_volume: u64,
-    pub fee_bps: u64,
-    pub bump: u8,
+#[event]
+pub struct AuctionBidRefunded {
+    pub bond: Pubkey,
+    pub bidder: Pubkey,
+    pub quantity: u64,
+    pub refund_amount: u64,
 }
 
-#[account]
-#[derive(InitSpace)]
-pub struct Bond {
-    pub issuer: Pubkey,
-    pub bond_mint: Pubkey,
-    pub collateral_mint: Pubkey,
-    pub collateral_vault: Pubkey,
-    #[max_len(64)]
-    pub name: String,
-    #[max_len(10)]
-    pub symbol: String,
-    #[max_len(500)]
-    pub description: String,
-    #[max_len(200)]
-    pub image_uri: String,
-    #[max_len(100)]
-    pub website: String,
-    #[max_len(100)]
-    pub twitter: String,
-    #[max_len(100)]
-    pub discord: String,
-    pub principal_amount: u64,
-    pub coupon_rate_bps: u64,
-    pub is_variable_rate: bool,
-    pub payment_frequency: u8, // 1=annual, 2=semi, 4=quarterly, 12=monthly
-    pub maturity_timestamp: i64,
-    pub created_at: i64,
-    pub total_supply: u64,
+#[event]
+pub struct BondCalled {
+    pub bond: Pubkey,
+    pub call_date: i64,
+    pub call_price: u64,
+    pub accrued_coupon: u64,
+    pub amount_funded: u64,
     pub outstanding_supply: u64,
-    pub is_capped: bool,
-    pub collateral_ratio_bps: u64,
-    pub collateral_deposited: u64,
-    pub total_yield_paid: u64,
-    pub last_yield_payment: i64,
-    pub is_active: bool,
-    pub is_matured: bool,
-    pub bump: u8,
 }
 
-#[account]
-#[derive(InitSpace)]
-pub struct HolderPosition {
+#[event]
+pub struct PutTendered {
+    pub bond: Pubkey,
     pub holder: Pubkey,
+    pub quantity: u64,
+    pub window_end: i64,
+}
+
+#[event]
+pub struct PutExercised {
     pub bond: Pubkey,
+    pub holder: Pubkey,
     pub quantity: u64,
-    pub purchase_price: u64,
-    pub purchase_timestamp: i64,
-    pub total_yield_claimed: u64,
-    pub bump: u8,
+    pub put_amount: u64,
 }
 
-#[account]
-#[derive(InitSpace)]
-pub struct Order {
-    pub seller: Pubkey,
+#[event]
+pub struct PutReturned {
     pub bond: Pubkey,
+    pub holder: Pubkey,
     pub quantity: u64,
-    pub price_per_bond: u64,
-    pub created_at: i64,
-    pub is_active: bool,
-    pub bump: u8,
 }
 
-// ============================================================================
-// Args
-// ============================================================================
+#[event]
+pub struct PutDefaulted {
+    pub bond: Pubkey,
+    pub holder: Pubkey,
+    pub quantity: u64,
+    pub put_amount: u64,
+    pub vault_balance: u64,
+    pub defaulted_at: i64,
+}
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct CreateBondArgs {
-    pub name: String,
-    pub symbol: String,
-    pub description: String,
-    pub image_uri: String,
-    pub website: String,
-    pub twitter: String,
-    pub discord: String,
-    pub principal_amount: u64,
-    pub coupon_rate_bps: u64,
-    pub is_variable_rate: bool,
-    pub payment_frequency: u8,
-    pub maturity_timestamp: i64,
-    pub total_supply: u64,
-    pub is_capped: bool,
-    pub collateral_ratio_bps: u64,
+#[event]
+pub struct PrincipalClaimed {
+    pub bond: Pubkey,
+    pub holder: Pubkey,
+    pub amount: u64,
 }
 
-// ============================================================================
-// Events
-// ============================================================================
+#[event]
+pub struct ConversionFunded {
+    pub bond: Pubkey,
+    pub amount: u64,
+    pub total_escrowed: u64,
+}
 
 #[event]
-pub struct PlatformInitialized {
-    pub authority: Pubkey,
-    pub treasury: Pubkey,
+pub struct BondConverted {
+    pub bond: Pubkey,
+    pub holder: Pubkey,
+    pub quantity: u64,
+    pub conversion_amount: u64,
 }
 
 #[event]
-pub struct BondCreated {
+pub struct ConversionReclaimed {
     pub bond: Pubkey,
-    pub issuer: Pubkey,
-    pub name: String,
-    pub principal_amount: u64,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ReferenceRatePublished {
+    pub reference_rate: Pubkey,
+    pub rate_bps: u64,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct CollateralOracleSet {
+    pub collateral_mint: Pubkey,
+    pub price_oracle: Pubkey,
+    pub set_by: Pubkey,
+}
+
+#[event]
+pub struct BondOracleSynced {
+    pub bond: Pubkey,
+    pub price_oracle: Pubkey,
+}
+
+#[event]
+pub struct CouponRateFixed {
+    pub bond: Pubkey,
+    pub period: u32,
+    pub fixing_date: i64,
+    pub reference_rate_bps: u64,
     pub coupon_rate_bps: u64,
-    pub maturity_timestamp: i64,
-    pub total_supply: u64,
 }
 
 #[event]
-pub struct CollateralDeposited {
+pub struct SinkingFundDeposited {
     pub bond: Pubkey,
     pub amount: u64,
-    pub total_collateral: u64,
+    pub total_deposited: u64,
+    pub scheduled_to_date: u64,
 }
 
 #[event]
-pub struct BondPurchased {
+pub struct SinkingDrawCommitted {
     pub bond: Pubkey,
-    pub buyer: Pubkey,
+    pub draw: u32,
+    pub quantity: u64,
+    pub reveal_slot: u64,
+}
+
+#[event]
+pub struct SinkingFundDrawn {
+    pub bond: Pubkey,
+    pub draw: u32,
+    pub quantity: u64,
+    pub eligible_supply: u64,
+    pub price: u64,
+}
+
+#[event]
+pub struct SinkingFundRetired {
+    pub bond: Pubkey,
+    pub holder: Pubkey,
+    pub order: Option<Pubkey>,
     pub quantity: u64,
     pub payment_amount: u64,
-    pub fee_amount: u64,
+}
+
+#[event]
+pub struct SinkingDrawSettled {
+    pub bond: Pubkey,
+    pub holder: Pubkey,
+    pub draw: u32,
+    pub quantity: u64,
+    pub remaining: u64,
+}
+
+#[event]
+pub struct RetireQueuePruned {
+    pub bond: Pubkey,
+    pub pruned: u32,
+    pub head: Pubkey,
+}
+
+#[event]
+pub struct BondAccelerated {
+    pub bond: Pubkey,
+    pub trustee: Pubkey,
+    pub accelerated_at: i64,
+    pub outstanding_debt: u64,
+}
+
+#[event]
+pub struct TrusteeDefaultDeclared {
+    pub bond: Pubkey,
+    pub trustee: Pubkey,
+    pub collateral_deposited: u64,
+    pub outstanding_supply: u64,
+    pub defaulted_at: i64,
+}
+
+#[event]
+pub struct TradingFrozen {
+    pub bond: Pubkey,
+    pub trustee: Pubkey,
+    pub frozen: bool,
+}
+
+#[event]
+pub struct RecoverySwept {
+    pub bond: Pubkey,
+    pub trustee: Pubkey,
+    pub sinking_fund_amount: u64,
+    pub yield_surplus: u64,
+}
+
+#[event]
+pub struct AmendmentProposed {
+    pub bond: Pubkey,
+    pub proposal: u32,
+    pub amendment: Amendment,
+    pub supply_snapshot: u64,
+    pub voting_end: i64,
+}
+
+#[event]
+pub struct VoteCast {
+    pub bond: Pubkey,
+    pub proposal: u32,
+    pub voter: Pubkey,
+    pub weight: u64,
+    pub approve: bool,
+}
+
+#[event]
+pub struct AmendmentFinalized {
+    pub bond: Pubkey,
+    pub proposal: u32,
+    pub amendment: Amendment,
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub supply_snapshot: u64,
+    pub approved: bool,
 }
 
 #[event]
 pub struct YieldDeposited {
     pub bond: Pubkey,
+    pub period: u32,
     pub amount: u64,
+    pub amount_funded: u64,
     pub timestamp: i64,
 }
 
+#[event]
+pub struct CouponRecorded {
+    pub bond: Pubkey,
+    pub period: u32,
+    pub record_date: i64,
+    pub coupon_per_bond: u64,
+    pub principal_per_bond: u64,
+    pub coupon_rate_bps: u64,
+    pub supply_at_record: u64,
+    pub amount_due: u64,
+    pub amount_funded: u64,
+}
+
 #[event]
 pub struct YieldClaimed {
     pub bond: Pubkey,
@@ -1174,6 +7499,50 @@ pub struct YieldClaimed {
     pub amount: u64,
 }
 
+#[event]
+pub struct PrincipalDefaulted {
+    pub bond: Pubkey,
+    pub redemption_date: i64,
+    pub outstanding_debt: u64,
+    pub defaulted_at: i64,
+}
+
+#[event]
+pub struct SinkingFundWithdrawn {
+    pub bond: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct SinkingFundDefaulted {
+    pub bond: Pubkey,
+    pub scheduled: u64,
+    pub deposited: u64,
+    pub defaulted_at: i64,
+}
+
+#[event]
+pub struct BondDefaulted {
+    pub bond: Pubkey,
+    pub period: u32,
+    pub amount_due: u64,
+    pub amount_funded: u64,
+    pub last_yield_payment: i64,
+    pub collateral_deposited: u64,
+    pub defaulted_at: i64,
+}
+
+#[event]
+pub struct RecoveryClaimed {
+    pub bond: Pubkey,
+    pub holder: Pubkey,
+    pub quantity: u64,
+    pub collateral_amount: u64,
+    pub principal_amount: u64,
+    pub yield_amount: u64,
+    pub sinking_fund_amount: u64,
+}
+
 #[event]
 pub struct BondRedeemed {
     pub bond: Pubkey,
@@ -1186,7 +7555,9 @@ pub struct BondRedeemed {
 pub struct BondLiquidated {
     pub bond: Pubkey,
     pub liquidator: Pubkey,
+    pub repay_amount: u64,
     pub collateral_seized: u64,
+    pub remaining_collateral: u64,
 }
 
 #[event]
@@ -1211,6 +7582,14 @@ pub struct OrderCancelled {
     pub order: Pubkey,
 }
 
+#[event]
+pub struct BondTransferred {
+    pub bond: Pubkey,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub quantity: u64,
+}
+
 // ============================================================================
 // Errors
 // ============================================================================
@@ -1253,5 +7632,211 @@ pub enum BtrustError {
     OrderNotActive,
     #[msg("Exceeds order quantity")]
     ExceedsOrderQuantity,
+    #[msg("Fee exceeds maximum allowed")]
+    FeeTooHigh,
+    #[msg("No pending authority")]
+    NoPendingAuthority,
+    #[msg("Address cannot be the default pubkey")]
+    InvalidAddress,
+    #[msg("Platform is paused")]
+    PlatformPaused,
+    #[msg("Bond operation is paused")]
+    BondPaused,
+    #[msg("Invalid pause flags")]
+    InvalidPauseFlags,
+    #[msg("Invalid oracle account")]
+    InvalidOracle,
+    #[msg("Invalid oracle price")]
+    InvalidOraclePrice,
+    #[msg("Oracle price is stale")]
+    StaleOraclePrice,
+    #[msg("Oracle confidence interval too wide")]
+    OracleConfidenceTooWide,
+    #[msg("Insufficient collateral for bond's collateral ratio")]
+    InsufficientCollateral,
+    #[msg("Invalid payment frequency")]
+    InvalidPaymentFrequency,
+    #[msg("Invalid coupon period")]
+    InvalidCouponPeriod,
+    #[msg("Coupon periods must be processed in order")]
+    CouponPeriodOutOfOrder,
+    #[msg("Coupon record date not reached")]
+    CouponRecordDateNotReached,
+    #[msg("Coupon record date passed but not yet recorded")]
+    CouponRecordPending,
+    #[msg("Deposit exceeds coupon amount due")]
+    ExceedsCouponDue,
+    #[msg("Invalid grace period")]
+    InvalidGracePeriod,
+    #[msg("Bond has defaulted")]
+    BondDefaulted,
+    #[msg("No missed coupon")]
+    CouponNotMissed,
+    #[msg("Grace period has not elapsed")]
+    GracePeriodNotElapsed,
+    #[msg("Bond is in recovery")]
+    BondInRecovery,
+    #[msg("Bond is not in recovery")]
+    BondNotInRecovery,
+    #[msg("Invalid payment mint")]
+    InvalidPaymentMint,
+    #[msg("Exceeds outstanding debt")]
+    ExceedsOutstandingDebt,
+    #[msg("Invalid treasury fee account")]
+    InvalidTreasury,
+    #[msg("Invalid subscription window")]
+    InvalidSubscriptionWindow,
+    #[msg("Subscription has not opened")]
+    SubscriptionNotOpen,
+    #[msg("Subscription has closed")]
+    SubscriptionClosed,
+    #[msg("Subscription is still open")]
+    SubscriptionOpen,
+    #[msg("Bond has no subscription window")]
+    NoSubscription,
+    #[msg("Subscription already settled")]
+    SubscriptionSettled,
+    #[msg("Subscription not settled")]
+    SubscriptionNotSettled,
+    #[msg("Soft cap not met")]
+    SoftCapNotMet,
+    #[msg("Soft cap met")]
+    SoftCapMet,
+    #[msg("Nothing to refund")]
+    NothingToRefund,
+    #[msg("Invalid auction parameters")]
+    InvalidAuction,
+    #[msg("Bond is sold by auction")]
+    AuctionBond,
+    #[msg("Bond is not sold by auction")]
+    NotAuctionBond,
+    #[msg("Auction has not opened")]
+    AuctionNotOpen,
+    #[msg("Auction has closed")]
+    AuctionClosed,
+    #[msg("Auction is still open")]
+    AuctionOpen,
+    #[msg("Auction already settled")]
+    AuctionSettled,
+    #[msg("Auction not settled")]
+    AuctionNotSettled,
+    #[msg("Auction settlement window is still open")]
+    AuctionSettlementOpen,
+    #[msg("Auction settlement window has passed")]
+    AuctionExpired,
+    #[msg("Invalid call schedule")]
+    InvalidCallSchedule,
+    #[msg("Bond is not callable")]
+    NotCallable,
+    #[msg("Bond has been called")]
+    BondCalled,
+    #[msg("Primary sale still open")]
+    PrimarySaleOpen,
+    #[msg("Invalid call date")]
+    InvalidCallDate,
+    #[msg("Call date is inside the notice period")]
+    CallNoticeTooShort,
+    #[msg("Invalid put schedule")]
+    InvalidPutSchedule,
+    #[msg("No put window is open")]
+    PutWindowClosed,
+    #[msg("Put window has not closed")]
+    PutWindowOpen,
+    #[msg("Put payment pending")]
+    PutPaymentPending,
+    #[msg("Claim the previous put tender first")]
+    PutPending,
+    #[msg("Invalid amortization schedule")]
+    InvalidAmortization,
+    #[msg("No principal to claim")]
+    NoPrincipalToClaim,
+    #[msg("Invalid discount yield")]
+    InvalidDiscountYield,
+    #[msg("Invalid conversion terms")]
+    InvalidConversion,
+    #[msg("Bond is not convertible")]
+    NotConvertible,
+    #[msg("Invalid conversion mint")]
+    InvalidConversionMint,
+    #[msg("Conversion window is closed")]
+    ConversionWindowClosed,
+    #[msg("Conversion window is still open")]
+    ConversionWindowOpen,
+    #[msg("Insufficient conversion tokens escrowed")]
+    InsufficientConversionBalance,
+    #[msg("Nothing to reclaim")]
+    NothingToReclaim,
+    #[msg("Invalid floating rate terms")]
+    InvalidRateTerms,
+    #[msg("Invalid reference rate account")]
+    InvalidReferenceRate,
+    #[msg("Bond does not pay a floating rate")]
+    NotVariableRate,
+    #[msg("Coupon fixing date not reached")]
+    FixingDateNotReached,
+    #[msg("Reference rate was not published shortly before the fixing date")]
+    StaleReferenceRate,
+    #[msg("Coupon rate already fixed for this period")]
+    CouponRateAlreadyFixed,
+    #[msg("Order is priced above face value")]
+    OrderAbovePar,
+    #[msg("Insufficient unreserved sinking fund balance")]
+    InsufficientSinkingFund,
+    #[msg("Nothing to redeem")]
+    NothingToRedeem,
+    #[msg("Invalid amendment")]
+    InvalidAmendment,
+    #[msg("Voting has closed")]
+    VotingClosed,
+    #[msg("Voting is still open")]
+    VotingOpen,
+    #[msg("Proposal already finalized")]
+    ProposalFinalized,
+    #[msg("Position changed too often since the proposal snapshot")]
+    PositionChangedSinceSnapshot,
+    #[msg("No voting power")]
+    NoVotingPower,
+    #[msg("Bond already accelerated")]
+    BondAccelerated,
+    #[msg("Bonds are outstanding")]
+    BondsOutstanding,
+    #[msg("Mock price feeds are disabled in this build")]
+    MockOracleDisabled,
+    #[msg("No price feed registered for the collateral mint")]
+    CollateralOracleNotRegistered,
+    #[msg("Bond already uses the registered price feed")]
+    OracleUnchanged,
+    #[msg("Invalid recipient")]
+    InvalidRecipient,
+    #[msg("Order is out of place in the retire queue")]
+    InvalidRetireQueue,
+    #[msg("A cheaper sell order is available")]
+    NotLowestPricedOrder,
+    #[msg("The previous sinking fund draw is still being allocated")]
+    SinkingDrawInProgress,
+    #[msg("Position already settled for the latest sinking fund draw")]
+    SinkingDrawSettled,
+    #[msg("No sinking fund draw is waiting to be revealed")]
+    NoSinkingDrawCommitted,
+    #[msg("The sinking fund draw's seed slot has not passed yet")]
+    SinkingDrawNotRevealable,
+    #[msg("The sinking fund draw has not been revealed yet")]
+    SinkingDrawNotRevealed,
+    #[msg("Sinking fund deposits are on schedule")]
+    SinkingFundOnSchedule,
+    #[msg("Bond is not in breach of its covenants")]
+    NoCovenantBreach,
+    #[msg("Principal is not overdue")]
+    PrincipalNotOverdue,
+    #[msg("Trading is frozen by the trustee")]
+    TradingFrozen,
+    #[msg("Holders have already started claiming recovery")]
+    RecoveryStarted,
+    #[msg("Nothing to sweep into recovery")]
+    NothingToSweep,
+    #[msg("Bonds of this offering were already issued")]
+    BondsIssued,
+    #[msg("Every vault of the bond must be passed in")]
+    VaultMissing,
 }
 
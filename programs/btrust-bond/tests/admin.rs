@@ -0,0 +1,58 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use btrust_bond::BtrustError;
+use common::*;
+use solana_sdk::signature::Signer;
+
+#[tokio::test]
+async fn fee_is_bounded_and_authority_gated() {
+    let mut env = TestEnv::new().await;
+    let authority = env.authority();
+    let outsider = env.create_user(0).await;
+    
+    env.set_fee_bps(&authority, 500).await.unwrap();
+    assert_eq!(env.platform().await.fee_bps, 500);
+    assert_program_error(env.set_fee_bps(&authority, 501).await, BtrustError::FeeTooHigh);
+    assert_program_error(env.set_fee_bps(&outsider.keypair, 10).await, BtrustError::Unauthorized);
+    assert_eq!(env.platform().await.fee_bps, 500);
+}
+
+#[tokio::test]
+async fn treasury_cannot_be_cleared() {
+    let mut env = TestEnv::new().await;
+    let authority = env.authority();
+    let new_treasury = Pubkey::new_unique();
+    
+    assert_program_error(env.set_treasury(&authority, Pubkey::default()).await, BtrustError::InvalidAddress);
+    env.set_treasury(&authority, new_treasury).await.unwrap();
+    assert_eq!(env.platform().await.treasury, new_treasury);
+}
+
+#[tokio::test]
+async fn authority_moves_only_when_the_proposed_key_accepts() {
+    let mut env = TestEnv::new().await;
+    let authority = env.authority();
+    let successor = env.create_user(0).await;
+    let outsider = env.create_user(0).await;
+    
+    assert_program_error(env.accept_authority(&successor.keypair).await, BtrustError::Unauthorized);
+    assert_program_error(
+        env.propose_authority(&authority, Pubkey::default()).await,
+        BtrustError::InvalidAddress,
+    );
+    
+    env.propose_authority(&authority, successor.pubkey()).await.unwrap();
+    assert_eq!(env.platform().await.authority, authority.pubkey());
+    assert_program_error(env.accept_authority(&outsider.keypair).await, BtrustError::Unauthorized);
+    
+    env.accept_authority(&successor.keypair).await.unwrap();
+    let platform = env.platform().await;
+    assert_eq!(platform.authority, successor.pubkey());
+    assert_eq!(platform.pending_authority, Pubkey::default());
+    
+    // The old authority is locked out and the handoff cannot be replayed
+    assert_program_error(env.set_fee_bps(&authority, 10).await, BtrustError::Unauthorized);
+    env.set_fee_bps(&successor.keypair, 10).await.unwrap();
+    assert_program_error(env.accept_authority(&successor.keypair).await, BtrustError::Unauthorized);
+}
@@ -0,0 +1,52 @@
+mod common;
+
+use btrust_bond::BtrustError;
+use common::*;
+
+const INSTALLMENT: u64 = FACE_VALUE / 4;
+
+#[tokio::test]
+async fn installments_reduce_face_value_and_later_coupons() {
+    let mut env = TestEnv::new().await;
+    let mut args = env.bond_args();
+    args.amortization_bps = 2_500;
+    let bond = env.create_bond(args).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    
+    env.purchase(&bond, &alice, 10).await.unwrap();
+    let created_at = env.bond(&bond).await.created_at;
+    
+    env.warp_to(created_at + QUARTER + 1).await;
+    env.record_coupon(&bond, 0).await.unwrap();
+    env.deposit_yield(&bond, 0, 10 * (COUPON_PER_BOND + INSTALLMENT)).await.unwrap();
+    
+    // Interest and principal are claimed separately
+    let before = env.token_balance(&alice.payment).await;
+    env.claim_yield(&bond, &alice).await.unwrap();
+    assert_eq!(env.token_balance(&alice.payment).await - before, 10 * COUPON_PER_BOND);
+    env.claim_principal(&bond, &alice).await.unwrap();
+    assert_eq!(env.token_balance(&alice.payment).await - before, 10 * (COUPON_PER_BOND + INSTALLMENT));
+    assert_program_error(env.claim_principal(&bond, &alice).await, BtrustError::NoPrincipalToClaim);
+    
+    let state = env.bond(&bond).await;
+    assert_eq!(state.face_value(), FACE_VALUE - INSTALLMENT);
+    assert_eq!(state.outstanding_debt().unwrap(), 10 * (FACE_VALUE - INSTALLMENT));
+    
+    // The second coupon accrues on the amortized face value
+    env.warp_to(created_at + 2 * QUARTER + 1).await;
+    env.record_coupon(&bond, 1).await.unwrap();
+    let coupon = COUPON_PER_BOND * 3 / 4;
+    env.deposit_yield(&bond, 1, 10 * (coupon + INSTALLMENT)).await.unwrap();
+    let before = env.token_balance(&alice.payment).await;
+    env.claim_yield(&bond, &alice).await.unwrap();
+    assert_eq!(env.token_balance(&alice.payment).await - before, 10 * coupon);
+}
+
+#[tokio::test]
+async fn amortization_is_capped_at_par() {
+    let mut env = TestEnv::new().await;
+    let issuer = env.create_user(0).await;
+    let mut args = env.bond_args();
+    args.amortization_bps = 10_001;
+    assert_program_error(env.try_create_bond(args, &issuer).await, BtrustError::InvalidAmortization);
+}
@@ -0,0 +1,128 @@
+mod common;
+
+use btrust_bond::{BtrustError, CreateBondArgs};
+use common::*;
+
+const AUCTION_LENGTH: i64 = 10 * 86_400;
+const START_PRICE: u64 = FACE_VALUE * 12 / 10;
+const FLOOR_PRICE: u64 = FACE_VALUE * 9 / 10;
+
+fn auction_args(env: &TestEnv, mut args: CreateBondArgs, total_supply: u64) -> CreateBondArgs {
+    args.total_supply = total_supply;
+    args.auction_start = env.now;
+    args.auction_end = env.now + AUCTION_LENGTH;
+    args.auction_start_price = START_PRICE;
+    args.auction_floor_price = FLOOR_PRICE;
+    args
+}
+
+#[tokio::test]
+async fn bidders_settle_at_the_sell_out_price() {
+    let mut env = TestEnv::new().await;
+    let args = auction_args(&env, env.bond_args(), 10);
+    let bond = env.create_bond(args).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    let bob = env.create_user(100 * FACE_VALUE).await;
+    
+    env.place_bid(&bond, &alice, 4).await.unwrap();
+    assert_eq!(env.token_balance(&alice.payment).await, 100 * FACE_VALUE - 4 * START_PRICE);
+    
+    // Halfway through the window the price has decayed halfway to the floor, and
+    // Bob's oversized bid is trimmed to the remaining supply
+    env.warp_to(env.now + AUCTION_LENGTH / 2).await;
+    let clearing_price = (START_PRICE + FLOOR_PRICE) / 2;
+    env.place_bid(&bond, &bob, 10).await.unwrap();
+    assert_eq!(env.token_balance(&bob.payment).await, 100 * FACE_VALUE - 6 * clearing_price);
+    assert_program_error(env.place_bid(&bond, &bob, 1).await, BtrustError::AuctionClosed);
+    assert_program_error(env.claim_auction_bid(&bond, &alice).await, BtrustError::AuctionNotSettled);
+    
+    let issuer_before = env.token_balance(&bond.issuer.payment).await;
+    env.settle_auction(&bond).await.unwrap();
+    let proceeds = 10 * clearing_price;
+    let fee = proceeds * 50 / 10_000;
+    assert_eq!(env.token_balance(&bond.issuer.payment).await - issuer_before, proceeds - fee);
+    assert_program_error(env.settle_auction(&bond).await, BtrustError::AuctionSettled);
+    
+    // Claims return excess locked funds and stay open while issuance is paused
+    let authority = env.authority();
+    env.set_platform_paused(&authority, true).await.unwrap();
+    env.claim_auction_bid(&bond, &alice).await.unwrap();
+    assert_eq!(env.token_balance(&alice.payment).await, 100 * FACE_VALUE - 4 * clearing_price);
+    assert_eq!(env.token_balance(&bond.bond_account(&alice.pubkey())).await, 4);
+    env.claim_auction_bid(&bond, &bob).await.unwrap();
+    assert_eq!(env.position(&bond, &bob).await.quantity, 6);
+    assert_eq!(env.bond(&bond).await.auction_unclaimed, 0);
+    assert!(env.claim_auction_bid(&bond, &bob).await.is_err());
+}
+
+#[tokio::test]
+async fn unsold_auctions_clear_at_the_floor() {
+    let mut env = TestEnv::new().await;
+    let args = auction_args(&env, env.bond_args(), 10);
+    let bond = env.create_bond(args).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    
+    env.place_bid(&bond, &alice, 3).await.unwrap();
+    assert_program_error(env.settle_auction(&bond).await, BtrustError::AuctionOpen);
+    
+    env.warp_to(env.now + AUCTION_LENGTH).await;
+    assert_program_error(env.place_bid(&bond, &alice, 1).await, BtrustError::AuctionClosed);
+    env.settle_auction(&bond).await.unwrap();
+    assert_eq!(env.bond(&bond).await.auction_clearing_price, FLOOR_PRICE);
+    
+    env.claim_auction_bid(&bond, &alice).await.unwrap();
+    assert_eq!(env.token_balance(&alice.payment).await, 100 * FACE_VALUE - 3 * FLOOR_PRICE);
+    assert_eq!(env.bond(&bond).await.outstanding_supply, 3);
+}
+
+#[tokio::test]
+async fn pending_bids_count_against_collateral() {
+    let mut env = TestEnv::new().await;
+    let feed = env.create_price_feed(200_000_000, -8).await;
+    env.set_collateral_oracle(&feed).await.unwrap();
+    let args = auction_args(&env, env.secured_bond_args(), 20);
+    // 7.5 collateral tokens cover 10 bonds at face value
+    let bond = env.create_secured_bond(args, 7_500_000_000).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    
+    env.place_bid(&bond, &alice, 10).await.unwrap();
+    assert_program_error(env.place_bid(&bond, &alice, 1).await, BtrustError::InsufficientCollateral);
+    assert_program_error(env.withdraw_collateral(&bond, 1).await, BtrustError::InsufficientCollateral);
+    
+    // A price drop before settlement blocks issuing the bids
+    env.warp_to(env.now + AUCTION_LENGTH).await;
+    let now = env.now;
+    env.set_price(&feed, 100_000_000, -8, 0, now).await;
+    assert_program_error(env.settle_auction(&bond).await, BtrustError::InsufficientCollateral);
+    
+    env.deposit_collateral(&bond, 7_500_000_000).await.unwrap();
+    env.settle_auction(&bond).await.unwrap();
+}
+
+#[tokio::test]
+async fn bidders_withdraw_from_auctions_left_unsettled() {
+    let mut env = TestEnv::new().await;
+    let feed = env.create_price_feed(200_000_000, -8).await;
+    env.set_collateral_oracle(&feed).await.unwrap();
+    let args = auction_args(&env, env.secured_bond_args(), 20);
+    let bond = env.create_secured_bond(args, 7_500_000_000).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    
+    env.place_bid(&bond, &alice, 10).await.unwrap();
+    env.warp_to(env.now + AUCTION_LENGTH).await;
+    let now = env.now;
+    env.set_price(&feed, 100_000_000, -8, 0, now).await;
+    assert_program_error(env.settle_auction(&bond).await, BtrustError::InsufficientCollateral);
+    assert_program_error(env.refund_bid(&bond, &alice).await, BtrustError::AuctionSettlementOpen);
+    
+    // A week after the auction ends it can no longer settle, and bids come back
+    env.warp_to(env.now + 7 * 86_400).await;
+    let now = env.now;
+    env.set_price(&feed, 200_000_000, -8, 0, now).await;
+    assert_program_error(env.settle_auction(&bond).await, BtrustError::AuctionExpired);
+    env.refund_bid(&bond, &alice).await.unwrap();
+    assert_eq!(env.token_balance(&alice.payment).await, 100 * FACE_VALUE);
+    assert_eq!(env.bond(&bond).await.auction_bid_quantity, 0);
+    
+    env.cancel_offering(&bond, false).await.unwrap();
+}
@@ -0,0 +1,61 @@
+mod common;
+
+use btrust_bond::BtrustError;
+use common::*;
+
+const NOTICE: i64 = 30 * 86_400;
+
+#[tokio::test]
+async fn called_bonds_redeem_at_the_call_price_plus_accrued_coupon() {
+    let mut env = TestEnv::new().await;
+    let mut args = env.bond_args();
+    args.first_call_date = env.now + 2 * QUARTER;
+    args.call_schedule_bps[0] = 10_200;
+    args.call_schedule_bps[1] = 10_100;
+    args.call_notice_period = NOTICE;
+    let bond = env.create_bond(args).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    
+    env.purchase(&bond, &alice, 10).await.unwrap();
+    let created_at = env.bond(&bond).await.created_at;
+    let first_call_date = created_at + 2 * QUARTER;
+    assert_program_error(env.call_bond(&bond, first_call_date - 1).await, BtrustError::InvalidCallDate);
+    
+    env.warp_to(first_call_date - NOTICE / 3).await;
+    assert_program_error(env.call_bond(&bond, first_call_date).await, BtrustError::CallNoticeTooShort);
+    
+    // A call in the first step pays 102% of par plus the coupon accrued since the last coupon date
+    let call_date = first_call_date + NOTICE;
+    let issuer_before = env.token_balance(&bond.issuer.payment).await;
+    env.call_bond(&bond, call_date).await.unwrap();
+    let accrued = (COUPON_PER_BOND as i64 * NOTICE / QUARTER) as u64;
+    let call_price = FACE_VALUE * 10_200 / 10_000 + accrued;
+    let state = env.bond(&bond).await;
+    assert_eq!(state.call_price, call_price);
+    assert_eq!(state.coupon_periods, 2);
+    assert_eq!(issuer_before - env.token_balance(&bond.issuer.payment).await, 10 * call_price);
+    assert_program_error(env.call_bond(&bond, call_date).await, BtrustError::BondCalled);
+    assert_program_error(env.redeem_bond(&bond, &alice, 10).await, BtrustError::BondNotMatured);
+    
+    env.warp_to(call_date).await;
+    env.record_coupon(&bond, 0).await.unwrap();
+    env.record_coupon(&bond, 1).await.unwrap();
+    let before = env.token_balance(&alice.payment).await;
+    env.redeem_bond(&bond, &alice, 10).await.unwrap();
+    assert_eq!(env.token_balance(&alice.payment).await - before, 10 * call_price);
+}
+
+#[tokio::test]
+async fn bonds_without_call_terms_cannot_be_called() {
+    let mut env = TestEnv::new().await;
+    let args = env.bond_args();
+    let bond = env.create_bond(args).await;
+    let call_date = env.now + QUARTER;
+    assert_program_error(env.call_bond(&bond, call_date).await, BtrustError::NotCallable);
+    
+    let issuer = env.create_user(0).await;
+    let mut args = env.bond_args();
+    args.first_call_date = env.now + QUARTER;
+    args.call_schedule_bps[0] = 9_900;
+    assert_program_error(env.try_create_bond(args, &issuer).await, BtrustError::InvalidCallSchedule);
+}
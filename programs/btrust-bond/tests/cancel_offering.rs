@@ -0,0 +1,54 @@
+mod common;
+
+use btrust_bond::BtrustError;
+use common::*;
+
+#[tokio::test]
+async fn cancelling_closes_every_vault_and_refunds_the_issuer() {
+    let mut env = TestEnv::new().await;
+    let bond = env.create_bond(env.bond_args()).await;
+    let issuer_before = env.token_balance(&bond.issuer.payment).await;
+    env.fund_sinking_fund(&bond, FACE_VALUE).await.unwrap();
+    
+    assert_program_error(env.cancel_offering(&bond, false).await, BtrustError::VaultMissing);
+    env.cancel_offering(&bond, true).await.unwrap();
+    
+    assert_eq!(env.token_balance(&bond.issuer.payment).await, issuer_before);
+    for address in [bond.bond, bond.vault(b"sinking_fund"), bond.vault(b"yield_vault"), bond.collateral_vault] {
+        assert!(env.ctx.banks_client.get_account(address).await.unwrap().is_none());
+    }
+}
+
+#[tokio::test]
+async fn offerings_that_issued_bonds_cannot_be_cancelled() {
+    let mut env = TestEnv::new().await;
+    let bond = env.create_bond(env.bond_args()).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    env.purchase(&bond, &alice, 2).await.unwrap();
+    
+    // Retire every bond so none is outstanding
+    env.fund_sinking_fund(&bond, 2 * FACE_VALUE).await.unwrap();
+    let order = env.create_sell_order(&bond, &alice, 2, FACE_VALUE * 9 / 10).await;
+    env.retire_from_sinking_fund(&bond, &order, 2).await.unwrap();
+    assert_eq!(env.bond(&bond).await.outstanding_supply, 0);
+    
+    assert_program_error(env.cancel_offering(&bond, true).await, BtrustError::BondsIssued);
+}
+
+#[tokio::test]
+async fn failed_subscriptions_can_be_cancelled() {
+    let mut env = TestEnv::new().await;
+    let mut args = env.bond_args();
+    args.subscription_start = env.now;
+    args.subscription_end = env.now + 86_400;
+    args.soft_cap = 10 * FACE_VALUE;
+    let bond = env.create_bond(args).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    env.purchase(&bond, &alice, 3).await.unwrap();
+    assert_program_error(env.cancel_offering(&bond, false).await, BtrustError::BondsOutstanding);
+    
+    env.warp_to(env.now + 86_400).await;
+    env.refund_subscription(&bond, &alice).await.unwrap();
+    env.cancel_offering(&bond, false).await.unwrap();
+    assert!(env.ctx.banks_client.get_account(bond.bond).await.unwrap().is_none());
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use btrust_bond::BtrustError;
+use common::*;
+
+const PRICE: i64 = 200_000_000;
+const EXPO: i32 = -8;
+/// One collateral token (9 decimals), worth two payment tokens at `PRICE`
+const TOKEN: u64 = 1_000_000_000;
+
+async fn secured_bond(env: &mut TestEnv, collateral: u64) -> TestBond {
+    let feed = env.create_price_feed(PRICE, EXPO).await;
+    env.set_collateral_oracle(&feed).await.unwrap();
+    let args = env.secured_bond_args();
+    env.create_secured_bond(args, collateral).await
+}
+
+#[tokio::test]
+async fn issuer_withdraws_only_collateral_above_the_ratio() {
+    let mut env = TestEnv::new().await;
+    let bond = secured_bond(&mut env, 10 * TOKEN).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    
+    env.purchase(&bond, &alice, 10).await.unwrap();
+    assert_program_error(env.withdraw_collateral(&bond, 2_500_000_001).await, BtrustError::InsufficientCollateral);
+    env.withdraw_collateral(&bond, 2_500_000_000).await.unwrap();
+    assert_eq!(env.bond(&bond).await.collateral_deposited, 7_500_000_000);
+    
+    // Escrowed principal no longer needs collateral behind it
+    env.fund_redemption(&bond, 5 * FACE_VALUE).await.unwrap();
+    env.withdraw_collateral(&bond, 3_750_000_000).await.unwrap();
+    assert_program_error(env.withdraw_collateral(&bond, 1).await, BtrustError::InsufficientCollateral);
+    
+    // A falling price locks the collateral in
+    let feed = env.price_oracle;
+    let now = env.now;
+    env.set_price(&feed, PRICE * 2, EXPO, 0, now).await;
+    env.withdraw_collateral(&bond, TOKEN).await.unwrap();
+    env.set_price(&feed, PRICE / 2, EXPO, 0, now).await;
+    assert_program_error(env.withdraw_collateral(&bond, 1).await, BtrustError::InsufficientCollateral);
+}
+
+#[tokio::test]
+async fn secured_sales_stop_at_the_collateral_ratio() {
+    let mut env = TestEnv::new().await;
+    let bond = secured_bond(&mut env, 3 * TOKEN).await;
+    let args = env.secured_bond_args();
+    let empty = env.create_bond(args).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    
+    assert_program_error(env.purchase(&empty, &alice, 1).await, BtrustError::InsufficientCollateral);
+    env.purchase(&bond, &alice, 4).await.unwrap();
+    assert_program_error(env.purchase(&bond, &alice, 1).await, BtrustError::InsufficientCollateral);
+    
+    env.deposit_collateral(&bond, 3 * TOKEN).await.unwrap();
+    env.purchase(&bond, &alice, 4).await.unwrap();
+    assert_eq!(env.bond(&bond).await.outstanding_supply, 8);
+}
+
+#[tokio::test]
+async fn unsecured_bonds_opt_out_of_collateral() {
+    let mut env = TestEnv::new().await;
+    let args = env.bond_args();
+    let bond = env.create_bond(args).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    
+    env.purchase(&bond, &alice, 10).await.unwrap();
+    env.deposit_collateral(&bond, TOKEN).await.unwrap();
+    env.withdraw_collateral(&bond, TOKEN).await.unwrap();
+    assert_eq!(env.bond(&bond).await.collateral_deposited, 0);
+    
+    // Secured bonds need a registered feed for their collateral mint
+    let issuer = env.create_user(0).await;
+    let args = env.secured_bond_args();
+    assert_program_error(env.try_create_bond(args, &issuer).await, BtrustError::CollateralOracleNotRegistered);
+}
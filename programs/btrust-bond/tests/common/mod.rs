@@ -0,0 +1,1419 @@
+#![allow(dead_code)]
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{Instruction, InstructionError};
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::{program_pack::Pack, system_instruction, system_program, sysvar};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::{self, get_associated_token_address};
+use anchor_spl::token::spl_token;
+use btrust_bond::{Amendment, Bond, BtrustError, CreateBondArgs, HolderPosition, Order, Platform};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::account::Account as SolanaAccount;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::{Transaction, TransactionError};
+
+pub const FACE_VALUE: u64 = 1_000_000;
+pub const COUPON_RATE_BPS: u64 = 1_000;
+pub const PAYMENT_FREQUENCY: u8 = 4;
+/// Quarterly coupon on one bond at `COUPON_RATE_BPS`
+pub const COUPON_PER_BOND: u64 = FACE_VALUE * COUPON_RATE_BPS / 10_000 / PAYMENT_FREQUENCY as u64;
+const MAX_CALL_SCHEDULE_STEPS: usize = 8;
+const MAX_PUT_WINDOWS: usize = 8;
+pub const QUARTER: i64 = 31_536_000 / PAYMENT_FREQUENCY as i64;
+const PYTH_PRICE_ACCOUNT_LEN: usize = 240;
+
+fn entry(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    // The program-test processor hands out shorter lifetimes than `entry` expects
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    btrust_bond::entry(program_id, accounts, data)
+}
+
+pub struct User {
+    pub keypair: Keypair,
+    pub payment: Pubkey,
+}
+
+impl User {
+    pub fn pubkey(&self) -> Pubkey {
+        self.keypair.pubkey()
+    }
+}
+
+pub struct TestBond {
+    pub bond: Pubkey,
+    pub bond_mint: Pubkey,
+    pub collateral_vault: Pubkey,
+    pub issuer: User,
+}
+
+impl TestBond {
+    pub fn vault(&self, name: &[u8]) -> Pubkey {
+        Pubkey::find_program_address(&[name, self.bond.as_ref()], &btrust_bond::ID).0
+    }
+
+    pub fn position(&self, holder: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"position", self.bond.as_ref(), holder.as_ref()],
+            &btrust_bond::ID,
+        )
+        .0
+    }
+
+    pub fn coupon_period(&self, period: u32) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"coupon_period", self.bond.as_ref(), &period.to_le_bytes()],
+            &btrust_bond::ID,
+        )
+        .0
+    }
+
+    pub fn bond_account(&self, holder: &Pubkey) -> Pubkey {
+        get_associated_token_address(holder, &self.bond_mint)
+    }
+
+    pub fn proposal(&self, index: u32) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"proposal", self.bond.as_ref(), &index.to_le_bytes()],
+            &btrust_bond::ID,
+        )
+        .0
+    }
+}
+
+pub struct TestOrder {
+    pub order: Pubkey,
+    pub escrow: Pubkey,
+    pub seller: Pubkey,
+    pub seller_payment: Pubkey,
+}
+
+pub struct TestEnv {
+    pub ctx: ProgramTestContext,
+    pub now: i64,
+    pub slot: u64,
+    pub platform: Pubkey,
+    pub payment_mint: Pubkey,
+    pub collateral_mint: Pubkey,
+    pub treasury_payment: Pubkey,
+    pub price_oracle: Pubkey,
+}
+
+impl TestEnv {
+    pub async fn new() -> Self {
+        let program_test = ProgramTest::new("btrust_bond", btrust_bond::ID, processor!(entry));
+        let mut ctx = program_test.start_with_context().await;
+        let clock: Clock = ctx.banks_client.get_sysvar().await.unwrap();
+        let platform = Pubkey::find_program_address(&[b"platform"], &btrust_bond::ID).0;
+        
+        let mut env = TestEnv {
+            ctx,
+            now: clock.unix_timestamp,
+            slot: clock.slot,
+            platform,
+            payment_mint: Pubkey::default(),
+            collateral_mint: Pubkey::default(),
+            treasury_payment: Pubkey::default(),
+            price_oracle: Pubkey::default(),
+        };
+        env.payment_mint = env.create_mint(6).await;
+        env.collateral_mint = env.create_mint(9).await;
+        
+        let treasury = Keypair::new();
+        let payment_mint = env.payment_mint;
+        env.treasury_payment = env.create_token_account(&treasury.pubkey(), &payment_mint).await;
+        
+        let authority = env.ctx.payer.pubkey();
+        env.send(
+            &[program_ix(
+                btrust_bond::accounts::InitializePlatform {
+                    authority,
+                    platform,
+                    treasury: treasury.pubkey(),
+                    system_program: system_program::ID,
+                },
+                btrust_bond::instruction::InitializePlatform {},
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+        
+        env
+    }
+    
+    /// Send `ixs` paid for by the test payer, on a fresh slot so repeated
+    /// identical transactions are not deduplicated
+    pub async fn send(&mut self, ixs: &[Instruction], signers: &[&Keypair]) -> std::result::Result<(), BanksClientError> {
+        self.slot += 1;
+        self.ctx.warp_to_slot(self.slot).unwrap();
+        self.set_clock();
+        
+        let payer = self.ctx.payer.insecure_clone();
+        let mut all_signers = vec![&payer];
+        all_signers.extend(signers.iter().copied().filter(|signer| signer.pubkey() != payer.pubkey()));
+        let blockhash = self.ctx.banks_client.get_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(ixs, Some(&payer.pubkey()), &all_signers, blockhash);
+        self.ctx.banks_client.process_transaction(tx).await
+    }
+    
+    pub async fn warp_to(&mut self, unix_timestamp: i64) {
+        self.now = unix_timestamp;
+        self.set_clock();
+    }
+    
+    fn set_clock(&mut self) {
+        let clock = Clock {
+            slot: self.slot,
+            unix_timestamp: self.now,
+            ..Clock::default()
+        };
+        self.ctx.set_sysvar(&clock);
+    }
+    
+    pub async fn create_mint(&mut self, decimals: u8) -> Pubkey {
+        let mint = Keypair::new();
+        let payer = self.ctx.payer.pubkey();
+        let rent = self.ctx.banks_client.get_rent().await.unwrap();
+        self.send(
+            &[
+                system_instruction::create_account(
+                    &payer,
+                    &mint.pubkey(),
+                    rent.minimum_balance(spl_token::state::Mint::LEN),
+                    spl_token::state::Mint::LEN as u64,
+                    &spl_token::ID,
+                ),
+                spl_token::instruction::initialize_mint(&spl_token::ID, &mint.pubkey(), &payer, None, decimals)
+                    .unwrap(),
+            ],
+            &[&mint],
+        )
+        .await
+        .unwrap();
+        mint.pubkey()
+    }
+    
+    pub async fn create_token_account(&mut self, owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+        let payer = self.ctx.payer.pubkey();
+        self.send(
+            &[spl_associated_token_account_create(&payer, owner, mint)],
+            &[],
+        )
+        .await
+        .unwrap();
+        get_associated_token_address(owner, mint)
+    }
+    
+    pub async fn mint_to(&mut self, mint: &Pubkey, account: &Pubkey, amount: u64) {
+        let payer = self.ctx.payer.pubkey();
+        self.send(
+            &[spl_token::instruction::mint_to(&spl_token::ID, mint, account, &payer, &[], amount).unwrap()],
+            &[],
+        )
+        .await
+        .unwrap();
+    }
+    
+    /// A funded wallet with `payment_amount` of the payment token
+    pub async fn create_user(&mut self, payment_amount: u64) -> User {
+        let keypair = Keypair::new();
+        let payer = self.ctx.payer.pubkey();
+        self.send(
+            &[system_instruction::transfer(&payer, &keypair.pubkey(), 10_000_000_000)],
+            &[],
+        )
+        .await
+        .unwrap();
+        let payment_mint = self.payment_mint;
+        let payment = self.create_token_account(&keypair.pubkey(), &payment_mint).await;
+        if payment_amount > 0 {
+            self.mint_to(&payment_mint, &payment, payment_amount).await;
+        }
+        User { keypair, payment }
+    }
+    
+    /// The platform authority, which starts out as the test payer
+    pub fn authority(&self) -> Keypair {
+        self.ctx.payer.insecure_clone()
+    }
+    
+    pub async fn set_fee_bps(&mut self, authority: &Keypair, fee_bps: u64) -> std::result::Result<(), BanksClientError> {
+        let ix = program_ix(
+            btrust_bond::accounts::UpdatePlatform {
+                authority: authority.pubkey(),
+                platform: self.platform,
+            },
+            btrust_bond::instruction::SetFeeBps { fee_bps },
+        );
+        self.send(&[ix], &[authority]).await
+    }
+    
+    pub async fn set_treasury(&mut self, authority: &Keypair, new_treasury: Pubkey) -> std::result::Result<(), BanksClientError> {
+        let ix = program_ix(
+            btrust_bond::accounts::SetTreasury {
+                authority: authority.pubkey(),
+                platform: self.platform,
+                new_treasury,
+            },
+            btrust_bond::instruction::SetTreasury {},
+        );
+        self.send(&[ix], &[authority]).await
+    }
+    
+    pub async fn propose_authority(&mut self, authority: &Keypair, new_authority: Pubkey) -> std::result::Result<(), BanksClientError> {
+        let ix = program_ix(
+            btrust_bond::accounts::UpdatePlatform {
+                authority: authority.pubkey(),
+                platform: self.platform,
+            },
+            btrust_bond::instruction::ProposeAuthority { new_authority },
+        );
+        self.send(&[ix], &[authority]).await
+    }
+    
+    pub async fn accept_authority(&mut self, pending_authority: &Keypair) -> std::result::Result<(), BanksClientError> {
+        let ix = program_ix(
+            btrust_bond::accounts::AcceptAuthority {
+                pending_authority: pending_authority.pubkey(),
+                platform: self.platform,
+            },
+            btrust_bond::instruction::AcceptAuthority {},
+        );
+        self.send(&[ix], &[pending_authority]).await
+    }
+    
+    pub async fn set_guardian(&mut self, guardian: Pubkey) -> std::result::Result<(), BanksClientError> {
+        let authority = self.authority();
+        let ix = program_ix(
+            btrust_bond::accounts::UpdatePlatform {
+                authority: authority.pubkey(),
+                platform: self.platform,
+            },
+            btrust_bond::instruction::SetGuardian { guardian },
+        );
+        self.send(&[ix], &[]).await
+    }
+    
+    pub async fn set_platform_paused(&mut self, admin: &Keypair, paused: bool) -> std::result::Result<(), BanksClientError> {
+        let ix = program_ix(
+            btrust_bond::accounts::SetPlatformPause {
+                admin: admin.pubkey(),
+                platform: self.platform,
+            },
+            btrust_bond::instruction::SetPlatformPaused { paused },
+        );
+        self.send(&[ix], &[admin]).await
+    }
+    
+    pub async fn set_bond_pause_flags(&mut self, admin: &Keypair, bond: &TestBond, pause_flags: u8) -> std::result::Result<(), BanksClientError> {
+        let ix = program_ix(
+            btrust_bond::accounts::SetBondPause {
+                admin: admin.pubkey(),
+                platform: self.platform,
+                bond: bond.bond,
+            },
+            btrust_bond::instruction::SetBondPauseFlags { pause_flags },
+        );
+        self.send(&[ix], &[admin]).await
+    }
+    
+    pub async fn platform(&mut self) -> Platform {
+        let platform = self.platform;
+        self.program_account(&platform).await
+    }
+    
+    /// Pyth price account quoting whole collateral tokens in whole payment
+    /// tokens at `price * 10^expo`, published now
+    pub async fn create_price_feed(&mut self, price: i64, expo: i32) -> Pubkey {
+        let feed = Pubkey::new_unique();
+        let now = self.now;
+        self.set_price(&feed, price, expo, 0, now).await;
+        feed
+    }
+    
+    pub async fn set_price(&mut self, feed: &Pubkey, price: i64, expo: i32, conf: u64, publish_time: i64) {
+        let mut data = vec![0u8; PYTH_PRICE_ACCOUNT_LEN];
+        data[0..4].copy_from_slice(&0xa1b2c3d4u32.to_le_bytes());
+        data[8..12].copy_from_slice(&3u32.to_le_bytes());
+        data[20..24].copy_from_slice(&expo.to_le_bytes());
+        data[96..104].copy_from_slice(&publish_time.to_le_bytes());
+        data[208..216].copy_from_slice(&price.to_le_bytes());
+        data[216..224].copy_from_slice(&conf.to_le_bytes());
+        data[224..228].copy_from_slice(&1u32.to_le_bytes());
+        let account = SolanaAccount {
+            lamports: 1_000_000_000,
+            data,
+            owner: btrust_bond::pyth::ID,
+            executable: false,
+            rent_epoch: 0,
+        };
+        self.ctx.set_account(feed, &account.into());
+    }
+    
+    /// Register `feed` for the collateral mint; secured bonds created after this use it
+    pub async fn set_collateral_oracle(&mut self, feed: &Pubkey) -> std::result::Result<(), BanksClientError> {
+        let authority = self.authority();
+        let ix = program_ix(
+            btrust_bond::accounts::SetCollateralOracle {
+                authority: authority.pubkey(),
+                platform: self.platform,
+                collateral_mint: self.collateral_mint,
+                collateral_oracle: self.collateral_oracle(),
+                price_oracle: *feed,
+                system_program: system_program::ID,
+            },
+            btrust_bond::instruction::SetCollateralOracle {},
+        );
+        self.send(&[ix], &[]).await?;
+        self.price_oracle = *feed;
+        Ok(())
+    }
+    
+    pub async fn sync_bond_oracle(&mut self, bond: &TestBond) -> std::result::Result<(), BanksClientError> {
+        let ix = program_ix(
+            btrust_bond::accounts::SyncBondOracle {
+                bond: bond.bond,
+                collateral_oracle: self.collateral_oracle(),
+            },
+            btrust_bond::instruction::SyncBondOracle {},
+        );
+        self.send(&[ix], &[]).await
+    }
+    
+    pub fn collateral_oracle(&self) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"collateral_oracle", self.collateral_mint.as_ref()],
+            &btrust_bond::ID,
+        )
+        .0
+    }
+    
+    /// Secured version of `bond_args` at the minimum collateral ratio
+    pub fn secured_bond_args(&self) -> CreateBondArgs {
+        CreateBondArgs {
+            is_unsecured: false,
+            collateral_ratio_bps: 15_000,
+            ..self.bond_args()
+        }
+    }
+    
+    /// Plain unsecured bond: `FACE_VALUE` face, quarterly 10% coupons, one year term
+    pub fn bond_args(&self) -> CreateBondArgs {
+        CreateBondArgs {
+            name: "Test Bond".to_string(),
+            symbol: "TEST".to_string(),
+            description: String::new(),
+            image_uri: String::new(),
+            website: String::new(),
+            twitter: String::new(),
+            discord: String::new(),
+            principal_amount: FACE_VALUE,
+            coupon_rate_bps: COUPON_RATE_BPS,
+            amortization_bps: 0,
+            is_zero_coupon: false,
+            discount_yield_bps: 0,
+            is_variable_rate: false,
+            reference_rate: Pubkey::default(),
+            rate_spread_bps: 0,
+            rate_floor_bps: 0,
+            rate_cap_bps: 0,
+            payment_frequency: PAYMENT_FREQUENCY,
+            maturity_timestamp: self.now + 4 * QUARTER,
+            is_perpetual: false,
+            total_supply: 1_000,
+            is_capped: true,
+            collateral_ratio_bps: 0,
+            is_unsecured: true,
+            payment_mint: self.payment_mint,
+            grace_period: 86_400,
+            subscription_start: 0,
+            subscription_end: 0,
+            soft_cap: 0,
+            auction_start: 0,
+            auction_end: 0,
+            auction_start_price: 0,
+            auction_floor_price: 0,
+            first_call_date: 0,
+            call_schedule_bps: [0; MAX_CALL_SCHEDULE_STEPS],
+            call_notice_period: 0,
+            put_dates: [0; MAX_PUT_WINDOWS],
+            put_window_length: 0,
+            put_price_bps: 0,
+            sinking_fund_per_period: 0,
+            trustee: Pubkey::default(),
+            conversion_mint: Pubkey::default(),
+            conversion_ratio: 0,
+            conversion_start: 0,
+            conversion_end: 0,
+        }
+    }
+    
+    /// Create a bond with `args` and its payment vaults
+    pub async fn create_bond(&mut self, args: CreateBondArgs) -> TestBond {
+        let issuer = self.create_user(1_000_000_000_000).await;
+        let bond_mint = Keypair::new();
+        let collateral_vault = Keypair::new();
+        let ix = self.create_bond_ix(args, &issuer, &bond_mint, &collateral_vault);
+        self.send(&[ix], &[&issuer.keypair, &bond_mint, &collateral_vault])
+            .await
+            .unwrap();
+        let bond = bond_address(&bond_mint.pubkey());
+        
+        let test_bond = TestBond {
+            bond,
+            bond_mint: bond_mint.pubkey(),
+            collateral_vault: collateral_vault.pubkey(),
+            issuer,
+        };
+        self.send(
+            &[program_ix(
+                btrust_bond::accounts::InitBondVaults {
+                    issuer: test_bond.issuer.pubkey(),
+                    bond,
+                    payment_mint: self.payment_mint,
+                    redemption_vault: test_bond.vault(b"redemption_vault"),
+                    yield_vault: test_bond.vault(b"yield_vault"),
+                    subscription_vault: test_bond.vault(b"subscription_vault"),
+                    token_program: spl_token::ID,
+                    system_program: system_program::ID,
+                    rent: sysvar::rent::ID,
+                },
+                btrust_bond::instruction::InitBondVaults {},
+            )],
+            &[&test_bond.issuer.keypair],
+        )
+        .await
+        .unwrap();
+        
+        test_bond
+    }
+    
+    pub async fn try_create_bond(&mut self, args: CreateBondArgs, issuer: &User) -> std::result::Result<(), BanksClientError> {
+        let bond_mint = Keypair::new();
+        let collateral_vault = Keypair::new();
+        let ix = self.create_bond_ix(args, issuer, &bond_mint, &collateral_vault);
+        self.send(&[ix], &[&issuer.keypair, &bond_mint, &collateral_vault]).await
+    }
+    
+    fn create_bond_ix(&self, args: CreateBondArgs, issuer: &User, bond_mint: &Keypair, collateral_vault: &Keypair) -> Instruction {
+        program_ix(
+            btrust_bond::accounts::CreateBond {
+                issuer: issuer.pubkey(),
+                platform: self.platform,
+                bond: bond_address(&bond_mint.pubkey()),
+                bond_mint: bond_mint.pubkey(),
+                collateral_mint: self.collateral_mint,
+                payment_mint: self.payment_mint,
+                // Leave the registry out until a feed is registered, as a client would
+                collateral_oracle: (!args.is_unsecured && self.price_oracle != Pubkey::default())
+                    .then(|| self.collateral_oracle()),
+                price_oracle: if args.is_unsecured { Pubkey::default() } else { self.price_oracle },
+                collateral_vault: collateral_vault.pubkey(),
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+                rent: sysvar::rent::ID,
+            },
+            btrust_bond::instruction::CreateBond { args },
+        )
+    }
+    
+    pub async fn purchase(&mut self, bond: &TestBond, buyer: &User, quantity: u64) -> std::result::Result<(), BanksClientError> {
+        let issuer_payment = bond.issuer.payment;
+        let price_oracle = self.bond(bond).await.price_oracle;
+        let ix = program_ix(
+            btrust_bond::accounts::PurchaseBond {
+                buyer: buyer.pubkey(),
+                platform: self.platform,
+                bond: bond.bond,
+                bond_mint: bond.bond_mint,
+                price_oracle,
+                buyer_payment: buyer.payment,
+                issuer_payment,
+                treasury: self.treasury_payment,
+                subscription_vault: bond.vault(b"subscription_vault"),
+                buyer_bond_account: bond.bond_account(&buyer.pubkey()),
+                holder_position: bond.position(&buyer.pubkey()),
+                token_program: spl_token::ID,
+                associated_token_program: associated_token::ID,
+                system_program: system_program::ID,
+            },
+            btrust_bond::instruction::PurchaseBond { quantity },
+        );
+        self.send(&[ix], &[&buyer.keypair]).await
+    }
+    
+    pub async fn settle_subscription(&mut self, bond: &TestBond) -> std::result::Result<(), BanksClientError> {
+        let ix = program_ix(
+            btrust_bond::accounts::SettleSubscription {
+                platform: self.platform,
+                bond: bond.bond,
+                subscription_vault: bond.vault(b"subscription_vault"),
+                issuer_payment: bond.issuer.payment,
+                treasury: self.treasury_payment,
+                token_program: spl_token::ID,
+            },
+            btrust_bond::instruction::SettleSubscription {},
+        );
+        self.send(&[ix], &[]).await
+    }
+    
+    /// Mint `amount` of the collateral token to the issuer and deposit it
+    pub async fn deposit_collateral(&mut self, bond: &TestBond, amount: u64) -> std::result::Result<(), BanksClientError> {
+        let collateral_mint = self.collateral_mint;
+        let issuer = bond.issuer.pubkey();
+        let issuer_collateral = get_associated_token_address(&issuer, &collateral_mint);
+        if self.ctx.banks_client.get_account(issuer_collateral).await.unwrap().is_none() {
+            self.create_token_account(&issuer, &collateral_mint).await;
+        }
+        self.mint_to(&collateral_mint, &issuer_collateral, amount).await;
+        let ix = program_ix(
+            btrust_bond::accounts::DepositCollateral {
+                issuer,
+                bond: bond.bond,
+                issuer_collateral,
+                collateral_vault: self.bond(bond).await.collateral_vault,
+                token_program: spl_token::ID,
+            },
+            btrust_bond::instruction::DepositCollateral { amount },
+        );
+        self.send(&[ix], &[&bond.issuer.keypair]).await
+    }
+    
+    /// Secured bond priced by the registered oracle, with `collateral` base units deposited
+    pub async fn create_secured_bond(&mut self, args: CreateBondArgs, collateral: u64) -> TestBond {
+        let bond = self.create_bond(args).await;
+        self.deposit_collateral(&bond, collateral).await.unwrap();
+        bond
+    }
+    
+    pub async fn withdraw_collateral(&mut self, bond: &TestBond, amount: u64) -> std::result::Result<(), BanksClientError> {
+        let bond_account = self.bond(bond).await;
+        let ix = program_ix(
+            btrust_bond::accounts::WithdrawCollateral {
+                issuer: bond.issuer.pubkey(),
+                platform: self.platform,
+                bond: bond.bond,
+                issuer_collateral: get_associated_token_address(&bond.issuer.pubkey(), &self.collateral_mint),
+                collateral_vault: bond_account.collateral_vault,
+                price_oracle: bond_account.price_oracle,
+                token_program: spl_token::ID,
+            },
+            btrust_bond::instruction::WithdrawCollateral { amount },
+        );
+        self.send(&[ix], &[&bond.issuer.keypair]).await
+    }
+    
+    pub async fn liquidate(&mut self, bond: &TestBond, liquidator: &User, liquidator_collateral: &Pubkey, repay_amount: u64) -> std::result::Result<(), BanksClientError> {
+        let bond_account = self.bond(bond).await;
+        let ix = program_ix(
+            btrust_bond::accounts::Liquidate {
+                liquidator: liquidator.pubkey(),
+                platform: self.platform,
+                bond: bond.bond,
+                collateral_vault: bond_account.collateral_vault,
+                liquidator_collateral: *liquidator_collateral,
+                liquidator_payment: liquidator.payment,
+                redemption_vault: bond.vault(b"redemption_vault"),
+                price_oracle: bond_account.price_oracle,
+                token_program: spl_token::ID,
+            },
+            btrust_bond::instruction::Liquidate { repay_amount },
+        );
+        self.send(&[ix], &[&liquidator.keypair]).await
+    }
+    
+    pub async fn refund_subscription(&mut self, bond: &TestBond, holder: &User) -> std::result::Result<(), BanksClientError> {
+        let ix = program_ix(
+            btrust_bond::accounts::RefundSubscription {
+                holder: holder.pubkey(),
+                bond: bond.bond,
+                bond_mint: bond.bond_mint,
+                holder_position: bond.position(&holder.pubkey()),
+                holder_bond_account: bond.bond_account(&holder.pubkey()),
+                subscription_vault: bond.vault(b"subscription_vault"),
+                holder_payment: holder.payment,
+                token_program: spl_token::ID,
+            },
+            btrust_bond::instruction::RefundSubscription {},
+        );
+        self.send(&[ix], &[&holder.keypair]).await
+    }
+    
+    /// Reference rate published by the test payer
+    pub fn bid(&self, bond: &TestBond, bidder: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"bid", bond.bond.as_ref(), bidder.as_ref()],
+            &btrust_bond::ID,
+        )
+        .0
+    }
+    
+    pub async fn place_bid(&mut self, bond: &TestBond, bidder: &User, quantity: u64) -> std::result::Result<(), BanksClientError> {
+        let price_oracle = self.bond(bond).await.price_oracle;
+        let ix = program_ix(
+            btrust_bond::accounts::PlaceBid {
+                bidder: bidder.pubkey(),
+                platform: self.platform,
+                bond: bond.bond,
+                price_oracle,
+                bid: self.bid(bond, &bidder.pubkey()),
+                bidder_payment: bidder.payment,
+                subscription_vault: bond.vault(b"subscription_vault"),
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+            },
+            btrust_bond::instruction::PlaceBid { quantity },
+        );
+        self.send(&[ix], &[&bidder.keypair]).await
+    }
+    
+    pub async fn settle_auction(&mut self, bond: &TestBond) -> std::result::Result<(), BanksClientError> {
+        let price_oracle = self.bond(bond).await.price_oracle;
+        let ix = program_ix(
+            btrust_bond::accounts::SettleAuction {
+                platform: self.platform,
+                bond: bond.bond,
+                subscription_vault: bond.vault(b"subscription_vault"),
+                issuer_payment: bond.issuer.payment,
+                treasury: self.treasury_payment,
+                price_oracle,
+                token_program: spl_token::ID,
+            },
+            btrust_bond::instruction::SettleAuction {},
+        );
+        self.send(&[ix], &[]).await
+    }
+    
+    pub async fn claim_auction_bid(&mut self, bond: &TestBond, bidder: &User) -> std::result::Result<(), BanksClientError> {
+        let ix = program_ix(
+            btrust_bond::accounts::ClaimAuctionBid {
+                bidder: bidder.pubkey(),
+                bond: bond.bond,
+                bond_mint: bond.bond_mint,
+                bid: self.bid(bond, &bidder.pubkey()),
+                bidder_bond_account: bond.bond_account(&bidder.pubkey()),
+                holder_position: bond.position(&bidder.pubkey()),
+                subscription_vault: bond.vault(b"subscription_vault"),
+                bidder_payment: bidder.payment,
+                token_program: spl_token::ID,
+                associated_token_program: associated_token::ID,
+                system_program: system_program::ID,
+            },
+            btrust_bond::instruction::ClaimAuctionBid {},
+        );
+        self.send(&[ix], &[&bidder.keypair]).await
+    }
+    
+    pub async fn refund_bid(&mut self, bond: &TestBond, bidder: &User) -> std::result::Result<(), BanksClientError> {
+        let ix = program_ix(
+            btrust_bond::accounts::RefundBid {
+                bidder: bidder.pubkey(),
+                bond: bond.bond,
+                bid: self.bid(bond, &bidder.pubkey()),
+                subscription_vault: bond.vault(b"subscription_vault"),
+                bidder_payment: bidder.payment,
+                token_program: spl_token::ID,
+            },
+            btrust_bond::instruction::RefundBid {},
+        );
+        self.send(&[ix], &[&bidder.keypair]).await
+    }
+    
+    pub async fn create_reference_rate(&mut self, rate_bps: u64) -> Pubkey {
+        let reference_rate = Keypair::new();
+        let authority = self.ctx.payer.pubkey();
+        let ix = program_ix(
+            btrust_bond::accounts::InitReferenceRate {
+                authority,
+                platform: self.platform,
+                reference_rate: reference_rate.pubkey(),
+                system_program: system_program::ID,
+            },
+            btrust_bond::instruction::InitReferenceRate { publisher: authority, rate_bps },
+        );
+        self.send(&[ix], &[&reference_rate]).await.unwrap();
+        reference_rate.pubkey()
+    }
+    
+    pub async fn publish_reference_rate(&mut self, reference_rate: &Pubkey, rate_bps: u64) {
+        let ix = program_ix(
+            btrust_bond::accounts::PublishReferenceRate {
+                publisher: self.ctx.payer.pubkey(),
+                reference_rate: *reference_rate,
+            },
+            btrust_bond::instruction::PublishReferenceRate { rate_bps },
+        );
+        self.send(&[ix], &[]).await.unwrap();
+    }
+    
+    pub async fn fix_coupon_rate(&mut self, bond: &TestBond, reference_rate: &Pubkey, period: u32) -> std::result::Result<(), BanksClientError> {
+        let payer = self.ctx.payer.pubkey();
+        let ix = program_ix(
+            btrust_bond::accounts::FixCouponRate {
+                payer,
+                bond: bond.bond,
+                reference_rate: *reference_rate,
+                coupon_period: bond.coupon_period(period),
+                system_program: system_program::ID,
+            },
+            btrust_bond::instruction::FixCouponRate { period },
+        );
+        self.send(&[ix], &[]).await
+    }
+    
+    pub async fn record_coupon(&mut self, bond: &TestBond, period: u32) -> std::result::Result<(), BanksClientError> {
+        let payer = self.ctx.payer.pubkey();
+        let ix = program_ix(
+            btrust_bond::accounts::RecordCoupon {
+                payer,
+                platform: self.platform,
+                bond: bond.bond,
+                coupon_period: bond.coupon_period(period),
+                system_program: system_program::ID,
+            },
+            btrust_bond::instruction::RecordCoupon { period },
+        );
+        self.send(&[ix], &[]).await
+    }
+    
+    pub async fn deposit_yield(&mut self, bond: &TestBond, period: u32, amount: u64) -> std::result::Result<(), BanksClientError> {
+        let ix = program_ix(
+            btrust_bond::accounts::DepositYield {
+                issuer: bond.issuer.pubkey(),
+                bond: bond.bond,
+                coupon_period: bond.coupon_period(period),
+                issuer_payment: bond.issuer.payment,
+                yield_vault: bond.vault(b"yield_vault"),
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+            },
+            btrust_bond::instruction::DepositYield { period, amount },
+        );
+        self.send(&[ix], &[&bond.issuer.keypair]).await
+    }
+    
+    pub async fn claim_yield(&mut self, bond: &TestBond, holder: &User) -> std::result::Result<(), BanksClientError> {
+        let ix = program_ix(
+            btrust_bond::accounts::ClaimYield {
+                holder: holder.pubkey(),
+                platform: self.platform,
+                bond: bond.bond,
+                holder_position: bond.position(&holder.pubkey()),
+                yield_vault: bond.vault(b"yield_vault"),
+                holder_payment: holder.payment,
+                token_program: spl_token::ID,
+            },
+            btrust_bond::instruction::ClaimYield {},
+        );
+        self.send(&[ix], &[&holder.keypair]).await
+    }
+    
+    pub async fn claim_principal(&mut self, bond: &TestBond, holder: &User) -> std::result::Result<(), BanksClientError> {
+        let ix = program_ix(
+            btrust_bond::accounts::ClaimYield {
+                holder: holder.pubkey(),
+                platform: self.platform,
+                bond: bond.bond,
+                holder_position: bond.position(&holder.pubkey()),
+                yield_vault: bond.vault(b"yield_vault"),
+                holder_payment: holder.payment,
+                token_program: spl_token::ID,
+            },
+            btrust_bond::instruction::ClaimPrincipal {},
+        );
+        self.send(&[ix], &[&holder.keypair]).await
+    }
+    
+    pub async fn fund_redemption(&mut self, bond: &TestBond, amount: u64) -> std::result::Result<(), BanksClientError> {
+        let ix = program_ix(
+            btrust_bond::accounts::FundRedemption {
+                funder: bond.issuer.pubkey(),
+                bond: bond.bond,
+                funder_payment: bond.issuer.payment,
+                redemption_vault: bond.vault(b"redemption_vault"),
+                token_program: spl_token::ID,
+            },
+            btrust_bond::instruction::FundRedemption { amount },
+        );
+        self.send(&[ix], &[&bond.issuer.keypair]).await
+    }
+    
+    pub async fn redeem_bond(&mut self, bond: &TestBond, holder: &User, quantity: u64) -> std::result::Result<(), BanksClientError> {
+        let ix = program_ix(
+            btrust_bond::accounts::RedeemBond {
+                holder: holder.pubkey(),
+                platform: self.platform,
+                bond: bond.bond,
+                bond_mint: bond.bond_mint,
+                holder_position: bond.position(&holder.pubkey()),
+                holder_bond_account: bond.bond_account(&holder.pubkey()),
+                redemption_vault: bond.vault(b"redemption_vault"),
+                holder_payment: holder.payment,
+                token_program: spl_token::ID,
+            },
+            btrust_bond::instruction::RedeemBond { quantity },
+        );
+        self.send(&[ix], &[&holder.keypair]).await
+    }
+    
+    pub async fn call_bond(&mut self, bond: &TestBond, call_date: i64) -> std::result::Result<(), BanksClientError> {
+        let ix = program_ix(
+            btrust_bond::accounts::CallBond {
+                issuer: bond.issuer.pubkey(),
+                platform: self.platform,
+                bond: bond.bond,
+                issuer_payment: bond.issuer.payment,
+                redemption_vault: bond.vault(b"redemption_vault"),
+                token_program: spl_token::ID,
+            },
+            btrust_bond::instruction::CallBond { call_date },
+        );
+        self.send(&[ix], &[&bond.issuer.keypair]).await
+    }
+    
+    pub async fn exercise_put(&mut self, bond: &TestBond, holder: &User, quantity: u64) -> std::result::Result<(), BanksClientError> {
+        let ix = program_ix(
+            btrust_bond::accounts::ExercisePut {
+                holder: holder.pubkey(),
+                platform: self.platform,
+                bond: bond.bond,
+                bond_mint: bond.bond_mint,
+                holder_position: bond.position(&holder.pubkey()),
+                holder_bond_account: bond.bond_account(&holder.pubkey()),
+                put_escrow: bond.vault(b"put_escrow"),
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+            },
+            btrust_bond::instruction::ExercisePut { quantity },
+        );
+        self.send(&[ix], &[&holder.keypair]).await
+    }
+    
+    pub async fn claim_put(&mut self, bond: &TestBond, holder: &User) -> std::result::Result<(), BanksClientError> {
+        let ix = program_ix(
+            btrust_bond::accounts::ClaimPut {
+                holder: holder.pubkey(),
+                platform: self.platform,
+                bond: bond.bond,
+                bond_mint: bond.bond_mint,
+                holder_position: bond.position(&holder.pubkey()),
+                holder_bond_account: bond.bond_account(&holder.pubkey()),
+                put_escrow: bond.vault(b"put_escrow"),
+                redemption_vault: bond.vault(b"redemption_vault"),
+                holder_payment: holder.payment,
+                token_program: spl_token::ID,
+            },
+            btrust_bond::instruction::ClaimPut {},
+        );
+        self.send(&[ix], &[&holder.keypair]).await
+    }
+    
+    /// Mint `amount` of `conversion_mint` to the issuer and escrow it in the conversion vault
+    pub async fn fund_conversion(&mut self, bond: &TestBond, conversion_mint: &Pubkey, amount: u64) -> std::result::Result<(), BanksClientError> {
+        let issuer = bond.issuer.pubkey();
+        let issuer_conversion_account = get_associated_token_address(&issuer, conversion_mint);
+        if self.ctx.banks_client.get_account(issuer_conversion_account).await.unwrap().is_none() {
+            self.create_token_account(&issuer, conversion_mint).await;
+        }
+        self.mint_to(conversion_mint, &issuer_conversion_account, amount).await;
+        let ix = program_ix(
+            btrust_bond::accounts::FundConversion {
+                issuer: bond.issuer.pubkey(),
+                bond: bond.bond,
+                conversion_mint: *conversion_mint,
+                conversion_vault: bond.vault(b"conversion_vault"),
+                issuer_conversion_account,
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+                rent: sysvar::rent::ID,
+            },
+            btrust_bond::instruction::FundConversion { amount },
+        );
+        self.send(&[ix], &[&bond.issuer.keypair]).await
+    }
+    
+    pub async fn convert_bond(&mut self, bond: &TestBond, holder: &User, holder_conversion_account: &Pubkey, quantity: u64) -> std::result::Result<(), BanksClientError> {
+        let ix = program_ix(
+            btrust_bond::accounts::ConvertBond {
+                holder: holder.pubkey(),
+                platform: self.platform,
+                bond: bond.bond,
+                bond_mint: bond.bond_mint,
+                holder_position: bond.position(&holder.pubkey()),
+                holder_bond_account: bond.bond_account(&holder.pubkey()),
+                conversion_vault: bond.vault(b"conversion_vault"),
+                holder_conversion_account: *holder_conversion_account,
+                token_program: spl_token::ID,
+            },
+            btrust_bond::instruction::ConvertBond { quantity },
+        );
+        self.send(&[ix], &[&holder.keypair]).await
+    }
+    
+    pub async fn reclaim_conversion(&mut self, bond: &TestBond, issuer_conversion_account: &Pubkey) -> std::result::Result<(), BanksClientError> {
+        let ix = program_ix(
+            btrust_bond::accounts::ReclaimConversion {
+                issuer: bond.issuer.pubkey(),
+                platform: self.platform,
+                bond: bond.bond,
+                conversion_vault: bond.vault(b"conversion_vault"),
+                issuer_conversion_account: *issuer_conversion_account,
+                token_program: spl_token::ID,
+            },
+            btrust_bond::instruction::ReclaimConversion {},
+        );
+        self.send(&[ix], &[&bond.issuer.keypair]).await
+    }
+    
+    pub async fn fund_sinking_fund(&mut self, bond: &TestBond, amount: u64) -> std::result::Result<(), BanksClientError> {
+        let ix = program_ix(
+            btrust_bond::accounts::FundSinkingFund {
+                issuer: bond.issuer.pubkey(),
+                bond: bond.bond,
+                payment_mint: self.payment_mint,
+                sinking_fund: bond.vault(b"sinking_fund"),
+                issuer_payment: bond.issuer.payment,
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+                rent: sysvar::rent::ID,
+            },
+            btrust_bond::instruction::FundSinkingFund { amount },
+        );
+        self.send(&[ix], &[&bond.issuer.keypair]).await
+    }
+    
+    pub async fn draw_sinking_fund(&mut self, bond: &TestBond, quantity: u64) -> std::result::Result<(), BanksClientError> {
+        let ix = program_ix(
+            btrust_bond::accounts::DrawSinkingFund {
+                issuer: bond.issuer.pubkey(),
+                platform: self.platform,
+                bond: bond.bond,
+                sinking_fund: bond.vault(b"sinking_fund"),
+            },
+            btrust_bond::instruction::DrawSinkingFund { quantity },
+        );
+        self.send(&[ix], &[&bond.issuer.keypair]).await
+    }
+    
+    pub async fn reveal_sinking_draw(&mut self, bond: &TestBond) -> std::result::Result<(), BanksClientError> {
+        let ix = program_ix(
+            btrust_bond::accounts::RevealSinkingDraw {
+                bond: bond.bond,
+                slot_hashes: sysvar::slot_hashes::ID,
+            },
+            btrust_bond::instruction::RevealSinkingDraw {},
+        );
+        self.send(&[ix], &[]).await
+    }
+    
+    pub async fn settle_sinking_draw(&mut self, bond: &TestBond, holder: &User) -> std::result::Result<(), BanksClientError> {
+        let ix = program_ix(
+            btrust_bond::accounts::SettleSinkingDraw {
+                bond: bond.bond,
+                holder_position: bond.position(&holder.pubkey()),
+            },
+            btrust_bond::instruction::SettleSinkingDraw {},
+        );
+        self.send(&[ix], &[]).await
+    }
+    
+    pub async fn redeem_sinking_fund_draw(&mut self, bond: &TestBond, holder: &User) -> std::result::Result<(), BanksClientError> {
+        let ix = program_ix(
+            btrust_bond::accounts::RedeemSinkingFundDraw {
+                holder: holder.pubkey(),
+                platform: self.platform,
+                bond: bond.bond,
+                bond_mint: bond.bond_mint,
+                holder_position: bond.position(&holder.pubkey()),
+                holder_bond_account: bond.bond_account(&holder.pubkey()),
+                sinking_fund: bond.vault(b"sinking_fund"),
+                holder_payment: holder.payment,
+                token_program: spl_token::ID,
+            },
+            btrust_bond::instruction::RedeemSinkingFundDraw {},
+        );
+        self.send(&[ix], &[&holder.keypair]).await
+    }
+    
+    pub async fn withdraw_sinking_fund(&mut self, bond: &TestBond) -> std::result::Result<(), BanksClientError> {
+        let ix = program_ix(
+            btrust_bond::accounts::WithdrawSinkingFund {
+                issuer: bond.issuer.pubkey(),
+                bond: bond.bond,
+                sinking_fund: bond.vault(b"sinking_fund"),
+                issuer_payment: bond.issuer.payment,
+                token_program: spl_token::ID,
+            },
+            btrust_bond::instruction::WithdrawSinkingFund {},
+        );
+        self.send(&[ix], &[&bond.issuer.keypair]).await
+    }
+    
+    /// Place a sell order, one second after the last so each order gets its own address
+    ///
+    /// Orders at or below face value are slotted into the retire queue after
+    /// every order priced at or below theirs.
+    pub async fn create_sell_order(&mut self, bond: &TestBond, seller: &User, quantity: u64, price_per_bond: u64) -> TestOrder {
+        let mut queue_prev = None;
+        let mut queue_next = self.bond(bond).await.retire_queue_head;
+        while queue_next != Pubkey::default() {
+            let next: Order = self.program_account(&queue_next).await;
+            if next.price_per_bond > price_per_bond {
+                break;
+            }
+            queue_prev = Some(queue_next);
+            queue_next = next.next_retirable;
+        }
+        
+        self.now += 1;
+        let order = Pubkey::find_program_address(
+            &[b"order", seller.pubkey().as_ref(), bond.bond.as_ref(), &self.now.to_le_bytes()],
+            &btrust_bond::ID,
+        )
+        .0;
+        let escrow = Keypair::new();
+        let ix = program_ix(
+            btrust_bond::accounts::CreateSellOrder {
+                seller: seller.pubkey(),
+                platform: self.platform,
+                bond: bond.bond,
+                order,
+                seller_bond_account: bond.bond_account(&seller.pubkey()),
+                seller_position: bond.position(&seller.pubkey()),
+                bond_mint: bond.bond_mint,
+                order_escrow: escrow.pubkey(),
+                queue_prev,
+                queue_next: (queue_next != Pubkey::default()).then_some(queue_next),
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+                rent: sysvar::rent::ID,
+            },
+            btrust_bond::instruction::CreateSellOrder { quantity, price_per_bond },
+        );
+        self.send(&[ix], &[&seller.keypair, &escrow]).await.unwrap();
+        TestOrder { order, escrow: escrow.pubkey(), seller: seller.pubkey(), seller_payment: seller.payment }
+    }
+    
+    pub async fn fill_order(&mut self, bond: &TestBond, buyer: &User, order: &TestOrder, quantity: u64) -> std::result::Result<(), BanksClientError> {
+        let buyer_bond_account = bond.bond_account(&buyer.pubkey());
+        if self.ctx.banks_client.get_account(buyer_bond_account).await.unwrap().is_none() {
+            self.create_token_account(&buyer.pubkey(), &bond.bond_mint).await;
+        }
+        let ix = program_ix(
+            btrust_bond::accounts::FillOrder {
+                buyer: buyer.pubkey(),
+                platform: self.platform,
+                order: order.order,
+                bond: bond.bond,
+                buyer_payment: buyer.payment,
+                seller_payment: order.seller_payment,
+                treasury: self.treasury_payment,
+                order_escrow: order.escrow,
+                buyer_bond_account,
+                buyer_position: bond.position(&buyer.pubkey()),
+                seller_position: bond.position(&order.seller),
+                bond_mint: bond.bond_mint,
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+            },
+            btrust_bond::instruction::FillOrder { quantity },
+        );
+        self.send(&[ix], &[&buyer.keypair]).await
+    }
+    
+    pub async fn cancel_order(&mut self, bond: &TestBond, seller: &User, order: &TestOrder) -> std::result::Result<(), BanksClientError> {
+        let ix = program_ix(
+            btrust_bond::accounts::CancelOrder {
+                seller: seller.pubkey(),
+                order: order.order,
+                bond: bond.bond,
+                order_escrow: order.escrow,
+                seller_bond_account: bond.bond_account(&seller.pubkey()),
+                seller_position: bond.position(&seller.pubkey()),
+                bond_mint: bond.bond_mint,
+                token_program: spl_token::ID,
+            },
+            btrust_bond::instruction::CancelOrder {},
+        );
+        self.send(&[ix], &[&seller.keypair]).await
+    }
+    
+    pub async fn retire_from_sinking_fund(&mut self, bond: &TestBond, order: &TestOrder, quantity: u64) -> std::result::Result<(), BanksClientError> {
+        let ix = program_ix(
+            btrust_bond::accounts::RetireFromSinkingFund {
+                issuer: bond.issuer.pubkey(),
+                platform: self.platform,
+                bond: bond.bond,
+                bond_mint: bond.bond_mint,
+                order: order.order,
+                order_escrow: order.escrow,
+                sinking_fund: bond.vault(b"sinking_fund"),
+                seller_payment: order.seller_payment,
+                seller_position: bond.position(&order.seller),
+                token_program: spl_token::ID,
+            },
+            btrust_bond::instruction::RetireFromSinkingFund { quantity },
+        );
+        self.send(&[ix], &[&bond.issuer.keypair]).await
+    }
+    
+    /// Prune `orders` off the front of the retire queue, in the order given
+    pub async fn prune_retire_queue(&mut self, bond: &TestBond, orders: &[&TestOrder]) -> std::result::Result<(), BanksClientError> {
+        let mut ix = program_ix(
+            btrust_bond::accounts::PruneRetireQueue { bond: bond.bond },
+            btrust_bond::instruction::PruneRetireQueue {},
+        );
+        ix.accounts.extend(orders.iter().map(|order| AccountMeta::new(order.order, false)));
+        self.send(&[ix], &[]).await
+    }
+    
+    /// Cancel an unsold offering, passing the sinking fund only when `with_sinking_fund`
+    pub async fn cancel_offering(&mut self, bond: &TestBond, with_sinking_fund: bool) -> std::result::Result<(), BanksClientError> {
+        let collateral_mint = self.collateral_mint;
+        let issuer_collateral = get_associated_token_address(&bond.issuer.pubkey(), &collateral_mint);
+        if self.ctx.banks_client.get_account(issuer_collateral).await.unwrap().is_none() {
+            self.create_token_account(&bond.issuer.pubkey(), &collateral_mint).await;
+        }
+        let ix = program_ix(
+            btrust_bond::accounts::CancelOffering {
+                issuer: bond.issuer.pubkey(),
+                bond: bond.bond,
+                collateral_vault: bond.collateral_vault,
+                issuer_collateral,
+                issuer_payment: bond.issuer.payment,
+                redemption_vault: Some(bond.vault(b"redemption_vault")),
+                yield_vault: Some(bond.vault(b"yield_vault")),
+                subscription_vault: Some(bond.vault(b"subscription_vault")),
+                sinking_fund: with_sinking_fund.then(|| bond.vault(b"sinking_fund")),
+                conversion_vault: None,
+                issuer_conversion_account: None,
+                token_program: spl_token::ID,
+            },
+            btrust_bond::instruction::CancelOffering {},
+        );
+        self.send(&[ix], &[&bond.issuer.keypair]).await
+    }
+    
+    pub async fn mark_default(&mut self, bond: &TestBond, period: u32) -> std::result::Result<(), BanksClientError> {
+        let ix = program_ix(
+            btrust_bond::accounts::MarkDefault {
+                bond: bond.bond,
+                coupon_period: bond.coupon_period(period),
+            },
+            btrust_bond::instruction::MarkDefault {},
+        );
+        self.send(&[ix], &[]).await
+    }
+    
+    pub async fn mark_sinking_fund_default(&mut self, bond: &TestBond) -> std::result::Result<(), BanksClientError> {
+        let ix = program_ix(
+            btrust_bond::accounts::MarkSinkingFundDefault { bond: bond.bond },
+            btrust_bond::instruction::MarkSinkingFundDefault {},
+        );
+        self.send(&[ix], &[]).await
+    }
+    
+    pub async fn mark_principal_default(&mut self, bond: &TestBond) -> std::result::Result<(), BanksClientError> {
+        let ix = program_ix(
+            btrust_bond::accounts::MarkPrincipalDefault { bond: bond.bond },
+            btrust_bond::instruction::MarkPrincipalDefault {},
+        );
+        self.send(&[ix], &[]).await
+    }
+    
+    pub async fn claim_recovery(&mut self, bond: &TestBond, holder: &User, quantity: u64) -> std::result::Result<(), BanksClientError> {
+        let collateral_mint = self.collateral_mint;
+        let holder_collateral = get_associated_token_address(&holder.pubkey(), &collateral_mint);
+        if self.ctx.banks_client.get_account(holder_collateral).await.unwrap().is_none() {
+            self.create_token_account(&holder.pubkey(), &collateral_mint).await;
+        }
+        let state = self.bond(bond).await;
+        let ix = program_ix(
+            btrust_bond::accounts::ClaimRecovery {
+                holder: holder.pubkey(),
+                platform: self.platform,
+                bond: bond.bond,
+                bond_mint: bond.bond_mint,
+                holder_position: bond.position(&holder.pubkey()),
+                holder_bond_account: bond.bond_account(&holder.pubkey()),
+                collateral_vault: state.collateral_vault,
+                redemption_vault: bond.vault(b"redemption_vault"),
+                yield_vault: bond.vault(b"yield_vault"),
+                sinking_fund: state.has_sinking_fund.then(|| bond.vault(b"sinking_fund")),
+                holder_collateral,
+                holder_payment: holder.payment,
+                token_program: spl_token::ID,
+            },
+            btrust_bond::instruction::ClaimRecovery { quantity },
+        );
+        self.send(&[ix], &[&holder.keypair]).await
+    }
+    
+    pub async fn transfer_bond(&mut self, bond: &TestBond, from: &User, to: &User, quantity: u64) -> std::result::Result<(), BanksClientError> {
+        let ix = program_ix(
+            btrust_bond::accounts::TransferBond {
+                sender: from.pubkey(),
+                recipient: to.pubkey(),
+                platform: self.platform,
+                bond: bond.bond,
+                bond_mint: bond.bond_mint,
+                sender_bond_account: bond.bond_account(&from.pubkey()),
+                sender_position: bond.position(&from.pubkey()),
+                recipient_bond_account: bond.bond_account(&to.pubkey()),
+                recipient_position: bond.position(&to.pubkey()),
+                token_program: spl_token::ID,
+                associated_token_program: associated_token::ID,
+                system_program: system_program::ID,
+            },
+            btrust_bond::instruction::TransferBond { quantity },
+        );
+        self.send(&[ix], &[&from.keypair]).await
+    }
+    
+    pub async fn trustee_accelerate(&mut self, bond: &TestBond, trustee: &User, period: Option<u32>) -> std::result::Result<(), BanksClientError> {
+        let accounts = self.trustee_enforce(bond, trustee, period).await;
+        let ix = program_ix(accounts, btrust_bond::instruction::TrusteeAccelerate {});
+        self.send(&[ix], &[&trustee.keypair]).await
+    }
+    
+    pub async fn trustee_declare_default(&mut self, bond: &TestBond, trustee: &User, period: Option<u32>) -> std::result::Result<(), BanksClientError> {
+        let accounts = self.trustee_enforce(bond, trustee, period).await;
+        let ix = program_ix(accounts, btrust_bond::instruction::TrusteeDeclareDefault {});
+        self.send(&[ix], &[&trustee.keypair]).await
+    }
+    
+    async fn trustee_enforce(&mut self, bond: &TestBond, trustee: &User, period: Option<u32>) -> btrust_bond::accounts::TrusteeEnforce {
+        btrust_bond::accounts::TrusteeEnforce {
+            trustee: trustee.pubkey(),
+            bond: bond.bond,
+            price_oracle: self.bond(bond).await.price_oracle,
+            coupon_period: period.map(|period| bond.coupon_period(period)),
+        }
+    }
+    
+    pub async fn trustee_set_trading_frozen(&mut self, bond: &TestBond, trustee: &User, period: Option<u32>, frozen: bool) -> std::result::Result<(), BanksClientError> {
+        let accounts = self.trustee_enforce(bond, trustee, period).await;
+        let ix = program_ix(accounts, btrust_bond::instruction::TrusteeSetTradingFrozen { frozen });
+        self.send(&[ix], &[&trustee.keypair]).await
+    }
+    
+    pub async fn trustee_sweep(&mut self, bond: &TestBond, trustee: &User) -> std::result::Result<(), BanksClientError> {
+        let ix = program_ix(
+            btrust_bond::accounts::TrusteeSweep {
+                trustee: trustee.pubkey(),
+                bond: bond.bond,
+                redemption_vault: bond.vault(b"redemption_vault"),
+                yield_vault: bond.vault(b"yield_vault"),
+                sinking_fund: self.bond(bond).await.has_sinking_fund.then(|| bond.vault(b"sinking_fund")),
+                token_program: spl_token::ID,
+            },
+            btrust_bond::instruction::TrusteeSweep {},
+        );
+        self.send(&[ix], &[&trustee.keypair]).await
+    }
+    
+    /// Propose `amendment` as the bond's next proposal and return its index
+    pub async fn propose_amendment(&mut self, bond: &TestBond, amendment: Amendment) -> u32 {
+        let index = self.bond(bond).await.proposal_count;
+        let ix = program_ix(
+            btrust_bond::accounts::ProposeAmendment {
+                issuer: bond.issuer.pubkey(),
+                bond: bond.bond,
+                proposal: bond.proposal(index),
+                system_program: system_program::ID,
+            },
+            btrust_bond::instruction::ProposeAmendment { amendment },
+        );
+        self.send(&[ix], &[&bond.issuer.keypair]).await.unwrap();
+        index
+    }
+    
+    pub async fn cast_vote(&mut self, bond: &TestBond, proposal: u32, holder: &User, approve: bool) -> std::result::Result<(), BanksClientError> {
+        let proposal = bond.proposal(proposal);
+        let ix = program_ix(
+            btrust_bond::accounts::CastVote {
+                holder: holder.pubkey(),
+                bond: bond.bond,
+                proposal,
+                vote: Pubkey::find_program_address(
+                    &[b"vote", proposal.as_ref(), holder.pubkey().as_ref()],
+                    &btrust_bond::ID,
+                )
+                .0,
+                holder_position: bond.position(&holder.pubkey()),
+                system_program: system_program::ID,
+            },
+            btrust_bond::instruction::CastVote { approve },
+        );
+        self.send(&[ix], &[&holder.keypair]).await
+    }
+    
+    pub async fn finalize_amendment(&mut self, bond: &TestBond, proposal: u32) -> std::result::Result<(), BanksClientError> {
+        let ix = program_ix(
+            btrust_bond::accounts::FinalizeAmendment {
+                bond: bond.bond,
+                proposal: bond.proposal(proposal),
+            },
+            btrust_bond::instruction::FinalizeAmendment {},
+        );
+        self.send(&[ix], &[]).await
+    }
+    
+    pub async fn bond(&mut self, bond: &TestBond) -> Bond {
+        self.program_account(&bond.bond).await
+    }
+    
+    pub async fn position(&mut self, bond: &TestBond, holder: &User) -> HolderPosition {
+        self.program_account(&bond.position(&holder.pubkey())).await
+    }
+    
+    pub async fn program_account<T: AccountDeserialize>(&mut self, address: &Pubkey) -> T {
+        let account = self.ctx.banks_client.get_account(*address).await.unwrap().unwrap();
+        T::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+    
+    pub async fn token_balance(&mut self, address: &Pubkey) -> u64 {
+        let account = self.ctx.banks_client.get_account(*address).await.unwrap().unwrap();
+        spl_token::state::Account::unpack(&account.data).unwrap().amount
+    }
+}
+
+pub fn bond_address(bond_mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"bond", bond_mint.as_ref()], &btrust_bond::ID).0
+}
+
+pub fn program_ix(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: btrust_bond::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+fn spl_associated_token_account_create(payer: &Pubkey, owner: &Pubkey, mint: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: associated_token::ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(get_associated_token_address(owner, mint), false),
+            AccountMeta::new_readonly(*owner, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ],
+        data: vec![],
+    }
+}
+
+/// Assert that `result` failed with the program error `expected`
+pub fn assert_program_error(result: std::result::Result<(), BanksClientError>, expected: BtrustError) {
+    let code = u32::from(expected);
+    match result {
+        Err(BanksClientError::TransactionError(TransactionError::InstructionError(
+            _,
+            InstructionError::Custom(actual),
+        ))) => assert_eq!(actual, code, "expected {expected:?}"),
+        other => panic!("expected {expected:?}, got {other:?}"),
+    }
+}
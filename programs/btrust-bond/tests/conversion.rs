@@ -0,0 +1,70 @@
+mod common;
+
+use btrust_bond::BtrustError;
+use common::*;
+
+const RATIO: u64 = 50_000;
+
+#[tokio::test]
+async fn holders_convert_free_bonds_inside_the_window() {
+    let mut env = TestEnv::new().await;
+    let conversion_mint = env.create_mint(9).await;
+    let mut args = env.bond_args();
+    args.conversion_mint = conversion_mint;
+    args.conversion_ratio = RATIO;
+    args.conversion_start = env.now + 86_400;
+    args.conversion_end = env.now + QUARTER;
+    let bond = env.create_bond(args).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    let alice_conversion = env.create_token_account(&alice.pubkey(), &conversion_mint).await;
+    
+    env.purchase(&bond, &alice, 10).await.unwrap();
+    env.fund_conversion(&bond, &conversion_mint, 6 * RATIO).await.unwrap();
+    assert_program_error(env.convert_bond(&bond, &alice, &alice_conversion, 1).await, BtrustError::ConversionWindowClosed);
+    
+    env.warp_to(env.now + 86_400).await;
+    assert_program_error(
+        env.convert_bond(&bond, &alice, &alice.payment, 1).await,
+        BtrustError::InvalidConversionMint,
+    );
+    assert_program_error(
+        env.convert_bond(&bond, &alice, &alice_conversion, 7).await,
+        BtrustError::InsufficientConversionBalance,
+    );
+    env.convert_bond(&bond, &alice, &alice_conversion, 4).await.unwrap();
+    assert_eq!(env.token_balance(&alice_conversion).await, 4 * RATIO);
+    assert_eq!(env.token_balance(&bond.bond_account(&alice.pubkey())).await, 6);
+    assert_eq!(env.bond(&bond).await.outstanding_supply, 6);
+    
+    // Bonds escrowed in a sell order cannot be converted
+    env.create_sell_order(&bond, &alice, 5, FACE_VALUE).await;
+    assert_program_error(env.convert_bond(&bond, &alice, &alice_conversion, 2).await, BtrustError::InsufficientBalance);
+}
+
+#[tokio::test]
+async fn issuer_reclaims_unused_conversion_tokens_after_the_window() {
+    let mut env = TestEnv::new().await;
+    let conversion_mint = env.create_mint(9).await;
+    let mut args = env.bond_args();
+    args.conversion_mint = conversion_mint;
+    args.conversion_ratio = RATIO;
+    args.conversion_start = env.now;
+    args.conversion_end = env.now + QUARTER;
+    let bond = env.create_bond(args).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    let alice_conversion = env.create_token_account(&alice.pubkey(), &conversion_mint).await;
+    let issuer_conversion = env.create_token_account(&bond.issuer.pubkey(), &conversion_mint).await;
+    
+    env.purchase(&bond, &alice, 10).await.unwrap();
+    env.fund_conversion(&bond, &conversion_mint, 10 * RATIO).await.unwrap();
+    env.convert_bond(&bond, &alice, &alice_conversion, 3).await.unwrap();
+    assert_program_error(env.reclaim_conversion(&bond, &issuer_conversion).await, BtrustError::ConversionWindowOpen);
+    
+    let created_at = env.bond(&bond).await.created_at;
+    env.warp_to(created_at + QUARTER).await;
+    env.record_coupon(&bond, 0).await.unwrap();
+    assert_program_error(env.convert_bond(&bond, &alice, &alice_conversion, 1).await, BtrustError::ConversionWindowClosed);
+    env.reclaim_conversion(&bond, &issuer_conversion).await.unwrap();
+    assert_eq!(env.token_balance(&issuer_conversion).await, 7 * RATIO);
+    assert_program_error(env.reclaim_conversion(&bond, &issuer_conversion).await, BtrustError::NothingToReclaim);
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use btrust_bond::{BtrustError, CouponPeriod};
+use common::*;
+
+/// One quarter of the 10% coupon on `FACE_VALUE`
+const COUPON: u64 = FACE_VALUE * COUPON_RATE_BPS / 10_000 / 4;
+
+#[tokio::test]
+async fn coupons_are_recorded_and_funded_period_by_period() {
+    let mut env = TestEnv::new().await;
+    let args = env.bond_args();
+    let bond = env.create_bond(args).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    env.purchase(&bond, &alice, 10).await.unwrap();
+    assert_eq!(env.bond(&bond).await.coupon_periods, 4);
+    
+    assert_program_error(env.record_coupon(&bond, 0).await, BtrustError::CouponRecordDateNotReached);
+    let start = env.now;
+    env.warp_to(start + QUARTER).await;
+    assert_program_error(env.record_coupon(&bond, 1).await, BtrustError::CouponPeriodOutOfOrder);
+    env.record_coupon(&bond, 0).await.unwrap();
+    let period: CouponPeriod = env.program_account(&bond.coupon_period(0)).await;
+    assert_eq!((period.coupon_per_bond, period.supply_at_record, period.amount_due), (COUPON, 10, 10 * COUPON));
+    assert_eq!(env.bond(&bond).await.yield_index, COUPON as u128 * 1_000_000_000_000);
+    
+    assert_program_error(env.deposit_yield(&bond, 1, COUPON).await, BtrustError::CouponPeriodOutOfOrder);
+    assert_program_error(env.deposit_yield(&bond, 0, 10 * COUPON + 1).await, BtrustError::ExceedsCouponDue);
+    env.deposit_yield(&bond, 0, 4 * COUPON).await.unwrap();
+    assert_eq!(env.bond(&bond).await.coupon_periods_funded, 0);
+    env.deposit_yield(&bond, 0, 6 * COUPON).await.unwrap();
+    assert_eq!(env.bond(&bond).await.coupon_periods_funded, 1);
+    
+    // A period can be prefunded, and counts as funded once recorded
+    env.deposit_yield(&bond, 1, 10 * COUPON).await.unwrap();
+    env.warp_to(start + 2 * QUARTER).await;
+    env.record_coupon(&bond, 1).await.unwrap();
+    let state = env.bond(&bond).await;
+    assert_eq!((state.coupon_periods_recorded, state.coupon_periods_funded), (2, 2));
+    assert_eq!(env.token_balance(&bond.vault(b"yield_vault")).await, 20 * COUPON);
+}
+
+#[tokio::test]
+async fn payment_frequency_sets_the_schedule() {
+    let mut env = TestEnv::new().await;
+    let issuer = env.create_user(0).await;
+    let mut args = env.bond_args();
+    args.payment_frequency = 3;
+    assert_program_error(env.try_create_bond(args, &issuer).await, BtrustError::InvalidPaymentFrequency);
+    
+    // Monthly coupons over a term of two and a half months, the last one pro-rated
+    let mut args = env.bond_args();
+    args.payment_frequency = 12;
+    let month = 31_536_000 / 12;
+    args.maturity_timestamp = env.now + 2 * month + month / 2;
+    let bond = env.create_bond(args).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    env.purchase(&bond, &alice, 1).await.unwrap();
+    assert_eq!(env.bond(&bond).await.coupon_periods, 3);
+    
+    let maturity = env.bond(&bond).await.maturity_timestamp;
+    env.warp_to(maturity).await;
+    for period in 0..3 {
+        env.record_coupon(&bond, period).await.unwrap();
+    }
+    let full: CouponPeriod = env.program_account(&bond.coupon_period(1)).await;
+    let last: CouponPeriod = env.program_account(&bond.coupon_period(2)).await;
+    assert_eq!(full.coupon_per_bond, FACE_VALUE * COUPON_RATE_BPS / 10_000 / 12);
+    assert_eq!(last.coupon_per_bond, full.coupon_per_bond / 2);
+    assert_eq!(last.record_date, maturity);
+    assert_program_error(env.record_coupon(&bond, 3).await, BtrustError::InvalidCouponPeriod);
+}
+
+#[tokio::test]
+async fn funded_coupons_cannot_be_defaulted() {
+    let mut env = TestEnv::new().await;
+    let bond = env.create_bond(env.bond_args()).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    env.purchase(&bond, &alice, 10).await.unwrap();
+    
+    let created_at = env.bond(&bond).await.created_at;
+    env.warp_to(created_at + QUARTER).await;
+    env.record_coupon(&bond, 0).await.unwrap();
+    env.deposit_yield(&bond, 0, 10 * FACE_VALUE / 40).await.unwrap();
+    
+    // The next period is checked once the first is funded, and it has no missed coupon yet
+    env.deposit_yield(&bond, 1, 1).await.unwrap();
+    env.warp_to(created_at + QUARTER + 86_400 + 1).await;
+    assert_program_error(env.mark_default(&bond, 1).await, BtrustError::CouponNotMissed);
+    assert!(env.mark_default(&bond, 0).await.is_err());
+    assert!(!env.bond(&bond).await.is_defaulted);
+}
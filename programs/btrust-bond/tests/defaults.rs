@@ -0,0 +1,38 @@
+mod common;
+
+use btrust_bond::BtrustError;
+use common::*;
+
+#[tokio::test]
+async fn grace_period_cannot_be_negative() {
+    let mut env = TestEnv::new().await;
+    let mut args = env.bond_args();
+    args.grace_period = -1;
+    let issuer = env.create_user(0).await;
+    assert_program_error(env.try_create_bond(args.clone(), &issuer).await, BtrustError::InvalidGracePeriod);
+    
+    args.grace_period = 90 * 86_400;
+    env.try_create_bond(args, &issuer).await.unwrap();
+}
+
+#[tokio::test]
+async fn missed_coupon_defaults_after_the_grace_period() {
+    let mut env = TestEnv::new().await;
+    let bond = env.create_bond(env.bond_args()).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    let bob = env.create_user(0).await;
+    env.purchase(&bond, &alice, 10).await.unwrap();
+    
+    let created_at = env.bond(&bond).await.created_at;
+    env.warp_to(created_at + QUARTER).await;
+    env.record_coupon(&bond, 0).await.unwrap();
+    assert_program_error(env.mark_default(&bond, 0).await, BtrustError::GracePeriodNotElapsed);
+    
+    env.warp_to(created_at + QUARTER + 86_400 + 1).await;
+    env.mark_default(&bond, 0).await.unwrap();
+    let bond_state = env.bond(&bond).await;
+    assert!(bond_state.is_defaulted);
+    assert!(!bond_state.is_active);
+    assert_program_error(env.transfer_bond(&bond, &alice, &bob, 1).await, BtrustError::BondNotActive);
+}
+
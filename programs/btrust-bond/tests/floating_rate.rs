@@ -0,0 +1,69 @@
+mod common;
+
+use btrust_bond::{BtrustError, CouponPeriod};
+use common::*;
+
+const DAY: i64 = 86_400;
+
+/// Coupon per bond for a quarter at `rate_bps`
+fn coupon(rate_bps: u64) -> u64 {
+    FACE_VALUE * rate_bps / 10_000 / PAYMENT_FREQUENCY as u64
+}
+
+#[tokio::test]
+async fn fixings_only_accept_rates_published_near_the_fixing_date() {
+    let mut env = TestEnv::new().await;
+    let reference_rate = env.create_reference_rate(400).await;
+    let mut args = env.bond_args();
+    args.is_variable_rate = true;
+    args.reference_rate = reference_rate;
+    args.coupon_rate_bps = 500;
+    args.rate_spread_bps = 100;
+    args.rate_floor_bps = 200;
+    args.rate_cap_bps = 2_000;
+    let bond = env.create_bond(args).await;
+    let created_at = env.bond(&bond).await.created_at;
+    
+    env.fix_coupon_rate(&bond, &reference_rate, 0).await.unwrap();
+    assert_program_error(
+        env.fix_coupon_rate(&bond, &reference_rate, 0).await,
+        BtrustError::CouponRateAlreadyFixed,
+    );
+    
+    // Neither the old rate nor one published days after the fixing date fixes period 1
+    env.warp_to(created_at + QUARTER + 3 * DAY).await;
+    assert_program_error(
+        env.fix_coupon_rate(&bond, &reference_rate, 1).await,
+        BtrustError::StaleReferenceRate,
+    );
+    env.publish_reference_rate(&reference_rate, 900).await;
+    assert_program_error(
+        env.fix_coupon_rate(&bond, &reference_rate, 1).await,
+        BtrustError::StaleReferenceRate,
+    );
+    
+    env.record_coupon(&bond, 0).await.unwrap();
+    assert_program_error(
+        env.fix_coupon_rate(&bond, &reference_rate, 0).await,
+        BtrustError::CouponPeriodOutOfOrder,
+    );
+    let period: CouponPeriod = env.program_account(&bond.coupon_period(0)).await;
+    assert_eq!(period.coupon_per_bond, coupon(500));
+    
+    // Unfixed period 1 keeps period 0's rate
+    env.warp_to(created_at + 2 * QUARTER - DAY / 2).await;
+    env.publish_reference_rate(&reference_rate, 900).await;
+    env.warp_to(created_at + 2 * QUARTER + 1).await;
+    env.record_coupon(&bond, 1).await.unwrap();
+    let period: CouponPeriod = env.program_account(&bond.coupon_period(1)).await;
+    assert_eq!(period.coupon_per_bond, coupon(500));
+    
+    // Period 2 fixes at the rate in effect on its fixing date, even after a
+    // later publication
+    env.publish_reference_rate(&reference_rate, 1_500).await;
+    env.fix_coupon_rate(&bond, &reference_rate, 2).await.unwrap();
+    env.warp_to(created_at + 3 * QUARTER + 1).await;
+    env.record_coupon(&bond, 2).await.unwrap();
+    let period: CouponPeriod = env.program_account(&bond.coupon_period(2)).await;
+    assert_eq!(period.coupon_per_bond, coupon(1_000));
+}
@@ -0,0 +1,124 @@
+mod common;
+
+use btrust_bond::{Amendment, BtrustError, CouponPeriod, Proposal};
+use common::*;
+
+const NEW_COUPON_RATE_BPS: u64 = 1_200;
+const VOTING_PERIOD_SECS: i64 = 604_800;
+
+fn change_coupon_rate() -> Amendment {
+    Amendment::ChangeCouponRate { coupon_rate_bps: NEW_COUPON_RATE_BPS }
+}
+
+#[tokio::test]
+async fn amendments_pass_with_a_supermajority_of_holders() {
+    let mut env = TestEnv::new().await;
+    let bond = env.create_bond(env.bond_args()).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    let bob = env.create_user(100 * FACE_VALUE).await;
+    let carol = env.create_user(100 * FACE_VALUE).await;
+    env.purchase(&bond, &alice, 8).await.unwrap();
+    env.purchase(&bond, &bob, 2).await.unwrap();
+    
+    let proposal = env.propose_amendment(&bond, change_coupon_rate()).await;
+    
+    // Bonds bought after the proposal carry no vote on it
+    env.purchase(&bond, &carol, 5).await.unwrap();
+    assert_program_error(env.cast_vote(&bond, proposal, &carol, false).await, BtrustError::NoVotingPower);
+    
+    env.cast_vote(&bond, proposal, &alice, true).await.unwrap();
+    env.cast_vote(&bond, proposal, &bob, false).await.unwrap();
+    assert_program_error(env.finalize_amendment(&bond, proposal).await, BtrustError::VotingOpen);
+    
+    let created_at = env.program_account::<Proposal>(&bond.proposal(proposal)).await.created_at;
+    env.warp_to(created_at + VOTING_PERIOD_SECS).await;
+    assert_program_error(env.cast_vote(&bond, proposal, &carol, true).await, BtrustError::VotingClosed);
+    env.finalize_amendment(&bond, proposal).await.unwrap();
+    
+    let proposal = env.program_account::<Proposal>(&bond.proposal(proposal)).await;
+    assert_eq!((proposal.votes_for, proposal.votes_against), (8, 2));
+    assert!(proposal.is_approved);
+    
+    // The new rate applies from the next coupon period
+    let bond_state = env.bond(&bond).await;
+    assert_eq!(bond_state.coupon_rate_bps, COUPON_RATE_BPS);
+    assert_eq!(bond_state.pending_coupon_rate_bps, NEW_COUPON_RATE_BPS);
+    env.warp_to(bond_state.created_at + QUARTER).await;
+    env.record_coupon(&bond, 0).await.unwrap();
+    let period: CouponPeriod = env.program_account(&bond.coupon_period(0)).await;
+    assert_eq!(period.coupon_per_bond, COUPON_PER_BOND);
+    env.warp_to(bond_state.created_at + 2 * QUARTER).await;
+    env.record_coupon(&bond, 1).await.unwrap();
+    let period: CouponPeriod = env.program_account(&bond.coupon_period(1)).await;
+    assert_eq!(period.coupon_per_bond, COUPON_PER_BOND * NEW_COUPON_RATE_BPS / COUPON_RATE_BPS);
+    assert_eq!(env.bond(&bond).await.coupon_rate_bps, NEW_COUPON_RATE_BPS);
+}
+
+#[tokio::test]
+async fn bonds_moved_after_the_snapshot_vote_once() {
+    let mut env = TestEnv::new().await;
+    let bond = env.create_bond(env.bond_args()).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    let bob = env.create_user(100 * FACE_VALUE).await;
+    let carol = env.create_user(0).await;
+    env.purchase(&bond, &alice, 6).await.unwrap();
+    env.purchase(&bond, &bob, 4).await.unwrap();
+    
+    let proposal = env.propose_amendment(&bond, change_coupon_rate()).await;
+    env.cast_vote(&bond, proposal, &alice, true).await.unwrap();
+    
+    // Alice's bonds carry no second vote for their new holders
+    env.transfer_bond(&bond, &alice, &carol, 6).await.unwrap();
+    assert_program_error(env.cast_vote(&bond, proposal, &carol, true).await, BtrustError::NoVotingPower);
+    env.transfer_bond(&bond, &carol, &bob, 6).await.unwrap();
+    
+    // Bob still votes with the bonds he held at the snapshot
+    env.cast_vote(&bond, proposal, &bob, false).await.unwrap();
+    
+    let created_at = env.program_account::<Proposal>(&bond.proposal(proposal)).await.created_at;
+    env.warp_to(created_at + VOTING_PERIOD_SECS).await;
+    env.finalize_amendment(&bond, proposal).await.unwrap();
+    
+    let proposal = env.program_account::<Proposal>(&bond.proposal(proposal)).await;
+    assert_eq!((proposal.votes_for, proposal.votes_against), (6, 4));
+    assert!(!proposal.is_approved);
+    assert_eq!(env.bond(&bond).await.coupon_rate_bps, COUPON_RATE_BPS);
+}
+
+#[tokio::test]
+async fn positions_keep_the_weight_of_the_latest_snapshot() {
+    let mut env = TestEnv::new().await;
+    let bond = env.create_bond(env.bond_args()).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    let bob = env.create_user(100 * FACE_VALUE).await;
+    let carol = env.create_user(100 * FACE_VALUE).await;
+    env.purchase(&bond, &alice, 6).await.unwrap();
+    env.purchase(&bond, &bob, 4).await.unwrap();
+    env.purchase(&bond, &carol, 2).await.unwrap();
+    
+    let first = env.propose_amendment(&bond, change_coupon_rate()).await;
+    env.transfer_bond(&bond, &alice, &bob, 1).await.unwrap();
+    let second = env.propose_amendment(&bond, change_coupon_rate()).await;
+    env.transfer_bond(&bond, &alice, &bob, 1).await.unwrap();
+    
+    // Both positions changed after the second proposal, which overwrote
+    // their weight for the first
+    assert_program_error(
+        env.cast_vote(&bond, first, &alice, true).await,
+        BtrustError::PositionChangedSinceSnapshot,
+    );
+    assert_program_error(
+        env.cast_vote(&bond, first, &bob, true).await,
+        BtrustError::PositionChangedSinceSnapshot,
+    );
+    env.cast_vote(&bond, second, &alice, true).await.unwrap();
+    env.cast_vote(&bond, second, &bob, true).await.unwrap();
+    let proposal = env.program_account::<Proposal>(&bond.proposal(second)).await;
+    assert_eq!(proposal.votes_for, 10);
+    
+    // Carol's position never changed, so she votes on both
+    env.cast_vote(&bond, first, &carol, false).await.unwrap();
+    env.cast_vote(&bond, second, &carol, false).await.unwrap();
+    let proposal = env.program_account::<Proposal>(&bond.proposal(first)).await;
+    assert_eq!(proposal.votes_against, 2);
+}
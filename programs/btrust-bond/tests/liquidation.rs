@@ -0,0 +1,69 @@
+mod common;
+
+use btrust_bond::BtrustError;
+use common::*;
+
+const PRICE: i64 = 200_000_000;
+const EXPO: i32 = -8;
+const COLLATERAL: u64 = 7_500_000_000;
+
+#[tokio::test]
+async fn liquidation_repays_at_most_the_close_factor_for_discounted_collateral() {
+    let mut env = TestEnv::new().await;
+    let feed = env.create_price_feed(PRICE, EXPO).await;
+    env.set_collateral_oracle(&feed).await.unwrap();
+    let args = env.secured_bond_args();
+    let bond = env.create_secured_bond(args, COLLATERAL).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    let liquidator = env.create_user(100 * FACE_VALUE).await;
+    let collateral_mint = env.collateral_mint;
+    let liquidator_collateral = env.create_token_account(&liquidator.pubkey(), &collateral_mint).await;
+    
+    env.purchase(&bond, &alice, 10).await.unwrap();
+    assert_program_error(
+        env.liquidate(&bond, &liquidator, &liquidator_collateral, FACE_VALUE).await,
+        BtrustError::NotLiquidatable,
+    );
+    
+    // Collateral halves to 7.5 payment tokens against 10 of debt, under the 120% threshold
+    let now = env.now;
+    env.set_price(&feed, PRICE / 2, EXPO, 0, now).await;
+    assert!(env.liquidate(&bond, &liquidator, &liquidator.payment, FACE_VALUE).await.is_err());
+    
+    // Repayment is capped at half the debt, paid for with collateral worth 110% of it
+    env.liquidate(&bond, &liquidator, &liquidator_collateral, 8 * FACE_VALUE).await.unwrap();
+    assert_eq!(env.token_balance(&liquidator.payment).await, 95 * FACE_VALUE);
+    assert_eq!(env.token_balance(&liquidator_collateral).await, 5_500_000_000);
+    assert_eq!(env.token_balance(&bond.vault(b"redemption_vault")).await, 5 * FACE_VALUE);
+    let state = env.bond(&bond).await;
+    assert_eq!(state.principal_repaid, 5 * FACE_VALUE);
+    assert_eq!(state.collateral_deposited, 2_000_000_000);
+    assert!(state.is_active);
+    
+    // The remaining 2 tokens of collateral cannot cover the next close factor, so the repayment shrinks
+    env.liquidate(&bond, &liquidator, &liquidator_collateral, 8 * FACE_VALUE).await.unwrap();
+    let repaid = 2 * FACE_VALUE * 10_000 / 11_000;
+    assert_eq!(env.token_balance(&liquidator.payment).await, 95 * FACE_VALUE - repaid);
+    assert_eq!(env.token_balance(&liquidator_collateral).await, COLLATERAL);
+    let state = env.bond(&bond).await;
+    assert_eq!(state.principal_repaid, 5 * FACE_VALUE + repaid);
+    assert_eq!(state.collateral_deposited, 0);
+    assert!(state.is_liquidated);
+    assert!(!state.is_active);
+}
+
+#[tokio::test]
+async fn unsecured_bonds_cannot_be_liquidated() {
+    let mut env = TestEnv::new().await;
+    let args = env.bond_args();
+    let bond = env.create_bond(args).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    let collateral_mint = env.collateral_mint;
+    let alice_collateral = env.create_token_account(&alice.pubkey(), &collateral_mint).await;
+    
+    env.purchase(&bond, &alice, 10).await.unwrap();
+    assert_program_error(
+        env.liquidate(&bond, &alice, &alice_collateral, FACE_VALUE).await,
+        BtrustError::NotLiquidatable,
+    );
+}
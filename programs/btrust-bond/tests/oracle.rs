@@ -0,0 +1,90 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use btrust_bond::BtrustError;
+use common::*;
+
+/// Two payment tokens per collateral token at a Pyth exponent of -8
+const PRICE: i64 = 200_000_000;
+const EXPO: i32 = -8;
+/// 7.5 collateral tokens (9 decimals), worth 15 payment tokens at `PRICE`
+const COLLATERAL: u64 = 7_500_000_000;
+
+async fn secured_bond(env: &mut TestEnv) -> (TestBond, Pubkey) {
+    let feed = env.create_price_feed(PRICE, EXPO).await;
+    env.set_collateral_oracle(&feed).await.unwrap();
+    let args = env.secured_bond_args();
+    (env.create_secured_bond(args, COLLATERAL).await, feed)
+}
+
+#[tokio::test]
+async fn collateral_is_valued_across_mint_decimals_and_price_exponents() {
+    let mut env = TestEnv::new().await;
+    let (bond, feed) = secured_bond(&mut env).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    
+    // 15 payment tokens of collateral cover exactly 10 bonds at 150%
+    env.purchase(&bond, &alice, 10).await.unwrap();
+    assert_program_error(env.purchase(&bond, &alice, 1).await, BtrustError::InsufficientCollateral);
+    
+    // The same price quoted at another exponent values the collateral identically
+    let now = env.now;
+    env.set_price(&feed, 200_000, -5, 0, now).await;
+    assert_program_error(env.purchase(&bond, &alice, 1).await, BtrustError::InsufficientCollateral);
+    
+    env.set_price(&feed, 300, -2, 0, now).await;
+    env.purchase(&bond, &alice, 5).await.unwrap();
+    assert_program_error(env.purchase(&bond, &alice, 1).await, BtrustError::InsufficientCollateral);
+}
+
+#[tokio::test]
+async fn stale_or_uncertain_prices_are_rejected() {
+    let mut env = TestEnv::new().await;
+    let (bond, feed) = secured_bond(&mut env).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    let now = env.now;
+    
+    env.set_price(&feed, PRICE, EXPO, 0, now - 61).await;
+    assert_program_error(env.purchase(&bond, &alice, 1).await, BtrustError::StaleOraclePrice);
+    
+    // Confidence is capped at 2% of the price
+    env.set_price(&feed, PRICE, EXPO, PRICE as u64 / 50 + 1, now).await;
+    assert_program_error(env.purchase(&bond, &alice, 1).await, BtrustError::OracleConfidenceTooWide);
+    
+    env.set_price(&feed, PRICE, EXPO, PRICE as u64 / 50, now - 60).await;
+    env.purchase(&bond, &alice, 1).await.unwrap();
+    
+    env.set_price(&feed, 0, EXPO, 0, now).await;
+    assert_program_error(env.purchase(&bond, &alice, 1).await, BtrustError::InvalidOraclePrice);
+}
+
+#[tokio::test]
+async fn only_live_pyth_feeds_can_be_registered() {
+    let mut env = TestEnv::new().await;
+    assert_program_error(env.set_collateral_oracle(&Pubkey::new_unique()).await, BtrustError::InvalidOracle);
+    
+    let feed = env.create_price_feed(PRICE, EXPO).await;
+    let now = env.now;
+    env.set_price(&feed, PRICE, EXPO, 0, now - 61).await;
+    assert_program_error(env.set_collateral_oracle(&feed).await, BtrustError::StaleOraclePrice);
+}
+
+#[tokio::test]
+async fn existing_bonds_follow_a_replaced_feed_once_synced() {
+    let mut env = TestEnv::new().await;
+    let (bond, old_feed) = secured_bond(&mut env).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    assert_program_error(env.sync_bond_oracle(&bond).await, BtrustError::OracleUnchanged);
+    
+    let new_feed = env.create_price_feed(PRICE, EXPO).await;
+    env.set_collateral_oracle(&new_feed).await.unwrap();
+    assert_eq!(env.bond(&bond).await.price_oracle, old_feed);
+    
+    // Anyone can re-point the bond at the registered feed
+    env.sync_bond_oracle(&bond).await.unwrap();
+    assert_eq!(env.bond(&bond).await.price_oracle, new_feed);
+    
+    let now = env.now;
+    env.set_price(&old_feed, PRICE, EXPO, 0, now - 61).await;
+    env.purchase(&bond, &alice, 1).await.unwrap();
+}
@@ -0,0 +1,68 @@
+mod common;
+
+use btrust_bond::{BtrustError, PAUSE_ALL, PAUSE_ISSUANCE, PAUSE_TRADING, PAUSE_YIELD};
+use common::*;
+
+#[tokio::test]
+async fn guardian_pause_halts_the_whole_platform() {
+    let mut env = TestEnv::new().await;
+    let args = env.bond_args();
+    let bond = env.create_bond(args).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    let guardian = env.create_user(0).await;
+    let outsider = env.create_user(0).await;
+    
+    env.set_guardian(guardian.pubkey()).await.unwrap();
+    assert_program_error(env.set_platform_paused(&outsider.keypair, true).await, BtrustError::Unauthorized);
+    env.set_platform_paused(&guardian.keypair, true).await.unwrap();
+    assert_program_error(env.purchase(&bond, &alice, 1).await, BtrustError::PlatformPaused);
+    
+    let authority = env.authority();
+    env.set_platform_paused(&authority, false).await.unwrap();
+    env.purchase(&bond, &alice, 1).await.unwrap();
+}
+
+#[tokio::test]
+async fn bond_pause_flags_only_halt_the_flagged_flows() {
+    let mut env = TestEnv::new().await;
+    let args = env.bond_args();
+    let bond = env.create_bond(args).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    let bob = env.create_user(0).await;
+    let authority = env.authority();
+    
+    env.purchase(&bond, &alice, 10).await.unwrap();
+    let created_at = env.bond(&bond).await.created_at;
+    env.warp_to(created_at + QUARTER + 1).await;
+    env.record_coupon(&bond, 0).await.unwrap();
+    env.deposit_yield(&bond, 0, 10 * COUPON_PER_BOND).await.unwrap();
+    
+    assert_program_error(env.set_bond_pause_flags(&authority, &bond, PAUSE_ALL + 1).await, BtrustError::InvalidPauseFlags);
+    env.set_bond_pause_flags(&authority, &bond, PAUSE_ISSUANCE | PAUSE_YIELD).await.unwrap();
+    assert_program_error(env.purchase(&bond, &alice, 1).await, BtrustError::BondPaused);
+    assert_program_error(env.claim_yield(&bond, &alice).await, BtrustError::BondPaused);
+    env.transfer_bond(&bond, &alice, &bob, 1).await.unwrap();
+    
+    env.set_bond_pause_flags(&authority, &bond, PAUSE_TRADING).await.unwrap();
+    assert_program_error(env.transfer_bond(&bond, &alice, &bob, 1).await, BtrustError::BondPaused);
+    env.claim_yield(&bond, &alice).await.unwrap();
+    env.purchase(&bond, &alice, 1).await.unwrap();
+}
+
+#[tokio::test]
+async fn sellers_can_cancel_orders_while_paused() {
+    let mut env = TestEnv::new().await;
+    let args = env.bond_args();
+    let bond = env.create_bond(args).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    let bob = env.create_user(100 * FACE_VALUE).await;
+    
+    env.purchase(&bond, &alice, 5).await.unwrap();
+    let order = env.create_sell_order(&bond, &alice, 5, FACE_VALUE).await;
+    
+    let authority = env.authority();
+    env.set_platform_paused(&authority, true).await.unwrap();
+    assert_program_error(env.fill_order(&bond, &bob, &order, 1).await, BtrustError::PlatformPaused);
+    env.cancel_order(&bond, &alice, &order).await.unwrap();
+    assert_eq!(env.position(&bond, &alice).await.escrowed_quantity, 0);
+}
@@ -0,0 +1,39 @@
+mod common;
+
+use btrust_bond::BtrustError;
+use common::*;
+
+#[tokio::test]
+async fn bonds_only_accept_their_payment_mint() {
+    let mut env = TestEnv::new().await;
+    let args = env.bond_args();
+    let bond = env.create_bond(args).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    
+    let fake_mint = env.create_mint(6).await;
+    let fake_payment = env.create_token_account(&alice.pubkey(), &fake_mint).await;
+    env.mint_to(&fake_mint, &fake_payment, 100 * FACE_VALUE).await;
+    let mallory = User {
+        keypair: alice.keypair.insecure_clone(),
+        payment: fake_payment,
+    };
+    
+    assert_program_error(env.purchase(&bond, &mallory, 1).await, BtrustError::InvalidPaymentMint);
+    env.purchase(&bond, &alice, 10).await.unwrap();
+    
+    let created_at = env.bond(&bond).await.created_at;
+    env.warp_to(created_at + QUARTER + 1).await;
+    env.record_coupon(&bond, 0).await.unwrap();
+    env.deposit_yield(&bond, 0, 10 * COUPON_PER_BOND).await.unwrap();
+    assert_program_error(env.claim_yield(&bond, &mallory).await, BtrustError::InvalidPaymentMint);
+    env.claim_yield(&bond, &alice).await.unwrap();
+}
+
+#[tokio::test]
+async fn payment_mint_account_must_match_the_terms() {
+    let mut env = TestEnv::new().await;
+    let issuer = env.create_user(0).await;
+    let mut args = env.bond_args();
+    args.payment_mint = env.create_mint(6).await;
+    assert_program_error(env.try_create_bond(args, &issuer).await, BtrustError::InvalidPaymentMint);
+}
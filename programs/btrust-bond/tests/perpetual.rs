@@ -0,0 +1,95 @@
+mod common;
+
+use btrust_bond::{BtrustError, CreateBondArgs};
+use common::*;
+
+fn perpetual_args(env: &TestEnv) -> CreateBondArgs {
+    let mut args = env.bond_args();
+    args.is_perpetual = true;
+    args.maturity_timestamp = 0;
+    args
+}
+
+#[tokio::test]
+async fn perpetual_bonds_pay_coupons_past_any_term() {
+    let mut env = TestEnv::new().await;
+    let mut args = perpetual_args(&env);
+    args.first_call_date = env.now + 4 * QUARTER;
+    args.call_schedule_bps[0] = 10_000;
+    let bond = env.create_bond(args).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    
+    env.purchase(&bond, &alice, 10).await.unwrap();
+    let state = env.bond(&bond).await;
+    assert!(state.is_perpetual);
+    assert_eq!(state.coupon_periods, u32::MAX);
+    
+    // Five coupons in, past where a one-year bond would have matured
+    let created_at = state.created_at;
+    env.warp_to(created_at + 5 * QUARTER + 1).await;
+    for period in 0..5 {
+        env.record_coupon(&bond, period).await.unwrap();
+        env.deposit_yield(&bond, period, 10 * COUPON_PER_BOND).await.unwrap();
+    }
+    env.claim_yield(&bond, &alice).await.unwrap();
+    assert_eq!(env.token_balance(&alice.payment).await, 90 * FACE_VALUE + 50 * COUPON_PER_BOND);
+    assert_program_error(env.redeem_bond(&bond, &alice, 10).await, BtrustError::BondNotMatured);
+    
+    // A call after the lockup is the issuer's only way out
+    let call_date = created_at + 6 * QUARTER;
+    env.call_bond(&bond, call_date).await.unwrap();
+    env.warp_to(call_date).await;
+    env.record_coupon(&bond, 5).await.unwrap();
+    env.deposit_yield(&bond, 5, 10 * COUPON_PER_BOND).await.unwrap();
+    env.redeem_bond(&bond, &alice, 10).await.unwrap();
+    assert_eq!(env.bond(&bond).await.outstanding_supply, 0);
+}
+
+#[tokio::test]
+async fn perpetual_terms_are_validated() {
+    let mut env = TestEnv::new().await;
+    let issuer = env.create_user(0).await;
+    
+    let mut args = perpetual_args(&env);
+    args.maturity_timestamp = env.now + QUARTER;
+    assert_program_error(env.try_create_bond(args, &issuer).await, BtrustError::InvalidMaturity);
+    
+    let mut args = perpetual_args(&env);
+    args.is_zero_coupon = true;
+    args.coupon_rate_bps = 0;
+    args.discount_yield_bps = 500;
+    assert_program_error(env.try_create_bond(args, &issuer).await, BtrustError::InvalidMaturity);
+    
+    let mut args = perpetual_args(&env);
+    args.amortization_bps = 1_000;
+    assert_program_error(env.try_create_bond(args, &issuer).await, BtrustError::InvalidAmortization);
+}
+
+#[tokio::test]
+async fn accelerated_perpetual_bonds_become_dated() {
+    let mut env = TestEnv::new().await;
+    let trustee = env.create_user(0).await;
+    let mut args = perpetual_args(&env);
+    args.trustee = trustee.pubkey();
+    let bond = env.create_bond(args).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    env.purchase(&bond, &alice, 10).await.unwrap();
+    
+    let created_at = env.bond(&bond).await.created_at;
+    env.warp_to(created_at + QUARTER).await;
+    env.record_coupon(&bond, 0).await.unwrap();
+    env.warp_to(created_at + QUARTER + 86_400 + 1).await;
+    env.trustee_accelerate(&bond, &trustee, Some(0)).await.unwrap();
+    
+    let state = env.bond(&bond).await;
+    assert!(!state.is_perpetual);
+    assert!(state.is_accelerated);
+    assert_eq!(state.maturity_timestamp, env.now);
+    assert_eq!(state.coupon_periods, 2);
+    
+    // Principal is due now and redeems once escrowed
+    env.record_coupon(&bond, 1).await.unwrap();
+    env.fund_redemption(&bond, 10 * FACE_VALUE).await.unwrap();
+    env.redeem_bond(&bond, &alice, 10).await.unwrap();
+    assert_eq!(env.token_balance(&alice.payment).await, 100 * FACE_VALUE);
+}
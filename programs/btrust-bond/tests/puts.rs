@@ -0,0 +1,78 @@
+mod common;
+
+use btrust_bond::BtrustError;
+use common::*;
+
+const PUT_OPENS: i64 = 10 * 86_400;
+const PUT_WINDOW: i64 = 5 * 86_400;
+
+/// Par put window ten days in; Alice holds 3 bonds and Bob 2
+async fn putable_bond(env: &mut TestEnv) -> (TestBond, User, User) {
+    let mut args = env.bond_args();
+    args.put_dates[0] = env.now + PUT_OPENS;
+    args.put_window_length = PUT_WINDOW;
+    args.put_price_bps = 10_000;
+    let bond = env.create_bond(args).await;
+    
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    let bob = env.create_user(100 * FACE_VALUE).await;
+    env.purchase(&bond, &alice, 3).await.unwrap();
+    env.purchase(&bond, &bob, 2).await.unwrap();
+    (bond, alice, bob)
+}
+
+#[tokio::test]
+async fn funded_puts_pay_out_after_the_window_closes() {
+    let mut env = TestEnv::new().await;
+    let (bond, alice, bob) = putable_bond(&mut env).await;
+    let created_at = env.bond(&bond).await.created_at;
+    env.fund_redemption(&bond, 5 * FACE_VALUE).await.unwrap();
+    
+    assert_program_error(env.exercise_put(&bond, &alice, 3).await, BtrustError::PutWindowClosed);
+    env.warp_to(created_at + PUT_OPENS).await;
+    env.exercise_put(&bond, &alice, 3).await.unwrap();
+    env.exercise_put(&bond, &bob, 2).await.unwrap();
+    
+    // Tendered bonds are locked in the put escrow
+    assert_program_error(env.transfer_bond(&bond, &alice, &bob, 1).await, BtrustError::InsufficientBalance);
+    assert_program_error(env.claim_put(&bond, &alice).await, BtrustError::PutWindowOpen);
+    
+    env.warp_to(created_at + PUT_OPENS + PUT_WINDOW).await;
+    let alice_before = env.token_balance(&alice.payment).await;
+    env.claim_put(&bond, &alice).await.unwrap();
+    assert_eq!(env.token_balance(&alice.payment).await - alice_before, 3 * FACE_VALUE);
+    env.claim_put(&bond, &bob).await.unwrap();
+    assert_program_error(env.claim_put(&bond, &bob).await, BtrustError::NothingToRedeem);
+    
+    let bond_state = env.bond(&bond).await;
+    assert_eq!(bond_state.outstanding_supply, 0);
+    assert!(bond_state.is_active);
+}
+
+#[tokio::test]
+async fn underfunded_puts_default_before_anyone_is_paid() {
+    let mut env = TestEnv::new().await;
+    let (bond, alice, bob) = putable_bond(&mut env).await;
+    let created_at = env.bond(&bond).await.created_at;
+    
+    // Enough for Alice's tender alone, not for every put due
+    env.fund_redemption(&bond, 4 * FACE_VALUE).await.unwrap();
+    env.warp_to(created_at + PUT_OPENS).await;
+    env.exercise_put(&bond, &alice, 3).await.unwrap();
+    env.exercise_put(&bond, &bob, 2).await.unwrap();
+    env.warp_to(created_at + PUT_OPENS + PUT_WINDOW).await;
+    
+    // The issuer still has the grace period to fund the shortfall
+    assert_program_error(env.claim_put(&bond, &alice).await, BtrustError::PutPaymentPending);
+    env.warp_to(created_at + PUT_OPENS + PUT_WINDOW + 86_400 + 1).await;
+    
+    let alice_before = env.token_balance(&alice.payment).await;
+    env.claim_put(&bond, &alice).await.unwrap();
+    assert_eq!(env.token_balance(&alice.payment).await, alice_before);
+    assert_eq!(env.token_balance(&bond.bond_account(&alice.pubkey())).await, 3);
+    assert!(env.bond(&bond).await.is_defaulted);
+    
+    env.claim_put(&bond, &bob).await.unwrap();
+    assert_eq!(env.token_balance(&bond.bond_account(&bob.pubkey())).await, 2);
+    assert_eq!(env.bond(&bond).await.put_due, 0);
+}
@@ -0,0 +1,70 @@
+mod common;
+
+use btrust_bond::BtrustError;
+use common::*;
+
+/// Alice and Bob hold 6 and 4 bonds. Period 0 is paid but only Alice claims
+/// it; period 1 is recorded with 100_000 of its 250_000 deposited and the
+/// bond defaults.
+async fn defaulted_bond(env: &mut TestEnv) -> (TestBond, User, User) {
+    let args = env.bond_args();
+    let bond = env.create_bond(args).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    let bob = env.create_user(100 * FACE_VALUE).await;
+    env.purchase(&bond, &alice, 6).await.unwrap();
+    env.purchase(&bond, &bob, 4).await.unwrap();
+    
+    let created_at = env.bond(&bond).await.created_at;
+    env.warp_to(created_at + QUARTER + 1).await;
+    env.record_coupon(&bond, 0).await.unwrap();
+    env.deposit_yield(&bond, 0, 10 * COUPON_PER_BOND).await.unwrap();
+    env.claim_yield(&bond, &alice).await.unwrap();
+    
+    env.deposit_yield(&bond, 1, 100_000).await.unwrap();
+    env.warp_to(created_at + 2 * QUARTER + 1).await;
+    env.record_coupon(&bond, 1).await.unwrap();
+    assert_program_error(env.mark_default(&bond, 1).await, BtrustError::GracePeriodNotElapsed);
+    env.warp_to(created_at + 2 * QUARTER + 2 * 86_400).await;
+    env.mark_default(&bond, 1).await.unwrap();
+    
+    (bond, alice, bob)
+}
+
+#[tokio::test]
+async fn recovery_pays_settled_coupons_before_splitting_the_vault() {
+    let mut env = TestEnv::new().await;
+    let (bond, alice, bob) = defaulted_bond(&mut env).await;
+    
+    // 200_000 left against 350_000 owed: Alice 150_000 for period 1, Bob
+    // 100_000 for period 0 plus 100_000 for period 1
+    let yield_vault = bond.vault(b"yield_vault");
+    assert_eq!(env.token_balance(&yield_vault).await, 200_000);
+    assert_eq!(env.bond(&bond).await.yield_owed, 350_000);
+    
+    let alice_before = env.token_balance(&alice.payment).await;
+    env.claim_recovery(&bond, &alice, 6).await.unwrap();
+    assert_eq!(env.token_balance(&alice.payment).await - alice_before, 200_000 * 150_000 / 350_000);
+    
+    // Bob takes the rest of his entitlement without burning, then burns for nothing more
+    let bob_before = env.token_balance(&bob.payment).await;
+    env.claim_recovery(&bond, &bob, 0).await.unwrap();
+    assert_eq!(env.token_balance(&yield_vault).await, 0);
+    assert_eq!(env.token_balance(&bob.payment).await - bob_before, 200_000 - 200_000 * 150_000 / 350_000);
+    assert_program_error(env.claim_recovery(&bond, &bob, 0).await, BtrustError::InvalidAmount);
+    
+    env.claim_recovery(&bond, &bob, 4).await.unwrap();
+    let bond_state = env.bond(&bond).await;
+    assert_eq!(bond_state.outstanding_supply, 0);
+    assert_eq!(bond_state.yield_owed, 0);
+}
+
+#[tokio::test]
+async fn recovery_claims_ignore_pending_record_dates() {
+    let mut env = TestEnv::new().await;
+    let (bond, alice, _bob) = defaulted_bond(&mut env).await;
+    
+    let created_at = env.bond(&bond).await.created_at;
+    env.warp_to(created_at + 3 * QUARTER + 1).await;
+    env.claim_recovery(&bond, &alice, 6).await.unwrap();
+    assert_program_error(env.claim_recovery(&bond, &alice, 1).await, BtrustError::InsufficientBalance);
+}
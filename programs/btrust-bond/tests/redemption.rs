@@ -0,0 +1,39 @@
+mod common;
+
+use btrust_bond::BtrustError;
+use common::*;
+
+#[tokio::test]
+async fn escrowed_principal_pays_out_at_maturity() {
+    let mut env = TestEnv::new().await;
+    let args = env.bond_args();
+    let bond = env.create_bond(args).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    
+    env.purchase(&bond, &alice, 10).await.unwrap();
+    assert_program_error(env.fund_redemption(&bond, 10 * FACE_VALUE + 1).await, BtrustError::ExceedsOutstandingDebt);
+    env.fund_redemption(&bond, 6 * FACE_VALUE).await.unwrap();
+    assert_eq!(env.token_balance(&bond.vault(b"redemption_vault")).await, 6 * FACE_VALUE);
+    assert_eq!(env.bond(&bond).await.principal_repaid, 6 * FACE_VALUE);
+    assert_program_error(env.redeem_bond(&bond, &alice, 1).await, BtrustError::BondNotMatured);
+    
+    let maturity = env.bond(&bond).await.maturity_timestamp;
+    env.warp_to(maturity).await;
+    for period in 0..4 {
+        env.record_coupon(&bond, period).await.unwrap();
+    }
+    env.redeem_bond(&bond, &alice, 6).await.unwrap();
+    assert_eq!(env.token_balance(&alice.payment).await, 96 * FACE_VALUE);
+    assert_eq!(env.token_balance(&bond.bond_account(&alice.pubkey())).await, 4);
+    
+    // The vault is empty until the rest of the principal is escrowed
+    assert!(env.redeem_bond(&bond, &alice, 4).await.is_err());
+    env.fund_redemption(&bond, 4 * FACE_VALUE).await.unwrap();
+    env.redeem_bond(&bond, &alice, 4).await.unwrap();
+    
+    let state = env.bond(&bond).await;
+    assert_eq!(state.outstanding_supply, 0);
+    assert_eq!(state.principal_repaid, 0);
+    assert!(state.is_matured);
+    assert!(!state.is_active);
+}
@@ -0,0 +1,242 @@
+mod common;
+
+use btrust_bond::BtrustError;
+use common::*;
+
+async fn sinking_fund_bond(env: &mut TestEnv, per_period: u64) -> TestBond {
+    let mut args = env.bond_args();
+    args.sinking_fund_per_period = per_period;
+    env.create_bond(args).await
+}
+
+#[tokio::test]
+async fn draws_follow_the_holders_at_draw_time() {
+    let mut env = TestEnv::new().await;
+    let bond = sinking_fund_bond(&mut env, 0).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    let bob = env.create_user(100 * FACE_VALUE).await;
+    let carol = env.create_user(0).await;
+    env.purchase(&bond, &alice, 6).await.unwrap();
+    env.purchase(&bond, &bob, 4).await.unwrap();
+    
+    env.fund_sinking_fund(&bond, 5 * FACE_VALUE).await.unwrap();
+    assert_program_error(env.draw_sinking_fund(&bond, 11).await, BtrustError::ExceedsSupply);
+    assert_program_error(env.draw_sinking_fund(&bond, 6).await, BtrustError::InsufficientSinkingFund);
+    env.draw_sinking_fund(&bond, 5).await.unwrap();
+    assert_program_error(env.reveal_sinking_draw(&bond).await, BtrustError::SinkingDrawNotRevealable);
+    env.reveal_sinking_draw(&bond).await.unwrap();
+    assert_program_error(env.reveal_sinking_draw(&bond).await, BtrustError::NoSinkingDrawCommitted);
+    assert_program_error(env.draw_sinking_fund(&bond, 1).await, BtrustError::SinkingDrawInProgress);
+    
+    // Half of Alice's bonds are drawn and stay with her
+    assert_program_error(env.transfer_bond(&bond, &alice, &carol, 4).await, BtrustError::InsufficientBalance);
+    env.transfer_bond(&bond, &alice, &carol, 3).await.unwrap();
+    assert_program_error(env.redeem_sinking_fund_draw(&bond, &carol).await, BtrustError::NothingToRedeem);
+    
+    let bob_before = env.token_balance(&bob.payment).await;
+    env.redeem_sinking_fund_draw(&bond, &bob).await.unwrap();
+    assert_eq!(env.token_balance(&bob.payment).await - bob_before, 2 * FACE_VALUE);
+    
+    let alice_before = env.token_balance(&alice.payment).await;
+    env.redeem_sinking_fund_draw(&bond, &alice).await.unwrap();
+    assert_eq!(env.token_balance(&alice.payment).await - alice_before, 3 * FACE_VALUE);
+    assert_program_error(env.redeem_sinking_fund_draw(&bond, &alice).await, BtrustError::NothingToRedeem);
+    
+    let bond_state = env.bond(&bond).await;
+    assert_eq!(bond_state.outstanding_supply, 5);
+    assert_eq!(bond_state.sinking_drawn_pending, 0);
+    assert_eq!(bond_state.sinking_draw_remaining, 0);
+    assert_eq!(bond_state.sinking_fund_retired, 5);
+}
+
+#[tokio::test]
+async fn uneven_draws_are_allocated_in_full() {
+    let mut env = TestEnv::new().await;
+    let bond = sinking_fund_bond(&mut env, 0).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    let bob = env.create_user(100 * FACE_VALUE).await;
+    let carol = env.create_user(100 * FACE_VALUE).await;
+    env.purchase(&bond, &alice, 3).await.unwrap();
+    env.purchase(&bond, &bob, 3).await.unwrap();
+    env.purchase(&bond, &carol, 1).await.unwrap();
+    
+    env.fund_sinking_fund(&bond, 4 * FACE_VALUE).await.unwrap();
+    env.draw_sinking_fund(&bond, 2).await.unwrap();
+    assert_program_error(env.settle_sinking_draw(&bond, &alice).await, BtrustError::SinkingDrawNotRevealed);
+    env.reveal_sinking_draw(&bond).await.unwrap();
+    
+    let mut drawn = 0;
+    for holder in [&alice, &bob, &carol] {
+        env.settle_sinking_draw(&bond, holder).await.unwrap();
+        assert_program_error(env.settle_sinking_draw(&bond, holder).await, BtrustError::SinkingDrawSettled);
+        drawn += env.position(&bond, holder).await.drawn_quantity;
+    }
+    assert_eq!(drawn, 2);
+    
+    let bond_state = env.bond(&bond).await;
+    assert_eq!(bond_state.sinking_drawn_pending, 2);
+    assert_eq!(bond_state.sinking_draw_remaining, 0);
+    
+    // Bonds already drawn sit out the next draw
+    assert_program_error(env.draw_sinking_fund(&bond, 6).await, BtrustError::ExceedsSupply);
+    env.draw_sinking_fund(&bond, 2).await.unwrap();
+}
+
+#[tokio::test]
+async fn unallocated_draws_expire() {
+    let mut env = TestEnv::new().await;
+    let bond = sinking_fund_bond(&mut env, 0).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    let bob = env.create_user(100 * FACE_VALUE).await;
+    env.purchase(&bond, &alice, 6).await.unwrap();
+    env.purchase(&bond, &bob, 4).await.unwrap();
+    
+    env.fund_sinking_fund(&bond, 5 * FACE_VALUE).await.unwrap();
+    env.draw_sinking_fund(&bond, 5).await.unwrap();
+    assert_program_error(env.reveal_sinking_draw(&bond).await, BtrustError::SinkingDrawNotRevealable);
+    env.reveal_sinking_draw(&bond).await.unwrap();
+    env.settle_sinking_draw(&bond, &bob).await.unwrap();
+    
+    // Alice never settles, which holds back her share until the draw expires
+    assert_program_error(env.draw_sinking_fund(&bond, 1).await, BtrustError::SinkingDrawInProgress);
+    env.warp_to(env.now + 30 * 86_400).await;
+    env.draw_sinking_fund(&bond, 3).await.unwrap();
+    
+    let bond_state = env.bond(&bond).await;
+    assert_eq!(bond_state.sinking_drawn_pending, 2);
+    assert_eq!(bond_state.sinking_draw_remaining, 3);
+}
+
+#[tokio::test]
+async fn escrowed_bonds_sit_out_draws() {
+    let mut env = TestEnv::new().await;
+    let bond = sinking_fund_bond(&mut env, 0).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    let bob = env.create_user(100 * FACE_VALUE).await;
+    env.purchase(&bond, &alice, 4).await.unwrap();
+    env.purchase(&bond, &bob, 4).await.unwrap();
+    let order = env.create_sell_order(&bond, &alice, 3, FACE_VALUE * 2).await;
+    
+    env.fund_sinking_fund(&bond, 8 * FACE_VALUE).await.unwrap();
+    assert_program_error(env.draw_sinking_fund(&bond, 6).await, BtrustError::ExceedsSupply);
+    env.draw_sinking_fund(&bond, 5).await.unwrap();
+    assert_program_error(env.reveal_sinking_draw(&bond).await, BtrustError::SinkingDrawNotRevealable);
+    env.reveal_sinking_draw(&bond).await.unwrap();
+    
+    // Only Alice's bond outside the order and Bob's four can be drawn
+    env.settle_sinking_draw(&bond, &alice).await.unwrap();
+    env.settle_sinking_draw(&bond, &bob).await.unwrap();
+    assert_eq!(env.position(&bond, &alice).await.drawn_quantity, 1);
+    assert_eq!(env.position(&bond, &bob).await.drawn_quantity, 4);
+    
+    env.cancel_order(&bond, &alice, &order).await.unwrap();
+    env.redeem_sinking_fund_draw(&bond, &alice).await.unwrap();
+    assert_eq!(env.position(&bond, &alice).await.quantity, 3);
+    assert_eq!(env.bond(&bond).await.escrowed_supply, 0);
+}
+
+#[tokio::test]
+async fn retirement_buys_from_the_head_of_the_queue() {
+    let mut env = TestEnv::new().await;
+    let bond = sinking_fund_bond(&mut env, 0).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    env.purchase(&bond, &alice, 8).await.unwrap();
+    env.fund_sinking_fund(&bond, 5 * FACE_VALUE).await.unwrap();
+    
+    let dear = env.create_sell_order(&bond, &alice, 2, FACE_VALUE * 95 / 100).await;
+    let above_par = env.create_sell_order(&bond, &alice, 1, FACE_VALUE * 11 / 10).await;
+    let cheap = env.create_sell_order(&bond, &alice, 2, FACE_VALUE * 9 / 10).await;
+    assert_eq!(env.bond(&bond).await.retire_queue_head, cheap.order);
+    
+    assert_program_error(
+        env.retire_from_sinking_fund(&bond, &dear, 2).await,
+        BtrustError::NotLowestPricedOrder,
+    );
+    assert_program_error(
+        env.retire_from_sinking_fund(&bond, &above_par, 1).await,
+        BtrustError::NotLowestPricedOrder,
+    );
+    
+    let alice_before = env.token_balance(&alice.payment).await;
+    env.retire_from_sinking_fund(&bond, &cheap, 2).await.unwrap();
+    assert_eq!(env.token_balance(&alice.payment).await - alice_before, 2 * FACE_VALUE * 9 / 10);
+    assert_eq!(env.bond(&bond).await.retire_queue_head, dear.order);
+    
+    // A cancelled order holds up the queue until it is pruned
+    let mid = env.create_sell_order(&bond, &alice, 2, FACE_VALUE * 92 / 100).await;
+    env.cancel_order(&bond, &alice, &mid).await.unwrap();
+    assert_program_error(
+        env.retire_from_sinking_fund(&bond, &dear, 1).await,
+        BtrustError::NotLowestPricedOrder,
+    );
+    assert_program_error(env.prune_retire_queue(&bond, &[&dear]).await, BtrustError::InvalidRetireQueue);
+    env.prune_retire_queue(&bond, &[&mid]).await.unwrap();
+    env.retire_from_sinking_fund(&bond, &dear, 1).await.unwrap();
+    
+    let bond_state = env.bond(&bond).await;
+    assert_eq!(bond_state.outstanding_supply, 5);
+    assert_eq!(bond_state.retire_queue_head, dear.order);
+}
+
+#[tokio::test]
+async fn missed_sinking_fund_deposits_can_be_defaulted() {
+    let mut env = TestEnv::new().await;
+    let bond = sinking_fund_bond(&mut env, FACE_VALUE).await;
+    let created_at = env.bond(&bond).await.created_at;
+    
+    // The first deposit is due a grace period after the first record date
+    env.warp_to(created_at + QUARTER + 1).await;
+    assert_program_error(env.mark_sinking_fund_default(&bond).await, BtrustError::SinkingFundOnSchedule);
+    
+    env.warp_to(created_at + QUARTER + 86_400 + 1).await;
+    env.fund_sinking_fund(&bond, FACE_VALUE).await.unwrap();
+    assert_program_error(env.mark_sinking_fund_default(&bond).await, BtrustError::SinkingFundOnSchedule);
+    
+    env.warp_to(created_at + 2 * QUARTER + 86_400 + 1).await;
+    env.mark_sinking_fund_default(&bond).await.unwrap();
+    assert!(env.bond(&bond).await.is_defaulted);
+}
+
+#[tokio::test]
+async fn recovery_shares_out_the_sinking_fund() {
+    let mut env = TestEnv::new().await;
+    let bond = sinking_fund_bond(&mut env, FACE_VALUE).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    let bob = env.create_user(100 * FACE_VALUE).await;
+    env.purchase(&bond, &alice, 3).await.unwrap();
+    env.purchase(&bond, &bob, 1).await.unwrap();
+    env.fund_sinking_fund(&bond, FACE_VALUE).await.unwrap();
+    
+    let created_at = env.bond(&bond).await.created_at;
+    env.warp_to(created_at + 2 * QUARTER + 86_400 + 1).await;
+    env.mark_sinking_fund_default(&bond).await.unwrap();
+    
+    let alice_before = env.token_balance(&alice.payment).await;
+    env.claim_recovery(&bond, &alice, 3).await.unwrap();
+    assert_eq!(env.token_balance(&alice.payment).await - alice_before, FACE_VALUE * 3 / 4);
+    let bob_before = env.token_balance(&bob.payment).await;
+    env.claim_recovery(&bond, &bob, 1).await.unwrap();
+    assert_eq!(env.token_balance(&bob.payment).await - bob_before, FACE_VALUE / 4);
+    assert_eq!(env.token_balance(&bond.vault(b"sinking_fund")).await, 0);
+}
+
+#[tokio::test]
+async fn issuers_withdraw_the_surplus_once_every_bond_is_retired() {
+    let mut env = TestEnv::new().await;
+    let bond = sinking_fund_bond(&mut env, 0).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    env.purchase(&bond, &alice, 2).await.unwrap();
+    env.fund_sinking_fund(&bond, 3 * FACE_VALUE).await.unwrap();
+    assert_program_error(env.withdraw_sinking_fund(&bond).await, BtrustError::BondsOutstanding);
+    
+    let order = env.create_sell_order(&bond, &alice, 2, FACE_VALUE * 9 / 10).await;
+    env.retire_from_sinking_fund(&bond, &order, 2).await.unwrap();
+    
+    let issuer_before = env.token_balance(&bond.issuer.payment).await;
+    env.withdraw_sinking_fund(&bond).await.unwrap();
+    assert_eq!(
+        env.token_balance(&bond.issuer.payment).await - issuer_before,
+        3 * FACE_VALUE - 2 * FACE_VALUE * 9 / 10,
+    );
+    assert_program_error(env.withdraw_sinking_fund(&bond).await, BtrustError::NothingToReclaim);
+}
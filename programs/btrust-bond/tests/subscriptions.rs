@@ -0,0 +1,85 @@
+mod common;
+
+use btrust_bond::BtrustError;
+use common::*;
+
+const SUBSCRIPTION_LENGTH: i64 = 7 * 86_400;
+
+async fn subscription_bond(env: &mut TestEnv, soft_cap: u64) -> TestBond {
+    let mut args = env.bond_args();
+    args.subscription_start = env.now;
+    args.subscription_end = env.now + SUBSCRIPTION_LENGTH;
+    args.soft_cap = soft_cap;
+    env.create_bond(args).await
+}
+
+#[tokio::test]
+async fn subscribers_are_refunded_when_the_soft_cap_is_missed() {
+    let mut env = TestEnv::new().await;
+    let bond = subscription_bond(&mut env, 10 * FACE_VALUE).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    
+    env.purchase(&bond, &alice, 3).await.unwrap();
+    assert_eq!(env.token_balance(&alice.payment).await, 97 * FACE_VALUE);
+    assert_program_error(env.refund_subscription(&bond, &alice).await, BtrustError::SubscriptionOpen);
+    
+    env.warp_to(env.now + SUBSCRIPTION_LENGTH).await;
+    assert_program_error(env.settle_subscription(&bond).await, BtrustError::SoftCapNotMet);
+    
+    env.refund_subscription(&bond, &alice).await.unwrap();
+    assert_eq!(env.token_balance(&alice.payment).await, 100 * FACE_VALUE);
+    assert_eq!(env.token_balance(&bond.bond_account(&alice.pubkey())).await, 0);
+    assert_eq!(env.bond(&bond).await.outstanding_supply, 0);
+    assert_program_error(env.refund_subscription(&bond, &alice).await, BtrustError::NothingToRefund);
+}
+
+#[tokio::test]
+async fn anyone_can_settle_a_successful_subscription() {
+    let mut env = TestEnv::new().await;
+    let bond = subscription_bond(&mut env, 2 * FACE_VALUE).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    
+    env.purchase(&bond, &alice, 4).await.unwrap();
+    assert_program_error(env.settle_subscription(&bond).await, BtrustError::SubscriptionOpen);
+    
+    env.warp_to(env.now + SUBSCRIPTION_LENGTH).await;
+    assert_program_error(env.refund_subscription(&bond, &alice).await, BtrustError::SoftCapMet);
+    
+    // Settled by the test payer, not the issuer
+    let issuer_before = env.token_balance(&bond.issuer.payment).await;
+    env.settle_subscription(&bond).await.unwrap();
+    let fee = 4 * FACE_VALUE * 50 / 10_000;
+    assert_eq!(env.token_balance(&bond.issuer.payment).await - issuer_before, 4 * FACE_VALUE - fee);
+    let treasury = env.treasury_payment;
+    assert_eq!(env.token_balance(&treasury).await, fee);
+    assert_program_error(env.settle_subscription(&bond).await, BtrustError::SubscriptionSettled);
+}
+
+#[tokio::test]
+async fn refunds_stay_open_while_issuance_is_paused() {
+    let mut env = TestEnv::new().await;
+    let bond = subscription_bond(&mut env, 10 * FACE_VALUE).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    
+    env.purchase(&bond, &alice, 3).await.unwrap();
+    env.warp_to(env.now + SUBSCRIPTION_LENGTH).await;
+    
+    let authority = env.authority();
+    env.set_platform_paused(&authority, true).await.unwrap();
+    env.refund_subscription(&bond, &alice).await.unwrap();
+    assert_eq!(env.token_balance(&alice.payment).await, 100 * FACE_VALUE);
+}
+
+#[tokio::test]
+async fn failed_offerings_record_no_coupons_and_stay_refundable() {
+    let mut env = TestEnv::new().await;
+    let bond = subscription_bond(&mut env, 10 * FACE_VALUE).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    
+    env.purchase(&bond, &alice, 3).await.unwrap();
+    env.warp_to(env.now + 2 * QUARTER).await;
+    
+    assert_program_error(env.record_coupon(&bond, 0).await, BtrustError::SubscriptionNotSettled);
+    env.refund_subscription(&bond, &alice).await.unwrap();
+    assert_eq!(env.token_balance(&alice.payment).await, 100 * FACE_VALUE);
+}
@@ -0,0 +1,155 @@
+mod common;
+
+use anchor_spl::associated_token::get_associated_token_address;
+use btrust_bond::BtrustError;
+use common::*;
+
+async fn trustee_bond(env: &mut TestEnv, trustee: &User) -> TestBond {
+    let mut args = env.bond_args();
+    args.trustee = trustee.pubkey();
+    env.create_bond(args).await
+}
+
+#[tokio::test]
+async fn trustee_enforcement_needs_a_breach() {
+    let mut env = TestEnv::new().await;
+    let trustee = env.create_user(0).await;
+    let bond = trustee_bond(&mut env, &trustee).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    env.purchase(&bond, &alice, 10).await.unwrap();
+    
+    assert_program_error(env.trustee_accelerate(&bond, &trustee, None).await, BtrustError::NoCovenantBreach);
+    assert_program_error(env.trustee_declare_default(&bond, &trustee, None).await, BtrustError::NoCovenantBreach);
+    assert_program_error(env.trustee_accelerate(&bond, &alice, None).await, BtrustError::Unauthorized);
+    
+    // A coupon recorded but not yet funded is only missed after the grace period
+    let created_at = env.bond(&bond).await.created_at;
+    env.warp_to(created_at + QUARTER).await;
+    env.record_coupon(&bond, 0).await.unwrap();
+    assert_program_error(env.trustee_accelerate(&bond, &trustee, Some(0)).await, BtrustError::NoCovenantBreach);
+    
+    env.warp_to(created_at + QUARTER + 86_400 + 1).await;
+    env.trustee_accelerate(&bond, &trustee, Some(0)).await.unwrap();
+    let bond_state = env.bond(&bond).await;
+    assert!(bond_state.is_accelerated);
+    assert_eq!(bond_state.maturity_timestamp, env.now);
+}
+
+#[tokio::test]
+async fn unfunded_accelerated_principal_defaults() {
+    let mut env = TestEnv::new().await;
+    let trustee = env.create_user(0).await;
+    let bond = trustee_bond(&mut env, &trustee).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    env.purchase(&bond, &alice, 10).await.unwrap();
+    
+    let created_at = env.bond(&bond).await.created_at;
+    env.warp_to(created_at + QUARTER).await;
+    env.record_coupon(&bond, 0).await.unwrap();
+    env.warp_to(created_at + QUARTER + 86_400 + 1).await;
+    env.trustee_accelerate(&bond, &trustee, Some(0)).await.unwrap();
+    env.deposit_yield(&bond, 0, 10 * COUPON_PER_BOND).await.unwrap();
+    
+    // Principal is now due, with the grace period to fund it
+    assert_program_error(env.mark_principal_default(&bond).await, BtrustError::PrincipalNotOverdue);
+    let accelerated_at = env.bond(&bond).await.maturity_timestamp;
+    env.warp_to(accelerated_at + 86_400 + 1).await;
+    env.mark_principal_default(&bond).await.unwrap();
+    assert!(env.bond(&bond).await.is_defaulted);
+    
+    assert_program_error(env.trustee_declare_default(&bond, &trustee, None).await, BtrustError::BondNotActive);
+}
+
+#[tokio::test]
+async fn funded_principal_is_not_defaultable() {
+    let mut env = TestEnv::new().await;
+    let trustee = env.create_user(0).await;
+    let bond = trustee_bond(&mut env, &trustee).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    env.purchase(&bond, &alice, 10).await.unwrap();
+    env.fund_redemption(&bond, 10 * FACE_VALUE).await.unwrap();
+    
+    let maturity = env.bond(&bond).await.maturity_timestamp;
+    env.warp_to(maturity + 86_400 + 1).await;
+    assert_program_error(env.mark_principal_default(&bond).await, BtrustError::PrincipalNotOverdue);
+    assert_program_error(env.trustee_declare_default(&bond, &trustee, None).await, BtrustError::NoCovenantBreach);
+}
+
+#[tokio::test]
+async fn trustee_freeze_is_separate_from_pause_flags() {
+    let mut env = TestEnv::new().await;
+    let trustee = env.create_user(0).await;
+    let bond = trustee_bond(&mut env, &trustee).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    let bob = env.create_user(0).await;
+    env.purchase(&bond, &alice, 10).await.unwrap();
+    assert_program_error(
+        env.trustee_set_trading_frozen(&bond, &trustee, None, true).await,
+        BtrustError::NoCovenantBreach,
+    );
+    
+    let created_at = env.bond(&bond).await.created_at;
+    env.warp_to(created_at + QUARTER).await;
+    env.record_coupon(&bond, 0).await.unwrap();
+    env.warp_to(created_at + QUARTER + 86_400 + 1).await;
+    env.trustee_set_trading_frozen(&bond, &trustee, Some(0), true).await.unwrap();
+    let bond_state = env.bond(&bond).await;
+    assert!(bond_state.trading_frozen);
+    assert_eq!(bond_state.pause_flags, 0);
+    assert_program_error(env.transfer_bond(&bond, &alice, &bob, 1).await, BtrustError::TradingFrozen);
+    
+    // Lifting the freeze needs no breach
+    env.trustee_set_trading_frozen(&bond, &trustee, None, false).await.unwrap();
+    env.deposit_yield(&bond, 0, 10 * COUPON_PER_BOND).await.unwrap();
+    env.transfer_bond(&bond, &alice, &bob, 1).await.unwrap();
+}
+
+async fn defaulted_bond_with_collateral(env: &mut TestEnv, trustee: &User, holders: &[&User]) -> TestBond {
+    let bond = trustee_bond(env, trustee).await;
+    for holder in holders {
+        env.purchase(&bond, holder, 5).await.unwrap();
+    }
+    env.deposit_collateral(&bond, 1_000).await.unwrap();
+    env.fund_sinking_fund(&bond, FACE_VALUE).await.unwrap();
+    
+    let created_at = env.bond(&bond).await.created_at;
+    env.warp_to(created_at + QUARTER).await;
+    env.record_coupon(&bond, 0).await.unwrap();
+    env.warp_to(created_at + QUARTER + 86_400 + 1).await;
+    env.trustee_declare_default(&bond, trustee, Some(0)).await.unwrap();
+    bond
+}
+
+#[tokio::test]
+async fn issuer_balances_are_swept_into_recovery() {
+    let mut env = TestEnv::new().await;
+    let trustee = env.create_user(0).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    let bob = env.create_user(100 * FACE_VALUE).await;
+    let bond = defaulted_bond_with_collateral(&mut env, &trustee, &[&alice, &bob]).await;
+    
+    assert_program_error(env.trustee_sweep(&bond, &alice).await, BtrustError::Unauthorized);
+    env.trustee_sweep(&bond, &trustee).await.unwrap();
+    assert_eq!(env.token_balance(&bond.vault(b"sinking_fund")).await, 0);
+    assert_eq!(env.token_balance(&bond.vault(b"redemption_vault")).await, FACE_VALUE);
+    assert_eq!(env.token_balance(&bond.collateral_vault).await, 1_000);
+    assert_program_error(env.trustee_sweep(&bond, &trustee).await, BtrustError::NothingToSweep);
+    
+    let alice_before = env.token_balance(&alice.payment).await;
+    env.claim_recovery(&bond, &alice, 5).await.unwrap();
+    assert_eq!(env.token_balance(&alice.payment).await - alice_before, FACE_VALUE / 2);
+    let alice_collateral = get_associated_token_address(&alice.pubkey(), &env.collateral_mint);
+    assert_eq!(env.token_balance(&alice_collateral).await, 500);
+}
+
+#[tokio::test]
+async fn sweeping_stops_once_recovery_claims_start() {
+    let mut env = TestEnv::new().await;
+    let trustee = env.create_user(0).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    let bob = env.create_user(100 * FACE_VALUE).await;
+    let bond = defaulted_bond_with_collateral(&mut env, &trustee, &[&alice, &bob]).await;
+    
+    env.claim_recovery(&bond, &alice, 5).await.unwrap();
+    assert_program_error(env.trustee_sweep(&bond, &trustee).await, BtrustError::RecoveryStarted);
+}
@@ -0,0 +1,151 @@
+mod common;
+
+use anchor_spl::token::spl_token;
+use btrust_bond::BtrustError;
+use common::*;
+
+#[tokio::test]
+async fn transferred_bonds_cannot_claim_the_same_coupon_twice() {
+    let mut env = TestEnv::new().await;
+    let args = env.bond_args();
+    let bond = env.create_bond(args).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    let bob = env.create_user(0).await;
+    
+    env.purchase(&bond, &alice, 10).await.unwrap();
+    
+    let created_at = env.bond(&bond).await.created_at;
+    env.warp_to(created_at + QUARTER + 1).await;
+    env.record_coupon(&bond, 0).await.unwrap();
+    env.deposit_yield(&bond, 0, 10 * COUPON_PER_BOND).await.unwrap();
+    
+    // Alice holds on the record date, so the coupon follows her, not the bonds
+    env.transfer_bond(&bond, &alice, &bob, 10).await.unwrap();
+    assert_eq!(env.position(&bond, &bob).await.quantity, 10);
+    assert_program_error(env.claim_yield(&bond, &bob).await, BtrustError::NoYieldToClaim);
+    
+    let before = env.token_balance(&alice.payment).await;
+    env.claim_yield(&bond, &alice).await.unwrap();
+    assert_eq!(env.token_balance(&alice.payment).await - before, 10 * COUPON_PER_BOND);
+    
+    env.transfer_bond(&bond, &bob, &alice, 10).await.unwrap();
+    assert_program_error(env.claim_yield(&bond, &alice).await, BtrustError::NoYieldToClaim);
+    assert_program_error(env.claim_yield(&bond, &bob).await, BtrustError::NoYieldToClaim);
+}
+
+#[tokio::test]
+async fn bond_accounts_reject_transfers_outside_the_program() {
+    let mut env = TestEnv::new().await;
+    let args = env.bond_args();
+    let bond = env.create_bond(args).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    let bob = env.create_user(0).await;
+    
+    env.purchase(&bond, &alice, 5).await.unwrap();
+    env.transfer_bond(&bond, &alice, &bob, 1).await.unwrap();
+    
+    let ix = spl_token::instruction::transfer(
+        &spl_token::ID,
+        &bond.bond_account(&alice.pubkey()),
+        &bond.bond_account(&bob.pubkey()),
+        &alice.pubkey(),
+        &[],
+        1,
+    )
+    .unwrap();
+    assert!(env.send(&[ix], &[&alice.keypair]).await.is_err());
+    assert_eq!(env.token_balance(&bond.bond_account(&alice.pubkey())).await, 4);
+}
+
+#[tokio::test]
+async fn transfers_wait_for_the_coupon_record() {
+    let mut env = TestEnv::new().await;
+    let args = env.bond_args();
+    let bond = env.create_bond(args).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    let bob = env.create_user(0).await;
+    
+    env.purchase(&bond, &alice, 4).await.unwrap();
+    let created_at = env.bond(&bond).await.created_at;
+    env.warp_to(created_at + QUARTER + 1).await;
+    
+    assert_program_error(env.transfer_bond(&bond, &alice, &bob, 4).await, BtrustError::CouponRecordPending);
+    env.record_coupon(&bond, 0).await.unwrap();
+    env.transfer_bond(&bond, &alice, &bob, 4).await.unwrap();
+}
+
+#[tokio::test]
+async fn coupons_follow_the_holders_on_the_record_date() {
+    let mut env = TestEnv::new().await;
+    let args = env.bond_args();
+    let bond = env.create_bond(args).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    let bob = env.create_user(0).await;
+    
+    env.purchase(&bond, &alice, 10).await.unwrap();
+    env.transfer_bond(&bond, &alice, &bob, 4).await.unwrap();
+    
+    let created_at = env.bond(&bond).await.created_at;
+    env.warp_to(created_at + QUARTER + 1).await;
+    env.record_coupon(&bond, 0).await.unwrap();
+    env.deposit_yield(&bond, 0, 10 * COUPON_PER_BOND).await.unwrap();
+    
+    let alice_before = env.token_balance(&alice.payment).await;
+    env.claim_yield(&bond, &alice).await.unwrap();
+    env.claim_yield(&bond, &bob).await.unwrap();
+    assert_eq!(env.token_balance(&alice.payment).await - alice_before, 6 * COUPON_PER_BOND);
+    assert_eq!(env.token_balance(&bob.payment).await, 4 * COUPON_PER_BOND);
+}
+
+#[tokio::test]
+async fn filled_orders_move_bonds_and_later_coupons() {
+    let mut env = TestEnv::new().await;
+    let bond = env.create_bond(env.bond_args()).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    let bob = env.create_user(100 * FACE_VALUE).await;
+    env.purchase(&bond, &alice, 10).await.unwrap();
+    
+    let order = env.create_sell_order(&bond, &alice, 4, FACE_VALUE).await;
+    assert_program_error(env.fill_order(&bond, &alice, &order, 1).await, BtrustError::InvalidRecipient);
+    env.fill_order(&bond, &bob, &order, 4).await.unwrap();
+    assert_eq!(env.position(&bond, &alice).await.quantity, 6);
+    assert_eq!(env.position(&bond, &bob).await.quantity, 4);
+    assert_eq!(env.token_balance(&bond.bond_account(&bob.pubkey())).await, 4);
+    
+    let created_at = env.bond(&bond).await.created_at;
+    env.warp_to(created_at + QUARTER + 1).await;
+    env.record_coupon(&bond, 0).await.unwrap();
+    env.deposit_yield(&bond, 0, 10 * COUPON_PER_BOND).await.unwrap();
+    
+    let before = env.token_balance(&bob.payment).await;
+    env.claim_yield(&bond, &bob).await.unwrap();
+    assert_eq!(env.token_balance(&bob.payment).await - before, 4 * COUPON_PER_BOND);
+}
+
+#[tokio::test]
+async fn funded_coupons_are_claimable_while_a_later_one_is_unfunded() {
+    let mut env = TestEnv::new().await;
+    let bond = env.create_bond(env.bond_args()).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    let bob = env.create_user(0).await;
+    env.purchase(&bond, &alice, 10).await.unwrap();
+    
+    let created_at = env.bond(&bond).await.created_at;
+    env.warp_to(created_at + QUARTER + 1).await;
+    env.record_coupon(&bond, 0).await.unwrap();
+    env.deposit_yield(&bond, 0, 10 * COUPON_PER_BOND).await.unwrap();
+    env.warp_to(created_at + 2 * QUARTER + 1).await;
+    env.record_coupon(&bond, 1).await.unwrap();
+    
+    let before = env.token_balance(&alice.payment).await;
+    env.claim_yield(&bond, &alice).await.unwrap();
+    assert_eq!(env.token_balance(&alice.payment).await - before, 10 * COUPON_PER_BOND);
+    
+    // The unfunded coupon stays with Alice after she sells, until it is funded
+    env.transfer_bond(&bond, &alice, &bob, 10).await.unwrap();
+    assert_program_error(env.claim_yield(&bond, &alice).await, BtrustError::NoYieldToClaim);
+    env.deposit_yield(&bond, 1, 10 * COUPON_PER_BOND).await.unwrap();
+    env.claim_yield(&bond, &alice).await.unwrap();
+    assert_eq!(env.token_balance(&alice.payment).await - before, 20 * COUPON_PER_BOND);
+    assert_program_error(env.claim_yield(&bond, &bob).await, BtrustError::NoYieldToClaim);
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use btrust_bond::{BtrustError, CreateBondArgs};
+use common::*;
+
+const YEAR: i64 = 31_536_000;
+
+fn zero_coupon_args(env: &TestEnv, discount_yield_bps: u64, term: i64) -> CreateBondArgs {
+    let mut args = env.bond_args();
+    args.is_zero_coupon = true;
+    args.coupon_rate_bps = 0;
+    args.discount_yield_bps = discount_yield_bps;
+    args.maturity_timestamp = env.now + term;
+    args
+}
+
+#[tokio::test]
+async fn zero_coupon_bonds_sell_at_a_discount_that_accretes_to_par() {
+    let mut env = TestEnv::new().await;
+    let args = zero_coupon_args(&env, 1_000, YEAR);
+    let bond = env.create_bond(args).await;
+    let alice = env.create_user(100 * FACE_VALUE).await;
+    
+    // A year out at 10%, par is discounted by 1.1
+    env.purchase(&bond, &alice, 10).await.unwrap();
+    assert_eq!(env.token_balance(&alice.payment).await, 100 * FACE_VALUE - 10 * (FACE_VALUE * 10 / 11));
+    
+    // Half a year out, by 1.05
+    env.warp_to(env.now + YEAR / 2).await;
+    let before = env.token_balance(&alice.payment).await;
+    env.purchase(&bond, &alice, 10).await.unwrap();
+    assert_eq!(before - env.token_balance(&alice.payment).await, 10 * (FACE_VALUE * 100 / 105));
+    
+    // Holders redeem at par
+    env.fund_redemption(&bond, 20 * FACE_VALUE).await.unwrap();
+    env.warp_to(env.now + YEAR / 2).await;
+    let before = env.token_balance(&alice.payment).await;
+    env.redeem_bond(&bond, &alice, 20).await.unwrap();
+    assert_eq!(env.token_balance(&alice.payment).await - before, 20 * FACE_VALUE);
+}
+
+#[tokio::test]
+async fn discounts_must_be_priceable_at_creation() {
+    let mut env = TestEnv::new().await;
+    let issuer = env.create_user(0).await;
+    
+    let mut args = zero_coupon_args(&env, 1_000, YEAR);
+    args.coupon_rate_bps = 100;
+    assert_program_error(env.try_create_bond(args, &issuer).await, BtrustError::InvalidCouponRate);
+    
+    let args = zero_coupon_args(&env, 0, YEAR);
+    assert_program_error(env.try_create_bond(args, &issuer).await, BtrustError::InvalidDiscountYield);
+    
+    // Long terms at high yields discount par to nothing, then overflow the compounding
+    let args = zero_coupon_args(&env, 10_000, 25 * YEAR);
+    assert_program_error(env.try_create_bond(args, &issuer).await, BtrustError::InvalidDiscountYield);
+    let args = zero_coupon_args(&env, 10_000, 60 * YEAR);
+    assert_program_error(env.try_create_bond(args, &issuer).await, BtrustError::InvalidDiscountYield);
+    
+    let args = zero_coupon_args(&env, 10_000, 10 * YEAR);
+    env.try_create_bond(args, &issuer).await.unwrap();
+}